@@ -0,0 +1,45 @@
+use {
+    crate::storage::StorageSpec,
+    clap::Args,
+    color_eyre::Result,
+    glues_core::export::{ExportOptions, export_markdown},
+    std::path::PathBuf,
+};
+
+#[derive(Clone, Args)]
+pub struct ExportArgs {
+    /// Storage to read notes from (e.g. `redb:notes.redb`)
+    #[arg(long)]
+    pub from: StorageSpec,
+
+    /// Empty or missing folder to write the Markdown files into
+    #[arg(long)]
+    pub to: PathBuf,
+
+    /// Prepend front matter with the note id, timestamps and tags
+    #[arg(long)]
+    pub front_matter: bool,
+}
+
+pub async fn run(args: ExportArgs) -> Result<()> {
+    color_eyre::install()?;
+
+    let ExportArgs {
+        from,
+        to,
+        front_matter,
+    } = args;
+
+    let mut source = from.open().await?;
+    let options = ExportOptions { front_matter };
+    let summary = export_markdown(&mut source, &to, options).await?;
+
+    println!(
+        "exported {} directories and {} notes to {}",
+        summary.directories,
+        summary.notes,
+        to.display()
+    );
+
+    Ok(())
+}
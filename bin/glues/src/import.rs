@@ -0,0 +1,57 @@
+use {
+    crate::storage::StorageSpec,
+    clap::Args,
+    color_eyre::Result,
+    glues_core::{
+        CoreBackend,
+        import::{import_markdown, import_obsidian},
+    },
+    std::path::PathBuf,
+};
+
+#[derive(Clone, Args)]
+pub struct ImportArgs {
+    /// Folder of Markdown or text files to import
+    pub dir: PathBuf,
+
+    /// Storage to add the notes to (e.g. `redb:notes.redb`)
+    #[arg(long)]
+    pub into: StorageSpec,
+
+    /// Treat the folder as an Obsidian vault and rewrite wiki links to note IDs
+    #[arg(long)]
+    pub obsidian: bool,
+}
+
+pub async fn run(args: ImportArgs) -> Result<()> {
+    color_eyre::install()?;
+
+    let ImportArgs {
+        dir,
+        into,
+        obsidian,
+    } = args;
+
+    let mut destination = into.open().await?;
+    let root_id = destination.root_id();
+    let summary = if obsidian {
+        import_obsidian(&mut destination, &dir, root_id).await?
+    } else {
+        import_markdown(&mut destination, &dir, root_id).await?
+    };
+
+    println!(
+        "imported {} directories and {} notes from {}",
+        summary.directories,
+        summary.notes,
+        dir.display()
+    );
+    if summary.links > 0 {
+        println!("rewrote {} wiki links to note IDs", summary.links);
+    }
+    if summary.skipped > 0 {
+        println!("skipped {} unsupported files", summary.skipped);
+    }
+
+    Ok(())
+}
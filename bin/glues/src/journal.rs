@@ -0,0 +1,49 @@
+use {
+    crate::storage::StorageSpec, chrono::NaiveDate, clap::Args, color_eyre::Result,
+    glues_core::journal,
+};
+
+#[derive(Clone, Args)]
+pub struct JournalArgs {
+    /// Storage holding the journal (e.g. `redb:notes.redb`)
+    #[arg(long)]
+    pub storage: StorageSpec,
+
+    /// Day of the note as `YYYY-MM-DD`, today when omitted
+    #[arg(long)]
+    pub date: Option<NaiveDate>,
+
+    /// Add a line to the end of the note instead of printing it
+    #[arg(long, value_name = "TEXT")]
+    pub append: Option<String>,
+}
+
+pub async fn run(args: JournalArgs) -> Result<()> {
+    color_eyre::install()?;
+
+    let JournalArgs {
+        storage,
+        date,
+        append,
+    } = args;
+
+    let mut db = storage.open().await?;
+    let date = date.unwrap_or_else(journal::today);
+    let note = journal::find_or_create(&mut db, date).await?;
+    let content = db.fetch_note_content(note.id.clone()).await?;
+
+    match append {
+        Some(line) => {
+            let content = match content.trim_end() {
+                "" => line,
+                content => format!("{content}\n{line}"),
+            };
+            db.update_note_content(note.id, content, None).await?;
+
+            println!("appended to {}", journal::note_path(date));
+        }
+        None => println!("{content}"),
+    }
+
+    Ok(())
+}
@@ -1,8 +1,18 @@
+mod export;
+mod import;
+mod journal;
+mod migrate;
+mod storage;
+
 use {
     clap::{Parser, Subcommand},
     color_eyre::Result,
+    export::ExportArgs,
     glues_server::ServerArgs,
     glues_tui::cli,
+    import::ImportArgs,
+    journal::JournalArgs,
+    migrate::MigrateArgs,
 };
 
 #[derive(Parser)]
@@ -22,6 +32,14 @@ struct Cli {
 enum Command {
     /// Run the Glues proxy server
     Server(ServerArgs),
+    /// Copy every note from one storage into another, keeping IDs and timestamps
+    Migrate(MigrateArgs),
+    /// Write every note to a folder of Markdown files
+    Export(ExportArgs),
+    /// Add a folder of Markdown or text files to a notebook
+    Import(ImportArgs),
+    /// Print or add to a day's journal note, creating it when missing
+    Journal(JournalArgs),
 }
 
 #[tokio::main]
@@ -30,6 +48,10 @@ async fn main() -> Result<()> {
 
     match command {
         Some(Command::Server(args)) => glues_server::run(args).await,
+        Some(Command::Migrate(args)) => migrate::run(args).await,
+        Some(Command::Export(args)) => export::run(args).await,
+        Some(Command::Import(args)) => import::run(args).await,
+        Some(Command::Journal(args)) => journal::run(args).await,
         None => cli::run().await,
     }
 }
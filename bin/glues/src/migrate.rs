@@ -0,0 +1,36 @@
+use {
+    crate::storage::StorageSpec,
+    clap::Args,
+    color_eyre::{Result, eyre::eyre},
+};
+
+#[derive(Clone, Args)]
+pub struct MigrateArgs {
+    /// Storage to read notes from (e.g. `redb:notes.redb`)
+    #[arg(long)]
+    pub from: StorageSpec,
+
+    /// Empty storage to copy notes into (e.g. `git:./notes,origin,main`)
+    #[arg(long)]
+    pub to: StorageSpec,
+}
+
+pub async fn run(args: MigrateArgs) -> Result<()> {
+    color_eyre::install()?;
+
+    let MigrateArgs { from, to } = args;
+    if from == to {
+        return Err(eyre!("source and destination storages must differ"));
+    }
+
+    let mut source = from.open().await?;
+    let mut destination = to.open().await?;
+    let summary = destination.migrate_from(&mut source).await?;
+
+    println!(
+        "migrated {} directories and {} notes",
+        summary.directories, summary.notes
+    );
+
+    Ok(())
+}
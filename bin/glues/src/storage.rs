@@ -0,0 +1,114 @@
+use {color_eyre::Result, glues_core::backend::local::Db, std::str::FromStr};
+
+/// Local storage location given on the command line as `<kind>:<target>`.
+///
+/// Supported forms are `file:<path>`, `redb:<path>`,
+/// `git:<path>,<remote>,<branch>` and `mongo:<conn_str>,<db_name>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageSpec {
+    File {
+        path: String,
+    },
+    Redb {
+        path: String,
+    },
+    Git {
+        path: String,
+        remote: String,
+        branch: String,
+    },
+    Mongo {
+        conn_str: String,
+        db_name: String,
+    },
+}
+
+impl StorageSpec {
+    pub async fn open(&self) -> Result<Db> {
+        let db = match self {
+            Self::File { path } => Db::file(path).await?,
+            Self::Redb { path } => Db::redb(path).await?,
+            Self::Git {
+                path,
+                remote,
+                branch,
+            } => Db::git(path, remote.clone(), branch.clone()).await?,
+            Self::Mongo { conn_str, db_name } => Db::mongo(conn_str, db_name).await?,
+        };
+
+        Ok(db)
+    }
+}
+
+impl FromStr for StorageSpec {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let (kind, target) = value
+            .split_once(':')
+            .ok_or_else(|| format!("expected <kind>:<target>, got `{value}`"))?;
+        if target.is_empty() {
+            return Err(format!("missing target for `{kind}` storage"));
+        }
+
+        match kind {
+            "file" => Ok(Self::File {
+                path: target.to_owned(),
+            }),
+            "redb" => Ok(Self::Redb {
+                path: target.to_owned(),
+            }),
+            "git" => match target.rsplitn(3, ',').collect::<Vec<_>>()[..] {
+                [branch, remote, path] => Ok(Self::Git {
+                    path: path.to_owned(),
+                    remote: remote.to_owned(),
+                    branch: branch.to_owned(),
+                }),
+                _ => Err("git storage expects `git:<path>,<remote>,<branch>`".to_owned()),
+            },
+            "mongo" => match target.rsplit_once(',') {
+                Some((conn_str, db_name)) => Ok(Self::Mongo {
+                    conn_str: conn_str.to_owned(),
+                    db_name: db_name.to_owned(),
+                }),
+                None => Err("mongo storage expects `mongo:<conn_str>,<db_name>`".to_owned()),
+            },
+            _ => Err(format!(
+                "unknown storage `{kind}` (expected file, redb, git or mongo)"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageSpec;
+
+    #[test]
+    fn parses_storage_specs() {
+        assert_eq!(
+            "redb:notes.redb".parse(),
+            Ok(StorageSpec::Redb {
+                path: "notes.redb".to_owned()
+            })
+        );
+        assert_eq!(
+            "git:./my,notes,origin,main".parse(),
+            Ok(StorageSpec::Git {
+                path: "./my,notes".to_owned(),
+                remote: "origin".to_owned(),
+                branch: "main".to_owned(),
+            })
+        );
+        assert_eq!(
+            "mongo:mongodb://localhost:27017,glues".parse(),
+            Ok(StorageSpec::Mongo {
+                conn_str: "mongodb://localhost:27017".to_owned(),
+                db_name: "glues".to_owned(),
+            })
+        );
+        assert!("memory".parse::<StorageSpec>().is_err());
+        assert!("git:./notes,origin".parse::<StorageSpec>().is_err());
+        assert!("csv:notes".parse::<StorageSpec>().is_err());
+    }
+}
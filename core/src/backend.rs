@@ -1,7 +1,8 @@
 use crate::{
     Error, Result,
-    data::{Directory, Note},
-    types::{DirectoryId, NoteId},
+    backend::proxy::ChangeFeed,
+    data::{Directory, Note, NoteRevision, NoteSearchResult, Tag, Trash},
+    types::{DirectoryId, NoteId, RevisionId},
 };
 use async_trait::async_trait;
 
@@ -52,6 +53,11 @@ impl SyncJob {
                 }
 
                 let response = request.send()?;
+                if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+                    return Err(Error::Unauthorized(
+                        "proxy server rejected the authentication token".to_owned(),
+                    ));
+                }
                 if !response.status().is_success() {
                     return Err(Error::Proxy(format!(
                         "sync failed with status {}",
@@ -66,8 +72,8 @@ impl SyncJob {
                             "invalid sync response payload".to_owned(),
                         ));
                     }
-                    ProxyResponse::Err(message) => {
-                        return Err(Error::Proxy(message));
+                    ProxyResponse::Err(error) => {
+                        return Err(error.into());
                     }
                 }
             }
@@ -92,17 +98,63 @@ pub trait CoreBackend: Send {
     ) -> Result<()>;
     async fn rename_directory(&mut self, directory_id: DirectoryId, name: String) -> Result<()>;
 
+    async fn fetch_note(&mut self, note_id: NoteId) -> Result<Note>;
     async fn fetch_notes(&mut self, directory_id: DirectoryId) -> Result<Vec<Note>>;
     async fn fetch_note_content(&mut self, note_id: NoteId) -> Result<String>;
     async fn add_note(&mut self, directory_id: DirectoryId, name: String) -> Result<Note>;
     async fn remove_note(&mut self, note_id: NoteId) -> Result<()>;
-    async fn rename_note(&mut self, note_id: NoteId, name: String) -> Result<()>;
-    async fn update_note_content(&mut self, note_id: NoteId, content: String) -> Result<()>;
-    async fn move_note(&mut self, note_id: NoteId, directory_id: DirectoryId) -> Result<()>;
+
+    /// The note-changing methods below take the `updated_at` of the note as
+    /// the caller last read it; when it no longer matches, another client got
+    /// there first and they fail with [`Error::Conflict`]. `None` skips the
+    /// check. They return the note as changed.
+    async fn rename_note(
+        &mut self,
+        note_id: NoteId,
+        name: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note>;
+    async fn update_note_content(
+        &mut self,
+        note_id: NoteId,
+        content: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note>;
+    async fn move_note(
+        &mut self,
+        note_id: NoteId,
+        directory_id: DirectoryId,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note>;
+
+    async fn list_note_revisions(&mut self, note_id: NoteId) -> Result<Vec<NoteRevision>>;
+    async fn fetch_note_revision(
+        &mut self,
+        note_id: NoteId,
+        revision_id: RevisionId,
+    ) -> Result<String>;
+
+    async fn search_notes(&mut self, query: String) -> Result<Vec<NoteSearchResult>>;
+    /// Notes outside the trash whose content links to the note, by its ID or
+    /// by its name; see [`crate::link`].
+    async fn fetch_backlinks(&mut self, note_id: NoteId) -> Result<Vec<Note>>;
+
+    async fn fetch_tags(&mut self) -> Result<Vec<Tag>>;
+    async fn add_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()>;
+    async fn remove_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()>;
+    async fn fetch_notes_by_tag(&mut self, name: String) -> Result<Vec<Note>>;
+
+    async fn fetch_trash(&mut self) -> Result<Trash>;
+    async fn restore_note(&mut self, note_id: NoteId) -> Result<Note>;
+    async fn restore_directory(&mut self, directory_id: DirectoryId) -> Result<Directory>;
+    async fn purge(&mut self) -> Result<()>;
 
     async fn log(&mut self, category: String, message: String) -> Result<()>;
 
     fn sync_job(&self) -> Option<SyncJob>;
+
+    /// Changes made by other clients, for backends shared through a server.
+    fn change_feed(&self) -> Option<ChangeFeed>;
 }
 
 #[async_trait]
@@ -139,6 +191,10 @@ impl CoreBackend for Box<dyn CoreBackend + Send> {
         (**self).rename_directory(directory_id, name).await
     }
 
+    async fn fetch_note(&mut self, note_id: NoteId) -> Result<Note> {
+        (**self).fetch_note(note_id).await
+    }
+
     async fn fetch_notes(&mut self, directory_id: DirectoryId) -> Result<Vec<Note>> {
         (**self).fetch_notes(directory_id).await
     }
@@ -155,16 +211,89 @@ impl CoreBackend for Box<dyn CoreBackend + Send> {
         (**self).remove_note(note_id).await
     }
 
-    async fn rename_note(&mut self, note_id: NoteId, name: String) -> Result<()> {
-        (**self).rename_note(note_id, name).await
+    async fn rename_note(
+        &mut self,
+        note_id: NoteId,
+        name: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        (**self)
+            .rename_note(note_id, name, expected_updated_at)
+            .await
     }
 
-    async fn update_note_content(&mut self, note_id: NoteId, content: String) -> Result<()> {
-        (**self).update_note_content(note_id, content).await
+    async fn update_note_content(
+        &mut self,
+        note_id: NoteId,
+        content: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        (**self)
+            .update_note_content(note_id, content, expected_updated_at)
+            .await
     }
 
-    async fn move_note(&mut self, note_id: NoteId, directory_id: DirectoryId) -> Result<()> {
-        (**self).move_note(note_id, directory_id).await
+    async fn move_note(
+        &mut self,
+        note_id: NoteId,
+        directory_id: DirectoryId,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        (**self)
+            .move_note(note_id, directory_id, expected_updated_at)
+            .await
+    }
+
+    async fn list_note_revisions(&mut self, note_id: NoteId) -> Result<Vec<NoteRevision>> {
+        (**self).list_note_revisions(note_id).await
+    }
+
+    async fn fetch_note_revision(
+        &mut self,
+        note_id: NoteId,
+        revision_id: RevisionId,
+    ) -> Result<String> {
+        (**self).fetch_note_revision(note_id, revision_id).await
+    }
+
+    async fn search_notes(&mut self, query: String) -> Result<Vec<NoteSearchResult>> {
+        (**self).search_notes(query).await
+    }
+
+    async fn fetch_backlinks(&mut self, note_id: NoteId) -> Result<Vec<Note>> {
+        (**self).fetch_backlinks(note_id).await
+    }
+
+    async fn fetch_tags(&mut self) -> Result<Vec<Tag>> {
+        (**self).fetch_tags().await
+    }
+
+    async fn add_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()> {
+        (**self).add_note_tag(note_id, name).await
+    }
+
+    async fn remove_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()> {
+        (**self).remove_note_tag(note_id, name).await
+    }
+
+    async fn fetch_notes_by_tag(&mut self, name: String) -> Result<Vec<Note>> {
+        (**self).fetch_notes_by_tag(name).await
+    }
+
+    async fn fetch_trash(&mut self) -> Result<Trash> {
+        (**self).fetch_trash().await
+    }
+
+    async fn restore_note(&mut self, note_id: NoteId) -> Result<Note> {
+        (**self).restore_note(note_id).await
+    }
+
+    async fn restore_directory(&mut self, directory_id: DirectoryId) -> Result<Directory> {
+        (**self).restore_directory(directory_id).await
+    }
+
+    async fn purge(&mut self) -> Result<()> {
+        (**self).purge().await
     }
 
     async fn log(&mut self, category: String, message: String) -> Result<()> {
@@ -174,9 +303,14 @@ impl CoreBackend for Box<dyn CoreBackend + Send> {
     fn sync_job(&self) -> Option<SyncJob> {
         (**self).sync_job()
     }
+
+    fn change_feed(&self) -> Option<ChangeFeed> {
+        (**self).change_feed()
+    }
 }
 
 pub type BackendBox = Box<dyn CoreBackend + Send>;
 
 pub mod local;
+pub mod markdown;
 pub mod proxy;
@@ -1,7 +1,16 @@
 mod native;
 pub use native::*;
 
+pub use migrate::MigrationSummary;
+pub(crate) use {search::find_snippets, tag::normalize as normalize_tag};
+
 mod core_backend;
 mod directory;
+mod link;
 mod log;
+mod migrate;
 mod note;
+mod revision;
+mod search;
+mod tag;
+mod trash;
@@ -1,8 +1,8 @@
 use crate::{
     Result,
-    backend::{CoreBackend, SyncJob},
-    data::{Directory, Note},
-    types::{DirectoryId, NoteId},
+    backend::{CoreBackend, SyncJob, proxy::ChangeFeed},
+    data::{Directory, Note, NoteRevision, NoteSearchResult, Tag, Trash},
+    types::{DirectoryId, NoteId, RevisionId},
 };
 use async_trait::async_trait;
 
@@ -42,6 +42,10 @@ impl CoreBackend for Db {
         Db::rename_directory(self, directory_id, name).await
     }
 
+    async fn fetch_note(&mut self, note_id: NoteId) -> Result<Note> {
+        Db::fetch_note(self, note_id).await
+    }
+
     async fn fetch_notes(&mut self, directory_id: DirectoryId) -> Result<Vec<Note>> {
         Db::fetch_notes(self, directory_id).await
     }
@@ -58,16 +62,83 @@ impl CoreBackend for Db {
         Db::remove_note(self, note_id).await
     }
 
-    async fn rename_note(&mut self, note_id: NoteId, name: String) -> Result<()> {
-        Db::rename_note(self, note_id, name).await
+    async fn rename_note(
+        &mut self,
+        note_id: NoteId,
+        name: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        Db::rename_note(self, note_id, name, expected_updated_at).await
+    }
+
+    async fn update_note_content(
+        &mut self,
+        note_id: NoteId,
+        content: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        Db::update_note_content(self, note_id, content, expected_updated_at).await
+    }
+
+    async fn move_note(
+        &mut self,
+        note_id: NoteId,
+        directory_id: DirectoryId,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        Db::move_note(self, note_id, directory_id, expected_updated_at).await
+    }
+
+    async fn list_note_revisions(&mut self, note_id: NoteId) -> Result<Vec<NoteRevision>> {
+        Db::list_note_revisions(self, note_id).await
+    }
+
+    async fn fetch_note_revision(
+        &mut self,
+        note_id: NoteId,
+        revision_id: RevisionId,
+    ) -> Result<String> {
+        Db::fetch_note_revision(self, note_id, revision_id).await
+    }
+
+    async fn search_notes(&mut self, query: String) -> Result<Vec<NoteSearchResult>> {
+        Db::search_notes(self, query).await
     }
 
-    async fn update_note_content(&mut self, note_id: NoteId, content: String) -> Result<()> {
-        Db::update_note_content(self, note_id, content).await
+    async fn fetch_backlinks(&mut self, note_id: NoteId) -> Result<Vec<Note>> {
+        Db::fetch_backlinks(self, note_id).await
     }
 
-    async fn move_note(&mut self, note_id: NoteId, directory_id: DirectoryId) -> Result<()> {
-        Db::move_note(self, note_id, directory_id).await
+    async fn fetch_tags(&mut self) -> Result<Vec<Tag>> {
+        Db::fetch_tags(self).await
+    }
+
+    async fn add_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()> {
+        Db::add_note_tag(self, note_id, name).await
+    }
+
+    async fn remove_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()> {
+        Db::remove_note_tag(self, note_id, name).await
+    }
+
+    async fn fetch_notes_by_tag(&mut self, name: String) -> Result<Vec<Note>> {
+        Db::fetch_notes_by_tag(self, name).await
+    }
+
+    async fn fetch_trash(&mut self) -> Result<Trash> {
+        Db::fetch_trash(self).await
+    }
+
+    async fn restore_note(&mut self, note_id: NoteId) -> Result<Note> {
+        Db::restore_note(self, note_id).await
+    }
+
+    async fn restore_directory(&mut self, directory_id: DirectoryId) -> Result<Directory> {
+        Db::restore_directory(self, directory_id).await
+    }
+
+    async fn purge(&mut self) -> Result<()> {
+        Db::purge(self).await
     }
 
     async fn log(&mut self, category: String, message: String) -> Result<()> {
@@ -77,4 +148,8 @@ impl CoreBackend for Db {
     fn sync_job(&self) -> Option<SyncJob> {
         Db::sync_job(self)
     }
+
+    fn change_feed(&self) -> Option<ChangeFeed> {
+        None
+    }
 }
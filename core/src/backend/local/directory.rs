@@ -1,7 +1,6 @@
 use {
     super::{Db, Execute},
     crate::{Result, data::Directory, types::DirectoryId},
-    async_recursion::async_recursion,
     gluesql::{
         FromGlueRow,
         core::{
@@ -13,7 +12,7 @@ use {
 };
 
 #[derive(FromGlueRow)]
-struct DirectoryRow {
+pub(super) struct DirectoryRow {
     id: String,
     parent_id: Option<String>,
     name: String,
@@ -59,7 +58,11 @@ impl Db {
     pub async fn fetch_directories(&mut self, parent_id: DirectoryId) -> Result<Vec<Directory>> {
         let directories = table("Directory")
             .select()
-            .filter(col("parent_id").eq(uuid(parent_id)))
+            .filter(
+                col("parent_id")
+                    .eq(uuid(parent_id))
+                    .and(col("deleted_at").is_null()),
+            )
             .project(vec!["id", "parent_id", "name", "created_at", "updated_at"])
             .execute(&mut self.storage)
             .await?
@@ -87,26 +90,18 @@ impl Db {
         self.fetch_directory(id).await
     }
 
-    #[async_recursion]
     pub async fn remove_directory(&mut self, directory_id: DirectoryId) -> Result<()> {
-        table("Note")
-            .delete()
-            .filter(col("directory_id").eq(uuid(directory_id.clone())))
-            .execute(&mut self.storage)
-            .await?;
-
-        let directories = self.fetch_directories(directory_id.clone()).await?;
-        for directory in directories {
-            self.remove_directory(directory.id).await?;
-        }
-
         table("Directory")
-            .delete()
-            .filter(col("id").eq(uuid(directory_id)))
+            .update()
+            .filter(col("id").eq(uuid(directory_id.clone())))
+            .set("deleted_at", now())
             .execute(&mut self.storage)
             .await?;
 
-        Ok(())
+        let deleted_at = self
+            .fetch_directory_deleted_at(directory_id.clone())
+            .await?;
+        self.trash_children(directory_id, &deleted_at).await
     }
 
     pub async fn move_directory(
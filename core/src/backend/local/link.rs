@@ -0,0 +1,75 @@
+use {
+    super::{Db, Execute, note::NoteRow},
+    crate::{Result, data::Note, link, types::NoteId},
+    gluesql::core::{
+        ast_builder::{col, table, text, uuid},
+        row_conversion::SelectExt,
+    },
+};
+
+impl Db {
+    pub async fn fetch_backlinks(&mut self, note_id: NoteId) -> Result<Vec<Note>> {
+        let note = self.fetch_note(note_id.clone()).await?;
+        let keys = vec![text(note.id.to_lowercase()), text(note.name.to_lowercase())];
+
+        let mut notes = table("Note")
+            .select()
+            .filter(
+                col("deleted_at")
+                    .is_null()
+                    .and(col("id").neq(uuid(note_id)))
+                    .and(
+                        col("id").in_list(
+                            table("NoteLink")
+                                .select()
+                                .filter(col("target").in_list(keys))
+                                .project("note_id"),
+                        ),
+                    ),
+            )
+            .project(vec![
+                "id",
+                "directory_id",
+                "name",
+                "created_at",
+                "updated_at",
+            ])
+            .order_by("name")
+            .execute(&mut self.storage)
+            .await?
+            .rows_as::<NoteRow>()?
+            .into_iter()
+            .map(Note::from)
+            .collect::<Vec<_>>();
+
+        self.attach_tags(&mut notes).await?;
+
+        Ok(notes)
+    }
+
+    /// Replaces the links indexed for the note with those in `content`.
+    pub(super) async fn index_links(&mut self, note_id: NoteId, content: &str) -> Result<()> {
+        table("NoteLink")
+            .delete()
+            .filter(col("note_id").eq(uuid(note_id.clone())))
+            .execute(&mut self.storage)
+            .await?;
+
+        let rows = link::keys(content)
+            .into_iter()
+            .map(|key| vec![uuid(note_id.clone()), text(key)])
+            .collect::<Vec<_>>();
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        table("NoteLink")
+            .insert()
+            .columns(vec!["note_id", "target"])
+            .values(rows)
+            .execute(&mut self.storage)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use {
+    super::{Db, Execute},
+    crate::{
+        Error, Result,
+        backend::CoreBackend,
+        data::{Directory, Note},
+    },
+    gluesql::core::ast_builder::{col, null, table, text, timestamp, uuid},
+};
+
+/// Counts of the entries copied by [`Db::migrate_from`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MigrationSummary {
+    pub directories: usize,
+    pub notes: usize,
+}
+
+impl Db {
+    /// Copies the whole tree of `source` into this storage, keeping the
+    /// original IDs, names, contents, tags and timestamps.
+    ///
+    /// The destination must be empty apart from its root directory, which is
+    /// replaced by the root of `source`.
+    pub async fn migrate_from<B>(&mut self, source: &mut B) -> Result<MigrationSummary>
+    where
+        B: CoreBackend + ?Sized,
+    {
+        let root_id = self.root_id.clone();
+        if !self.fetch_directories(root_id.clone()).await?.is_empty()
+            || !self.fetch_notes(root_id.clone()).await?.is_empty()
+        {
+            return Err(Error::InvalidState(
+                "destination storage is not empty".to_owned(),
+            ));
+        }
+
+        let root = source.fetch_directory(source.root_id()).await?;
+        table("Directory")
+            .delete()
+            .filter(col("id").eq(uuid(root_id)))
+            .execute(&mut self.storage)
+            .await?;
+        self.insert_directory(&root, true).await?;
+        self.root_id = root.id.clone();
+
+        let mut summary = MigrationSummary::default();
+        let mut pending = vec![root.id];
+        while let Some(directory_id) = pending.pop() {
+            for note in source.fetch_notes(directory_id.clone()).await? {
+                let content = source.fetch_note_content(note.id.clone()).await?;
+                self.insert_note(&note, content).await?;
+                for tag in note.tags {
+                    self.add_note_tag(note.id.clone(), tag).await?;
+                }
+                summary.notes += 1;
+            }
+
+            for directory in source.fetch_directories(directory_id).await? {
+                self.insert_directory(&directory, false).await?;
+                pending.push(directory.id);
+                summary.directories += 1;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    async fn insert_directory(&mut self, directory: &Directory, is_root: bool) -> Result<()> {
+        let parent_id = if is_root {
+            null()
+        } else {
+            uuid(directory.parent_id.clone())
+        };
+
+        table("Directory")
+            .insert()
+            .columns(vec!["id", "parent_id", "name", "created_at", "updated_at"])
+            .values(vec![vec![
+                uuid(directory.id.clone()),
+                parent_id,
+                text(directory.name.clone()),
+                timestamp(directory.created_at.clone()),
+                timestamp(directory.updated_at.clone()),
+            ]])
+            .execute(&mut self.storage)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn insert_note(&mut self, note: &Note, content: String) -> Result<()> {
+        table("Note")
+            .insert()
+            .columns(vec![
+                "id",
+                "directory_id",
+                "name",
+                "created_at",
+                "updated_at",
+                "content",
+            ])
+            .values(vec![vec![
+                uuid(note.id.clone()),
+                uuid(note.directory_id.clone()),
+                text(note.name.clone()),
+                timestamp(note.created_at.clone()),
+                timestamp(note.updated_at.clone()),
+                text(content),
+            ]])
+            .execute(&mut self.storage)
+            .await?;
+
+        Ok(())
+    }
+}
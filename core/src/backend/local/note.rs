@@ -1,7 +1,7 @@
 use {
     super::{Db, Execute},
     crate::{
-        Result,
+        Error, Result,
         data::Note,
         types::{DirectoryId, NoteId},
     },
@@ -16,7 +16,7 @@ use {
 };
 
 #[derive(FromGlueRow)]
-struct NoteRow {
+pub(super) struct NoteRow {
     id: String,
     directory_id: String,
     name: String,
@@ -32,6 +32,7 @@ impl From<NoteRow> for Note {
             name: row.name,
             created_at: row.created_at,
             updated_at: row.updated_at,
+            tags: Vec::new(),
         }
     }
 }
@@ -54,10 +55,36 @@ impl Db {
         Ok(row.content)
     }
 
+    /// Fetches a single note, including one that is in the trash.
+    pub async fn fetch_note(&mut self, note_id: NoteId) -> Result<Note> {
+        let mut note = table("Note")
+            .select()
+            .filter(col("id").eq(uuid(note_id)))
+            .project(vec![
+                "id",
+                "directory_id",
+                "name",
+                "created_at",
+                "updated_at",
+            ])
+            .execute(&mut self.storage)
+            .await?
+            .one_as::<NoteRow>()
+            .map(Note::from)?;
+
+        self.attach_tags(std::slice::from_mut(&mut note)).await?;
+
+        Ok(note)
+    }
+
     pub async fn fetch_notes(&mut self, directory_id: DirectoryId) -> Result<Vec<Note>> {
-        let notes = table("Note")
+        let mut notes = table("Note")
             .select()
-            .filter(col("directory_id").eq(uuid(directory_id)))
+            .filter(
+                col("directory_id")
+                    .eq(uuid(directory_id))
+                    .and(col("deleted_at").is_null()),
+            )
             .project(vec![
                 "id",
                 "directory_id",
@@ -70,7 +97,9 @@ impl Db {
             .rows_as::<NoteRow>()?
             .into_iter()
             .map(Note::from)
-            .collect();
+            .collect::<Vec<_>>();
+
+        self.attach_tags(&mut notes).await?;
 
         Ok(notes)
     }
@@ -102,47 +131,93 @@ impl Db {
 
     pub async fn remove_note(&mut self, note_id: NoteId) -> Result<()> {
         table("Note")
-            .delete()
+            .update()
             .filter(col("id").eq(uuid(note_id)))
+            .set("deleted_at", now())
             .execute(&mut self.storage)
             .await?;
 
         Ok(())
     }
 
-    pub async fn update_note_content(&mut self, note_id: NoteId, content: String) -> Result<()> {
+    pub async fn update_note_content(
+        &mut self,
+        note_id: NoteId,
+        content: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        self.check_updated_at(&note_id, expected_updated_at).await?;
+        self.record_revision(note_id.clone(), &content).await?;
+        self.index_links(note_id.clone(), &content).await?;
+
         table("Note")
             .update()
-            .filter(col("id").eq(uuid(note_id)))
+            .filter(col("id").eq(uuid(note_id.clone())))
             .set("content", text(content))
             .set("updated_at", now())
             .execute(&mut self.storage)
             .await?;
 
-        Ok(())
+        self.fetch_note(note_id).await
     }
 
-    pub async fn rename_note(&mut self, note_id: NoteId, name: String) -> Result<()> {
+    pub async fn rename_note(
+        &mut self,
+        note_id: NoteId,
+        name: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        self.check_updated_at(&note_id, expected_updated_at).await?;
+
         table("Note")
             .update()
-            .filter(col("id").eq(uuid(note_id)))
+            .filter(col("id").eq(uuid(note_id.clone())))
             .set("name", text(name))
             .set("updated_at", now())
             .execute(&mut self.storage)
             .await?;
 
-        Ok(())
+        self.fetch_note(note_id).await
     }
 
-    pub async fn move_note(&mut self, note_id: NoteId, directory_id: DirectoryId) -> Result<()> {
+    pub async fn move_note(
+        &mut self,
+        note_id: NoteId,
+        directory_id: DirectoryId,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        self.check_updated_at(&note_id, expected_updated_at).await?;
+
         table("Note")
             .update()
-            .filter(col("id").eq(uuid(note_id)))
+            .filter(col("id").eq(uuid(note_id.clone())))
             .set("directory_id", uuid(directory_id))
             .set("updated_at", now())
             .execute(&mut self.storage)
             .await?;
 
+        self.fetch_note(note_id).await
+    }
+
+    /// Fails with [`Error::Conflict`] when the note was changed since the
+    /// caller read it at `expected_updated_at`.
+    async fn check_updated_at(
+        &mut self,
+        note_id: &NoteId,
+        expected_updated_at: Option<String>,
+    ) -> Result<()> {
+        let Some(expected) = expected_updated_at else {
+            return Ok(());
+        };
+
+        let note = self.fetch_note(note_id.clone()).await?;
+        if note.updated_at != expected {
+            return Err(Error::Conflict(format!(
+                "note '{}' was changed at {} after it was read at {expected}",
+                note.name, note.updated_at
+            )));
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,169 @@
+use {
+    super::{Db, Execute},
+    crate::{
+        Result,
+        data::NoteRevision,
+        types::{NoteId, RevisionId},
+    },
+    gluesql::{
+        FromGlueRow,
+        core::{
+            ast_builder::{col, table, text, timestamp, uuid},
+            row_conversion::SelectExt,
+        },
+    },
+    uuid::Uuid,
+};
+
+/// Number of revisions kept per note; older ones are dropped first.
+const MAX_REVISIONS: usize = 100;
+
+#[derive(FromGlueRow)]
+struct RevisionRow {
+    id: String,
+    note_id: String,
+    created_at: String,
+}
+
+impl From<RevisionRow> for NoteRevision {
+    fn from(row: RevisionRow) -> Self {
+        Self {
+            id: row.id,
+            note_id: row.note_id,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(FromGlueRow)]
+struct RevisionContentRow {
+    content: String,
+}
+
+#[derive(FromGlueRow)]
+struct CurrentContentRow {
+    content: String,
+    updated_at: String,
+}
+
+impl Db {
+    pub async fn list_note_revisions(&mut self, note_id: NoteId) -> Result<Vec<NoteRevision>> {
+        let revisions = table("NoteRevision")
+            .select()
+            .filter(col("note_id").eq(uuid(note_id)))
+            .project(vec!["id", "note_id", "created_at"])
+            .order_by("created_at DESC")
+            .execute(&mut self.storage)
+            .await?
+            .rows_as::<RevisionRow>()?
+            .into_iter()
+            .map(NoteRevision::from)
+            .collect();
+
+        Ok(revisions)
+    }
+
+    pub async fn fetch_note_revision(
+        &mut self,
+        note_id: NoteId,
+        revision_id: RevisionId,
+    ) -> Result<String> {
+        let row = table("NoteRevision")
+            .select()
+            .filter(
+                col("id")
+                    .eq(uuid(revision_id))
+                    .and(col("note_id").eq(uuid(note_id))),
+            )
+            .project("content")
+            .execute(&mut self.storage)
+            .await?
+            .one_as::<RevisionContentRow>()?;
+
+        Ok(row.content)
+    }
+
+    /// Stores `content` as the newest revision of the note unless it matches
+    /// the latest one. The first revision of a note also keeps the content it
+    /// had before, so the original text can always be restored.
+    pub(super) async fn record_revision(&mut self, note_id: NoteId, content: &str) -> Result<()> {
+        let revisions = self.list_note_revisions(note_id.clone()).await?;
+        match revisions.first() {
+            Some(latest) => {
+                if self
+                    .fetch_note_revision(note_id.clone(), latest.id.clone())
+                    .await?
+                    == content
+                {
+                    return Ok(());
+                }
+            }
+            None => {
+                let current = table("Note")
+                    .select()
+                    .filter(col("id").eq(uuid(note_id.clone())))
+                    .project(vec!["content", "updated_at"])
+                    .execute(&mut self.storage)
+                    .await?
+                    .one_as::<CurrentContentRow>()?;
+
+                if current.content == content {
+                    return Ok(());
+                } else if !current.content.is_empty() {
+                    self.insert_revision(
+                        note_id.clone(),
+                        current.content,
+                        Some(current.updated_at),
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        self.insert_revision(note_id.clone(), content.to_owned(), None)
+            .await?;
+
+        let expired = self
+            .list_note_revisions(note_id)
+            .await?
+            .into_iter()
+            .skip(MAX_REVISIONS)
+            .map(|revision| uuid(revision.id))
+            .collect::<Vec<_>>();
+        if !expired.is_empty() {
+            table("NoteRevision")
+                .delete()
+                .filter(col("id").in_list(expired))
+                .execute(&mut self.storage)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn insert_revision(
+        &mut self,
+        note_id: NoteId,
+        content: String,
+        created_at: Option<String>,
+    ) -> Result<()> {
+        let id = Uuid::now_v7().to_string();
+        let insert = table("NoteRevision").insert();
+        let insert = match created_at {
+            Some(created_at) => insert
+                .columns(vec!["id", "note_id", "content", "created_at"])
+                .values(vec![vec![
+                    uuid(id),
+                    uuid(note_id),
+                    text(content),
+                    timestamp(created_at),
+                ]]),
+            None => insert
+                .columns(vec!["id", "note_id", "content"])
+                .values(vec![vec![uuid(id), uuid(note_id), text(content)]]),
+        };
+        insert.execute(&mut self.storage).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use {
+    super::{Db, Execute},
+    crate::{
+        Result,
+        data::{Note, NoteSearchResult, SearchSnippet},
+    },
+    gluesql::{
+        FromGlueRow,
+        core::{
+            ast_builder::{col, function::lower, function::position, num, table, text},
+            row_conversion::SelectExt,
+        },
+    },
+};
+
+const MAX_SNIPPETS: usize = 3;
+
+#[derive(FromGlueRow)]
+struct SearchRow {
+    id: String,
+    directory_id: String,
+    name: String,
+    created_at: String,
+    updated_at: String,
+    content: String,
+}
+
+impl Db {
+    pub async fn search_notes(&mut self, query: String) -> Result<Vec<NoteSearchResult>> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows = table("Note")
+            .select()
+            .filter(
+                col("deleted_at").is_null().and(
+                    position(lower(col("name")), text(query.clone()))
+                        .gt(num(0))
+                        .or(position(lower(col("content")), text(query.clone())).gt(num(0))),
+                ),
+            )
+            .project(vec![
+                "id",
+                "directory_id",
+                "name",
+                "created_at",
+                "updated_at",
+                "content",
+            ])
+            .order_by("name")
+            .execute(&mut self.storage)
+            .await?
+            .rows_as::<SearchRow>()?;
+
+        let mut snippets = Vec::with_capacity(rows.len());
+        let mut notes = Vec::with_capacity(rows.len());
+        for row in rows {
+            snippets.push(find_snippets(&row.content, &query));
+            notes.push(Note {
+                id: row.id,
+                directory_id: row.directory_id,
+                name: row.name,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                tags: Vec::new(),
+            });
+        }
+
+        self.attach_tags(&mut notes).await?;
+
+        let results = notes
+            .into_iter()
+            .zip(snippets)
+            .map(|(note, snippets)| NoteSearchResult { note, snippets })
+            .collect();
+
+        Ok(results)
+    }
+}
+
+pub(crate) fn find_snippets(content: &str, query: &str) -> Vec<SearchSnippet> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.to_lowercase().contains(query))
+        .take(MAX_SNIPPETS)
+        .map(|(i, line)| SearchSnippet {
+            line_number: i + 1,
+            text: line.trim().to_owned(),
+        })
+        .collect()
+}
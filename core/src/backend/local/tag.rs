@@ -0,0 +1,231 @@
+use {
+    super::{Db, Execute},
+    crate::{
+        Error, Result,
+        data::{Note, Tag},
+        types::NoteId,
+    },
+    gluesql::{
+        FromGlueRow,
+        core::{
+            ast_builder::{col, table, text, uuid},
+            row_conversion::SelectExt,
+        },
+    },
+    std::collections::HashMap,
+    uuid::Uuid,
+};
+
+#[derive(FromGlueRow)]
+struct TagRow {
+    id: String,
+    name: String,
+    created_at: String,
+}
+
+impl From<TagRow> for Tag {
+    fn from(row: TagRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(FromGlueRow)]
+struct NoteTagRow {
+    note_id: String,
+    name: String,
+}
+
+impl Db {
+    pub async fn fetch_tags(&mut self) -> Result<Vec<Tag>> {
+        let tags = table("Tag")
+            .select()
+            .project(vec!["id", "name", "created_at"])
+            .order_by("name")
+            .execute(&mut self.storage)
+            .await?
+            .rows_as::<TagRow>()?
+            .into_iter()
+            .map(Tag::from)
+            .collect();
+
+        Ok(tags)
+    }
+
+    pub async fn add_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()> {
+        let name = normalize(name)?;
+        let tag_id = match self.find_tag(&name).await? {
+            Some(tag) => tag.id,
+            None => {
+                let id = Uuid::now_v7().to_string();
+                table("Tag")
+                    .insert()
+                    .columns(vec!["id", "name"])
+                    .values(vec![vec![uuid(id.clone()), text(name)]])
+                    .execute(&mut self.storage)
+                    .await?;
+
+                id
+            }
+        };
+
+        let tagged = table("NoteTag")
+            .select()
+            .filter(
+                col("note_id")
+                    .eq(uuid(note_id.clone()))
+                    .and(col("tag_id").eq(uuid(tag_id.clone()))),
+            )
+            .project("note_id")
+            .execute(&mut self.storage)
+            .await?
+            .select()
+            .is_some_and(|rows| rows.count() > 0);
+        if tagged {
+            return Ok(());
+        }
+
+        table("NoteTag")
+            .insert()
+            .columns(vec!["note_id", "tag_id"])
+            .values(vec![vec![uuid(note_id), uuid(tag_id)]])
+            .execute(&mut self.storage)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()> {
+        let name = normalize(name)?;
+        let Some(tag) = self.find_tag(&name).await? else {
+            return Ok(());
+        };
+
+        table("NoteTag")
+            .delete()
+            .filter(
+                col("note_id")
+                    .eq(uuid(note_id))
+                    .and(col("tag_id").eq(uuid(tag.id))),
+            )
+            .execute(&mut self.storage)
+            .await?;
+        self.prune_tags().await?;
+
+        Ok(())
+    }
+
+    pub async fn fetch_notes_by_tag(&mut self, name: String) -> Result<Vec<Note>> {
+        let name = normalize(name)?;
+        let mut notes = table("Note")
+            .select()
+            .filter(
+                col("deleted_at").is_null().and(
+                    col("id").in_list(
+                        table("NoteTag")
+                            .select()
+                            .filter(
+                                col("tag_id").in_list(
+                                    table("Tag")
+                                        .select()
+                                        .filter(col("name").eq(text(name)))
+                                        .project("id"),
+                                ),
+                            )
+                            .project("note_id"),
+                    ),
+                ),
+            )
+            .project(vec![
+                "id",
+                "directory_id",
+                "name",
+                "created_at",
+                "updated_at",
+            ])
+            .order_by("name")
+            .execute(&mut self.storage)
+            .await?
+            .rows_as::<super::note::NoteRow>()?
+            .into_iter()
+            .map(Note::from)
+            .collect::<Vec<_>>();
+
+        self.attach_tags(&mut notes).await?;
+
+        Ok(notes)
+    }
+
+    pub(super) async fn attach_tags(&mut self, notes: &mut [Note]) -> Result<()> {
+        if notes.is_empty() {
+            return Ok(());
+        }
+
+        let note_ids = notes
+            .iter()
+            .map(|note| uuid(note.id.clone()))
+            .collect::<Vec<_>>();
+        let rows = table("NoteTag")
+            .select()
+            .join("Tag")
+            .on(col("Tag.id").eq(col("NoteTag.tag_id")))
+            .filter(col("NoteTag.note_id").in_list(note_ids))
+            .project(vec!["NoteTag.note_id", "Tag.name"])
+            .order_by("Tag.name")
+            .execute(&mut self.storage)
+            .await?
+            .rows_as::<NoteTagRow>()?;
+
+        let mut tags: HashMap<NoteId, Vec<String>> = HashMap::new();
+        for row in rows {
+            tags.entry(row.note_id).or_default().push(row.name);
+        }
+
+        for note in notes.iter_mut() {
+            note.tags = tags.remove(&note.id).unwrap_or_default();
+        }
+
+        Ok(())
+    }
+
+    pub(super) async fn prune_tags(&mut self) -> Result<()> {
+        table("Tag")
+            .delete()
+            .filter(
+                col("id")
+                    .in_list(table("NoteTag").select().project("tag_id"))
+                    .negate(),
+            )
+            .execute(&mut self.storage)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_tag(&mut self, name: &str) -> Result<Option<Tag>> {
+        let tag = table("Tag")
+            .select()
+            .filter(col("name").eq(text(name)))
+            .project(vec!["id", "name", "created_at"])
+            .execute(&mut self.storage)
+            .await?
+            .rows_as::<TagRow>()?
+            .into_iter()
+            .next()
+            .map(Tag::from);
+
+        Ok(tag)
+    }
+}
+
+pub(crate) fn normalize(name: String) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(Error::InvalidState("tag name cannot be empty".to_owned()));
+    }
+
+    Ok(name.to_owned())
+}
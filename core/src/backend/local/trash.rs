@@ -0,0 +1,353 @@
+use {
+    super::{Db, Execute},
+    crate::{
+        Error, Result,
+        data::{Directory, Note, Trash},
+        types::{DirectoryId, NoteId},
+    },
+    async_recursion::async_recursion,
+    gluesql::{
+        FromGlueRow,
+        core::{
+            ast_builder::{col, null, table, timestamp, uuid},
+            row_conversion::SelectExt,
+        },
+    },
+    std::collections::HashMap,
+};
+
+#[derive(FromGlueRow)]
+struct TrashedDirectoryRow {
+    id: String,
+    parent_id: Option<String>,
+    name: String,
+    created_at: String,
+    updated_at: String,
+    deleted_at: String,
+}
+
+#[derive(FromGlueRow)]
+struct TrashedNoteRow {
+    id: String,
+    directory_id: String,
+    name: String,
+    created_at: String,
+    updated_at: String,
+    deleted_at: String,
+}
+
+#[derive(FromGlueRow)]
+struct DirectoryStateRow {
+    parent_id: Option<String>,
+    deleted_at: Option<String>,
+}
+
+#[derive(FromGlueRow)]
+struct NoteStateRow {
+    directory_id: String,
+    deleted_at: Option<String>,
+}
+
+#[derive(FromGlueRow)]
+struct IdRow {
+    id: String,
+}
+
+impl Db {
+    pub async fn fetch_trash(&mut self) -> Result<Trash> {
+        let directory_rows = table("Directory")
+            .select()
+            .filter(col("deleted_at").is_not_null())
+            .project(vec![
+                "id",
+                "parent_id",
+                "name",
+                "created_at",
+                "updated_at",
+                "deleted_at",
+            ])
+            .order_by("deleted_at DESC")
+            .execute(&mut self.storage)
+            .await?
+            .rows_as::<TrashedDirectoryRow>()?;
+        let note_rows = table("Note")
+            .select()
+            .filter(col("deleted_at").is_not_null())
+            .project(vec![
+                "id",
+                "directory_id",
+                "name",
+                "created_at",
+                "updated_at",
+                "deleted_at",
+            ])
+            .order_by("deleted_at DESC")
+            .execute(&mut self.storage)
+            .await?
+            .rows_as::<TrashedNoteRow>()?;
+
+        // Entries removed together with their parent directory share its
+        // `deleted_at`, so only the directory itself is listed.
+        let removed = directory_rows
+            .iter()
+            .map(|row| (row.id.clone(), row.deleted_at.clone()))
+            .collect::<HashMap<_, _>>();
+        let removed_with_parent = |parent_id: &str, deleted_at: &str| {
+            removed.get(parent_id).map(String::as_str) == Some(deleted_at)
+        };
+
+        let directories = directory_rows
+            .iter()
+            .filter(|row| {
+                row.parent_id
+                    .as_deref()
+                    .is_none_or(|parent_id| !removed_with_parent(parent_id, &row.deleted_at))
+            })
+            .map(|row| Directory {
+                id: row.id.clone(),
+                parent_id: row.parent_id.clone().unwrap_or_else(|| row.id.clone()),
+                name: row.name.clone(),
+                created_at: row.created_at.clone(),
+                updated_at: row.updated_at.clone(),
+            })
+            .collect();
+        let mut notes = note_rows
+            .into_iter()
+            .filter(|row| !removed_with_parent(&row.directory_id, &row.deleted_at))
+            .map(|row| Note {
+                id: row.id,
+                directory_id: row.directory_id,
+                name: row.name,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                tags: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+
+        self.attach_tags(&mut notes).await?;
+
+        Ok(Trash { directories, notes })
+    }
+
+    pub async fn restore_note(&mut self, note_id: NoteId) -> Result<Note> {
+        let row = table("Note")
+            .select()
+            .filter(col("id").eq(uuid(note_id.clone())))
+            .project(vec!["directory_id", "deleted_at"])
+            .execute(&mut self.storage)
+            .await?
+            .one_as::<NoteStateRow>()?;
+        if row.deleted_at.is_none() {
+            return Err(Error::InvalidState(format!(
+                "[restore_note] note is not in the trash: {note_id}"
+            )));
+        }
+
+        // A note whose directory is still in the trash goes back to the root.
+        let directory_id = if self.is_active_directory(row.directory_id.clone()).await? {
+            row.directory_id
+        } else {
+            self.root_id.clone()
+        };
+
+        table("Note")
+            .update()
+            .filter(col("id").eq(uuid(note_id.clone())))
+            .set("directory_id", uuid(directory_id))
+            .set("deleted_at", null())
+            .execute(&mut self.storage)
+            .await?;
+
+        let mut notes = table("Note")
+            .select()
+            .filter(col("id").eq(uuid(note_id)))
+            .project(vec![
+                "id",
+                "directory_id",
+                "name",
+                "created_at",
+                "updated_at",
+            ])
+            .execute(&mut self.storage)
+            .await?
+            .rows_as::<super::note::NoteRow>()?
+            .into_iter()
+            .map(Note::from)
+            .collect::<Vec<_>>();
+        self.attach_tags(&mut notes).await?;
+
+        notes
+            .pop()
+            .ok_or_else(|| Error::NotFound("[restore_note] note not found".to_owned()))
+    }
+
+    pub async fn restore_directory(&mut self, directory_id: DirectoryId) -> Result<Directory> {
+        let row = table("Directory")
+            .select()
+            .filter(col("id").eq(uuid(directory_id.clone())))
+            .project(vec!["parent_id", "deleted_at"])
+            .execute(&mut self.storage)
+            .await?
+            .one_as::<DirectoryStateRow>()?;
+        let (Some(parent_id), Some(deleted_at)) = (row.parent_id, row.deleted_at) else {
+            return Err(Error::InvalidState(format!(
+                "[restore_directory] directory is not in the trash: {directory_id}"
+            )));
+        };
+
+        let parent_id = if self.is_active_directory(parent_id.clone()).await? {
+            parent_id
+        } else {
+            self.root_id.clone()
+        };
+
+        table("Directory")
+            .update()
+            .filter(col("id").eq(uuid(directory_id.clone())))
+            .set("parent_id", uuid(parent_id))
+            .set("deleted_at", null())
+            .execute(&mut self.storage)
+            .await?;
+        self.restore_children(directory_id.clone(), &deleted_at)
+            .await?;
+
+        self.fetch_directory(directory_id).await
+    }
+
+    pub async fn purge(&mut self) -> Result<()> {
+        for name in ["NoteTag", "NoteRevision", "NoteLink"] {
+            table(name)
+                .delete()
+                .filter(
+                    col("note_id").in_list(
+                        table("Note")
+                            .select()
+                            .filter(col("deleted_at").is_not_null())
+                            .project("id"),
+                    ),
+                )
+                .execute(&mut self.storage)
+                .await?;
+        }
+
+        for name in ["Note", "Directory"] {
+            table(name)
+                .delete()
+                .filter(col("deleted_at").is_not_null())
+                .execute(&mut self.storage)
+                .await?;
+        }
+        self.prune_tags().await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn fetch_directory_deleted_at(
+        &mut self,
+        directory_id: DirectoryId,
+    ) -> Result<String> {
+        table("Directory")
+            .select()
+            .filter(col("id").eq(uuid(directory_id.clone())))
+            .project(vec!["parent_id", "deleted_at"])
+            .execute(&mut self.storage)
+            .await?
+            .one_as::<DirectoryStateRow>()?
+            .deleted_at
+            .ok_or_else(|| {
+                Error::InvalidState(format!(
+                    "[remove_directory] directory is not in the trash: {directory_id}"
+                ))
+            })
+    }
+
+    #[async_recursion]
+    pub(super) async fn trash_children(
+        &mut self,
+        directory_id: DirectoryId,
+        deleted_at: &str,
+    ) -> Result<()> {
+        table("Note")
+            .update()
+            .filter(
+                col("directory_id")
+                    .eq(uuid(directory_id.clone()))
+                    .and(col("deleted_at").is_null()),
+            )
+            .set("deleted_at", timestamp(deleted_at.to_owned()))
+            .execute(&mut self.storage)
+            .await?;
+
+        for directory in self.fetch_directories(directory_id).await? {
+            table("Directory")
+                .update()
+                .filter(col("id").eq(uuid(directory.id.clone())))
+                .set("deleted_at", timestamp(deleted_at.to_owned()))
+                .execute(&mut self.storage)
+                .await?;
+            self.trash_children(directory.id, deleted_at).await?;
+        }
+
+        Ok(())
+    }
+
+    #[async_recursion]
+    async fn restore_children(
+        &mut self,
+        directory_id: DirectoryId,
+        deleted_at: &str,
+    ) -> Result<()> {
+        table("Note")
+            .update()
+            .filter(
+                col("directory_id")
+                    .eq(uuid(directory_id.clone()))
+                    .and(col("deleted_at").eq(timestamp(deleted_at.to_owned()))),
+            )
+            .set("deleted_at", null())
+            .execute(&mut self.storage)
+            .await?;
+
+        let directories = table("Directory")
+            .select()
+            .filter(
+                col("parent_id")
+                    .eq(uuid(directory_id))
+                    .and(col("deleted_at").eq(timestamp(deleted_at.to_owned()))),
+            )
+            .project("id")
+            .execute(&mut self.storage)
+            .await?
+            .rows_as::<IdRow>()?;
+        for IdRow { id } in directories {
+            table("Directory")
+                .update()
+                .filter(col("id").eq(uuid(id.clone())))
+                .set("deleted_at", null())
+                .execute(&mut self.storage)
+                .await?;
+            self.restore_children(id, deleted_at).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn is_active_directory(&mut self, directory_id: DirectoryId) -> Result<bool> {
+        let active = table("Directory")
+            .select()
+            .filter(
+                col("id")
+                    .eq(uuid(directory_id))
+                    .and(col("deleted_at").is_null()),
+            )
+            .project("id")
+            .execute(&mut self.storage)
+            .await?
+            .rows_as::<IdRow>()?
+            .into_iter()
+            .next()
+            .is_some();
+
+        Ok(active)
+    }
+}
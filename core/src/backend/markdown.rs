@@ -0,0 +1,388 @@
+//! A notebook kept as plain Markdown that other editors can work on.
+//!
+//! Every directory is a folder and every note a `<name>.md` file below the
+//! notebook root. IDs travel with the entries so renames and moves made with
+//! other tools are picked up: a note keeps its ID, creation time and tags in
+//! front matter, and a folder in a hidden `.glues.json` next to its notes.
+//! A note's `updated_at` is the modification time of its file.
+//!
+//! Everything else Glues keeps lives in `.glues/` at the root: the trash,
+//! note revisions, tag IDs and the log. Hidden files and folders are never
+//! listed as entries.
+
+mod core_backend;
+mod directory;
+mod front_matter;
+mod link;
+mod note;
+mod revision;
+mod tag;
+mod trash;
+
+use {
+    crate::{
+        Error, Result,
+        export::{sanitize, unique_name},
+        types::{DirectoryId, NoteId},
+    },
+    chrono::{DateTime, Utc},
+    front_matter::FrontMatter,
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::{HashMap, HashSet},
+        fs::{self, File},
+        io::ErrorKind,
+        path::{Path, PathBuf},
+        time::SystemTime,
+    },
+    uuid::Uuid,
+};
+
+/// Name shown for the folder the notebook was opened at.
+const ROOT_NAME: &str = "Notes";
+/// Per-folder file holding the directory ID.
+const SIDECAR: &str = ".glues.json";
+/// Folder at the root for everything that is not a note or directory.
+const META_DIR: &str = ".glues";
+/// File next to a trashed entry recording where it came from.
+const TRASH_ENTRY: &str = "entry.json";
+const NOTE_EXTENSION: &str = "md";
+
+pub struct MarkdownBackend {
+    pub root_id: DirectoryId,
+    root: PathBuf,
+    /// Where each entry was last seen; checked on use and rebuilt by
+    /// [`MarkdownBackend::scan`] when something was moved by another tool.
+    directories: HashMap<DirectoryId, PathBuf>,
+    notes: HashMap<NoteId, PathBuf>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Sidecar {
+    id: DirectoryId,
+    created_at: String,
+    updated_at: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TrashEntry {
+    parent_id: DirectoryId,
+    deleted_at: String,
+}
+
+impl MarkdownBackend {
+    /// Opens the notebook at `path`, creating the folder when missing.
+    pub async fn open(path: &str) -> Result<Self> {
+        fs::create_dir_all(path)?;
+        let root = fs::canonicalize(path)?;
+        fs::create_dir_all(root.join(META_DIR))?;
+
+        let root_id = match read_sidecar(&root)? {
+            Some(sidecar) => sidecar.id,
+            None => write_new_sidecar(&root)?.id,
+        };
+        let mut backend = Self {
+            root_id,
+            root,
+            directories: HashMap::new(),
+            notes: HashMap::new(),
+        };
+        backend.scan()?;
+
+        Ok(backend)
+    }
+
+    fn meta_dir(&self) -> PathBuf {
+        self.root.join(META_DIR)
+    }
+
+    fn trash_dir(&self) -> PathBuf {
+        self.meta_dir().join("trash")
+    }
+
+    fn is_trashed(&self, path: &Path) -> bool {
+        path.starts_with(self.trash_dir())
+    }
+
+    /// Rebuilds the index from the files on disk, giving entries created by
+    /// other tools an ID on the way.
+    fn scan(&mut self) -> Result<()> {
+        self.directories.clear();
+        self.notes.clear();
+        self.directories
+            .insert(self.root_id.clone(), self.root.clone());
+
+        let mut pending = vec![self.root.clone()];
+        let trash_dir = self.trash_dir();
+        if trash_dir.is_dir() {
+            for slot in fs::read_dir(&trash_dir)? {
+                pending.extend(visible_entries(&slot?.path())?);
+            }
+        }
+
+        while let Some(path) = pending.pop() {
+            if path.is_dir() {
+                if path != self.root {
+                    self.claim_directory(&path)?;
+                }
+                pending.extend(visible_entries(&path)?);
+            } else if is_note_file(&path) {
+                self.claim_note(&path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn directory_path(&mut self, directory_id: &DirectoryId) -> Result<PathBuf> {
+        if let Some(path) = self.directories.get(directory_id)
+            && read_sidecar(path)?.is_some_and(|sidecar| &sidecar.id == directory_id)
+        {
+            return Ok(path.clone());
+        }
+
+        self.scan()?;
+        self.directories.get(directory_id).cloned().ok_or_else(|| {
+            Error::NotFound(format!("[markdown] directory not found: {directory_id}"))
+        })
+    }
+
+    fn note_path(&mut self, note_id: &NoteId) -> Result<PathBuf> {
+        if let Some(path) = self.notes.get(note_id)
+            && read_front_matter(path)?
+                .is_some_and(|front_matter| front_matter.id.as_ref() == Some(note_id))
+        {
+            return Ok(path.clone());
+        }
+
+        self.scan()?;
+        self.notes
+            .get(note_id)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("[markdown] note not found: {note_id}")))
+    }
+
+    /// Registers the folder at `path`, writing a sidecar when it has none
+    /// and a fresh ID when it is a copy of an indexed folder.
+    fn claim_directory(&mut self, path: &Path) -> Result<DirectoryId> {
+        let id = match read_sidecar(path)? {
+            Some(sidecar) if !self.is_claimed_directory(&sidecar.id, path)? => sidecar.id,
+            _ => write_new_sidecar(path)?.id,
+        };
+        self.directories.insert(id.clone(), path.to_path_buf());
+
+        Ok(id)
+    }
+
+    fn is_claimed_directory(&self, id: &DirectoryId, path: &Path) -> Result<bool> {
+        match self.directories.get(id) {
+            Some(other) if other != path => {
+                Ok(read_sidecar(other)?.is_some_and(|sidecar| &sidecar.id == id))
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Registers the note at `path`, adding front matter when it has none
+    /// and a fresh ID when it is a copy of an indexed note.
+    fn claim_note(&mut self, path: &Path) -> Result<NoteId> {
+        let text = fs::read_to_string(path)?;
+        let (mut front_matter, body) = FrontMatter::split(&text);
+
+        let needs_id = match &front_matter.id {
+            Some(id) => match self.notes.get(id) {
+                Some(other) if other != path => {
+                    read_front_matter(other)?.is_some_and(|other| other.id.as_ref() == Some(id))
+                }
+                _ => false,
+            },
+            None => true,
+        };
+        if needs_id {
+            front_matter.id = Some(Uuid::now_v7().to_string());
+            if front_matter.created_at.is_none() {
+                front_matter.created_at = Some(modified_at(path)?);
+            }
+            write_keeping_mtime(path, &front_matter.render(body))?;
+        }
+
+        let id = front_matter.id.expect("note id should be assigned");
+        self.notes.insert(id.clone(), path.to_path_buf());
+
+        Ok(id)
+    }
+
+    /// The ID of the directory holding the entry at `path`.
+    fn parent_id(&self, path: &Path) -> Result<DirectoryId> {
+        let parent = path
+            .parent()
+            .ok_or_else(|| Error::InvalidState(format!("no parent: {}", path.display())))?;
+        if parent.parent() == Some(self.trash_dir().as_path()) {
+            return Ok(read_trash_entry(parent)?.parent_id);
+        }
+
+        read_sidecar(parent)?
+            .map(|sidecar| sidecar.id)
+            .ok_or_else(|| {
+                Error::NotFound(format!("[markdown] no {SIDECAR} in {}", parent.display()))
+            })
+    }
+}
+
+/// Non-hidden entries of a folder, sorted by name.
+fn visible_entries(path: &Path) -> Result<Vec<PathBuf>> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        if !entry.file_name().to_string_lossy().starts_with('.') {
+            entries.push(entry.path());
+        }
+    }
+    entries.sort();
+
+    Ok(entries)
+}
+
+fn is_note_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case(NOTE_EXTENSION))
+}
+
+fn note_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn folder_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// A path in `folder` for an entry called `name` that does not clash with
+/// the other entries there; `current` is left out so renaming an entry to
+/// its own name keeps it.
+fn free_path(
+    folder: &Path,
+    name: &str,
+    extension: &str,
+    current: Option<&Path>,
+) -> Result<PathBuf> {
+    // A leading dot would hide the entry.
+    let name = name.trim_start_matches('.');
+    let mut taken = HashSet::new();
+    for entry in fs::read_dir(folder)? {
+        let path = entry?.path();
+        if Some(path.as_path()) != current {
+            taken.insert(
+                path.file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_lowercase(),
+            );
+        }
+    }
+
+    let extension = if extension.is_empty() {
+        String::new()
+    } else {
+        format!(".{extension}")
+    };
+    if let Some(current) = current
+        && current.parent() == Some(folder)
+        && current.file_name() == Some(format!("{}{extension}", sanitize(name)).as_ref())
+    {
+        return Ok(current.to_path_buf());
+    }
+
+    Ok(folder.join(unique_name(&mut taken, name, &extension)))
+}
+
+fn timestamp(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).naive_utc().to_string()
+}
+
+fn now() -> String {
+    timestamp(SystemTime::now())
+}
+
+fn modified_at(path: &Path) -> Result<String> {
+    Ok(timestamp(fs::metadata(path)?.modified()?))
+}
+
+/// Marks the entry at `path` as changed now.
+fn touch(path: &Path) -> Result<()> {
+    File::options()
+        .write(true)
+        .open(path)?
+        .set_modified(SystemTime::now())?;
+
+    Ok(())
+}
+
+/// Rewrites a note's front matter without it counting as an edit.
+fn write_keeping_mtime(path: &Path, text: &str) -> Result<()> {
+    let modified = fs::metadata(path)?.modified()?;
+    fs::write(path, text)?;
+    File::options()
+        .write(true)
+        .open(path)?
+        .set_modified(modified)?;
+
+    Ok(())
+}
+
+fn read_front_matter(path: &Path) -> Result<Option<FrontMatter>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(FrontMatter::split(&text).0)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<Option<T>> {
+    match fs::read_to_string(path) {
+        Ok(text) => serde_json::from_str(&text)
+            .map(Some)
+            .map_err(|error| Error::BackendError(format!("{}: {error}", path.display()))),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let text = serde_json::to_string_pretty(value)
+        .map_err(|error| Error::BackendError(error.to_string()))?;
+    fs::write(path, text)?;
+
+    Ok(())
+}
+
+fn read_sidecar(folder: &Path) -> Result<Option<Sidecar>> {
+    read_json(&folder.join(SIDECAR))
+}
+
+fn write_sidecar(folder: &Path, sidecar: &Sidecar) -> Result<()> {
+    write_json(&folder.join(SIDECAR), sidecar)
+}
+
+fn write_new_sidecar(folder: &Path) -> Result<Sidecar> {
+    let created_at = modified_at(folder)?;
+    let sidecar = Sidecar {
+        id: Uuid::now_v7().to_string(),
+        updated_at: created_at.clone(),
+        created_at,
+    };
+    write_sidecar(folder, &sidecar)?;
+
+    Ok(sidecar)
+}
+
+fn read_trash_entry(slot: &Path) -> Result<TrashEntry> {
+    read_json(&slot.join(TRASH_ENTRY))?.ok_or_else(|| {
+        Error::NotFound(format!("[markdown] no {TRASH_ENTRY} in {}", slot.display()))
+    })
+}
@@ -0,0 +1,155 @@
+use crate::{
+    Result,
+    backend::{CoreBackend, SyncJob, proxy::ChangeFeed},
+    data::{Directory, Note, NoteRevision, NoteSearchResult, Tag, Trash},
+    types::{DirectoryId, NoteId, RevisionId},
+};
+use async_trait::async_trait;
+
+use super::MarkdownBackend;
+
+#[async_trait]
+impl CoreBackend for MarkdownBackend {
+    fn root_id(&self) -> DirectoryId {
+        self.root_id.clone()
+    }
+
+    async fn fetch_directory(&mut self, directory_id: DirectoryId) -> Result<Directory> {
+        MarkdownBackend::fetch_directory(self, directory_id).await
+    }
+
+    async fn fetch_directories(&mut self, parent_id: DirectoryId) -> Result<Vec<Directory>> {
+        MarkdownBackend::fetch_directories(self, parent_id).await
+    }
+
+    async fn add_directory(&mut self, parent_id: DirectoryId, name: String) -> Result<Directory> {
+        MarkdownBackend::add_directory(self, parent_id, name).await
+    }
+
+    async fn remove_directory(&mut self, directory_id: DirectoryId) -> Result<()> {
+        MarkdownBackend::remove_directory(self, directory_id).await
+    }
+
+    async fn move_directory(
+        &mut self,
+        directory_id: DirectoryId,
+        parent_id: DirectoryId,
+    ) -> Result<()> {
+        MarkdownBackend::move_directory(self, directory_id, parent_id).await
+    }
+
+    async fn rename_directory(&mut self, directory_id: DirectoryId, name: String) -> Result<()> {
+        MarkdownBackend::rename_directory(self, directory_id, name).await
+    }
+
+    async fn fetch_note(&mut self, note_id: NoteId) -> Result<Note> {
+        MarkdownBackend::fetch_note(self, note_id).await
+    }
+
+    async fn fetch_notes(&mut self, directory_id: DirectoryId) -> Result<Vec<Note>> {
+        MarkdownBackend::fetch_notes(self, directory_id).await
+    }
+
+    async fn fetch_note_content(&mut self, note_id: NoteId) -> Result<String> {
+        MarkdownBackend::fetch_note_content(self, note_id).await
+    }
+
+    async fn add_note(&mut self, directory_id: DirectoryId, name: String) -> Result<Note> {
+        MarkdownBackend::add_note(self, directory_id, name).await
+    }
+
+    async fn remove_note(&mut self, note_id: NoteId) -> Result<()> {
+        MarkdownBackend::remove_note(self, note_id).await
+    }
+
+    async fn rename_note(
+        &mut self,
+        note_id: NoteId,
+        name: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        MarkdownBackend::rename_note(self, note_id, name, expected_updated_at).await
+    }
+
+    async fn update_note_content(
+        &mut self,
+        note_id: NoteId,
+        content: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        MarkdownBackend::update_note_content(self, note_id, content, expected_updated_at).await
+    }
+
+    async fn move_note(
+        &mut self,
+        note_id: NoteId,
+        directory_id: DirectoryId,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        MarkdownBackend::move_note(self, note_id, directory_id, expected_updated_at).await
+    }
+
+    async fn list_note_revisions(&mut self, note_id: NoteId) -> Result<Vec<NoteRevision>> {
+        MarkdownBackend::list_note_revisions(self, note_id).await
+    }
+
+    async fn fetch_note_revision(
+        &mut self,
+        note_id: NoteId,
+        revision_id: RevisionId,
+    ) -> Result<String> {
+        MarkdownBackend::fetch_note_revision(self, note_id, revision_id).await
+    }
+
+    async fn search_notes(&mut self, query: String) -> Result<Vec<NoteSearchResult>> {
+        MarkdownBackend::search_notes(self, query).await
+    }
+
+    async fn fetch_backlinks(&mut self, note_id: NoteId) -> Result<Vec<Note>> {
+        MarkdownBackend::fetch_backlinks(self, note_id).await
+    }
+
+    async fn fetch_tags(&mut self) -> Result<Vec<Tag>> {
+        MarkdownBackend::fetch_tags(self).await
+    }
+
+    async fn add_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()> {
+        MarkdownBackend::add_note_tag(self, note_id, name).await
+    }
+
+    async fn remove_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()> {
+        MarkdownBackend::remove_note_tag(self, note_id, name).await
+    }
+
+    async fn fetch_notes_by_tag(&mut self, name: String) -> Result<Vec<Note>> {
+        MarkdownBackend::fetch_notes_by_tag(self, name).await
+    }
+
+    async fn fetch_trash(&mut self) -> Result<Trash> {
+        MarkdownBackend::fetch_trash(self).await
+    }
+
+    async fn restore_note(&mut self, note_id: NoteId) -> Result<Note> {
+        MarkdownBackend::restore_note(self, note_id).await
+    }
+
+    async fn restore_directory(&mut self, directory_id: DirectoryId) -> Result<Directory> {
+        MarkdownBackend::restore_directory(self, directory_id).await
+    }
+
+    async fn purge(&mut self) -> Result<()> {
+        MarkdownBackend::purge(self).await
+    }
+
+    async fn log(&mut self, category: String, message: String) -> Result<()> {
+        MarkdownBackend::log(self, category, message).await
+    }
+
+    fn sync_job(&self) -> Option<SyncJob> {
+        None
+    }
+
+    fn change_feed(&self) -> Option<ChangeFeed> {
+        None
+    }
+}
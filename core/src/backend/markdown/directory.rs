@@ -0,0 +1,155 @@
+use {
+    super::{
+        MarkdownBackend, ROOT_NAME, Sidecar, TRASH_ENTRY, TrashEntry, folder_name, free_path, now,
+        read_sidecar, visible_entries, write_json, write_new_sidecar, write_sidecar,
+    },
+    crate::{Error, Result, data::Directory, types::DirectoryId},
+    std::{fs, path::Path},
+};
+
+impl MarkdownBackend {
+    pub async fn fetch_directory(&mut self, directory_id: DirectoryId) -> Result<Directory> {
+        let path = self.directory_path(&directory_id)?;
+
+        self.read_directory(&path)
+    }
+
+    pub async fn fetch_directories(&mut self, parent_id: DirectoryId) -> Result<Vec<Directory>> {
+        let path = self.directory_path(&parent_id)?;
+
+        let mut directories = Vec::new();
+        for child in visible_entries(&path)? {
+            if child.is_dir() {
+                self.claim_directory(&child)?;
+                directories.push(self.read_directory(&child)?);
+            }
+        }
+
+        Ok(directories)
+    }
+
+    pub async fn add_directory(
+        &mut self,
+        parent_id: DirectoryId,
+        name: String,
+    ) -> Result<Directory> {
+        let parent = self.directory_path(&parent_id)?;
+        let path = free_path(&parent, &name, "", None)?;
+        fs::create_dir(&path)?;
+
+        let sidecar = write_new_sidecar(&path)?;
+        self.directories.insert(sidecar.id, path.clone());
+
+        self.read_directory(&path)
+    }
+
+    /// Moves the folder into the trash, along with everything in it.
+    pub async fn remove_directory(&mut self, directory_id: DirectoryId) -> Result<()> {
+        let path = self.directory_path(&directory_id)?;
+        self.check_not_root(&directory_id, "remove")?;
+
+        let slot = self.trash_dir().join(&directory_id);
+        fs::create_dir_all(&slot)?;
+        write_json(
+            &slot.join(TRASH_ENTRY),
+            &TrashEntry {
+                parent_id: self.parent_id(&path)?,
+                deleted_at: now(),
+            },
+        )?;
+
+        let trashed = slot.join(path.file_name().unwrap_or_default());
+        fs::rename(&path, &trashed)?;
+        self.directories.insert(directory_id, trashed);
+
+        Ok(())
+    }
+
+    pub async fn move_directory(
+        &mut self,
+        directory_id: DirectoryId,
+        parent_id: DirectoryId,
+    ) -> Result<()> {
+        let path = self.directory_path(&directory_id)?;
+        self.check_not_root(&directory_id, "move")?;
+        let parent = self.directory_path(&parent_id)?;
+        if parent.starts_with(&path) {
+            return Err(Error::InvalidState(
+                "cannot move a directory into itself".to_owned(),
+            ));
+        }
+
+        let target = free_path(&parent, &folder_name(&path), "", Some(&path))?;
+        self.relocate_directory(directory_id, &path, &target)
+    }
+
+    pub async fn rename_directory(
+        &mut self,
+        directory_id: DirectoryId,
+        name: String,
+    ) -> Result<()> {
+        let path = self.directory_path(&directory_id)?;
+        self.check_not_root(&directory_id, "rename")?;
+        let parent = path.parent().unwrap_or(&self.root).to_path_buf();
+
+        let target = free_path(&parent, &name, "", Some(&path))?;
+        self.relocate_directory(directory_id, &path, &target)
+    }
+
+    fn relocate_directory(
+        &mut self,
+        directory_id: DirectoryId,
+        path: &Path,
+        target: &Path,
+    ) -> Result<()> {
+        if path != target {
+            fs::rename(path, target)?;
+        }
+
+        if let Some(mut sidecar) = read_sidecar(target)? {
+            sidecar.updated_at = now();
+            write_sidecar(target, &sidecar)?;
+        }
+        self.directories.insert(directory_id, target.to_path_buf());
+
+        Ok(())
+    }
+
+    fn check_not_root(&self, directory_id: &DirectoryId, action: &str) -> Result<()> {
+        if directory_id == &self.root_id {
+            return Err(Error::InvalidState(format!(
+                "cannot {action} the notebook folder"
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn read_directory(&self, path: &Path) -> Result<Directory> {
+        let Some(Sidecar {
+            id,
+            created_at,
+            updated_at,
+        }) = read_sidecar(path)?
+        else {
+            return Err(Error::NotFound(format!(
+                "[markdown] directory not found: {}",
+                path.display()
+            )));
+        };
+
+        let (parent_id, name) = if path == self.root {
+            (id.clone(), ROOT_NAME.to_owned())
+        } else {
+            (self.parent_id(path)?, folder_name(path))
+        };
+
+        Ok(Directory {
+            id,
+            parent_id,
+            name,
+            created_at,
+            updated_at,
+        })
+    }
+}
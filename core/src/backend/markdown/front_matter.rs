@@ -0,0 +1,146 @@
+use crate::types::NoteId;
+
+const FENCE: &str = "---";
+
+/// The `---` delimited block at the top of a note file.
+///
+/// Only the keys Glues uses are read; any other lines are kept as they are
+/// so metadata added by other tools survives a save. `updated_at`, which
+/// `glues export --front-matter` writes, is dropped in favour of the file's
+/// modification time.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct FrontMatter {
+    pub id: Option<NoteId>,
+    pub created_at: Option<String>,
+    pub tags: Vec<String>,
+    other: Vec<String>,
+}
+
+impl FrontMatter {
+    pub fn new(id: NoteId, created_at: String) -> Self {
+        Self {
+            id: Some(id),
+            created_at: Some(created_at),
+            ..Self::default()
+        }
+    }
+
+    /// Splits a note file into its front matter and the note content.
+    pub fn split(text: &str) -> (Self, &str) {
+        let Some(rest) = text
+            .strip_prefix("---\n")
+            .or_else(|| text.strip_prefix("---\r\n"))
+        else {
+            return (Self::default(), text);
+        };
+
+        let mut front_matter = Self::default();
+        let mut in_tags = false;
+        let mut offset = 0;
+        for line in rest.split_inclusive('\n') {
+            offset += line.len();
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line == FENCE {
+                let body = &rest[offset..];
+                let body = body
+                    .strip_prefix('\n')
+                    .or_else(|| body.strip_prefix("\r\n"))
+                    .unwrap_or(body);
+                return (front_matter, body);
+            }
+
+            if in_tags && let Some(tag) = line.trim_start().strip_prefix("- ") {
+                front_matter.tags.push(unquote(tag));
+                continue;
+            }
+            in_tags = false;
+
+            match line.split_once(':') {
+                Some(("id", value)) => front_matter.id = Some(unquote(value)),
+                Some(("created_at", value)) => front_matter.created_at = Some(unquote(value)),
+                Some(("updated_at", _)) => {}
+                Some(("tags", value)) if value.trim().is_empty() => in_tags = true,
+                Some(("tags", value)) => front_matter.tags = parse_list(value),
+                _ => front_matter.other.push(line.to_owned()),
+            }
+        }
+
+        // No closing fence, so the dashes are part of the content.
+        (Self::default(), text)
+    }
+
+    pub fn render(&self, body: &str) -> String {
+        let mut text = format!("{FENCE}\n");
+        if let Some(id) = &self.id {
+            text.push_str(&format!("id: {id}\n"));
+        }
+        if let Some(created_at) = &self.created_at {
+            text.push_str(&format!("created_at: {created_at}\n"));
+        }
+        if !self.tags.is_empty() {
+            let tags = self
+                .tags
+                .iter()
+                .map(|tag| format!("\"{}\"", tag.replace('\\', "\\\\").replace('"', "\\\"")))
+                .collect::<Vec<_>>()
+                .join(", ");
+            text.push_str(&format!("tags: [{tags}]\n"));
+        }
+        for line in &self.other {
+            text.push_str(line);
+            text.push('\n');
+        }
+        text.push_str(&format!("{FENCE}\n\n"));
+        text.push_str(body);
+
+        text
+    }
+}
+
+/// Reads a YAML scalar, dropping the quotes and escapes of a quoted one.
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+        {
+            return inner.replace("\\\"", "\"").replace("\\\\", "\\");
+        }
+    }
+
+    value.to_owned()
+}
+
+/// Reads a flow sequence such as `[a, "b, c"]`.
+fn parse_list(value: &str) -> Vec<String> {
+    let value = value.trim();
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|value| value.strip_suffix(']'))
+        .unwrap_or(value);
+
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut quote = None;
+    let mut escaped = false;
+    for c in inner.chars() {
+        match (c, quote) {
+            (_, Some(_)) if escaped => escaped = false,
+            ('\\', Some('"')) => escaped = true,
+            (c, Some(q)) if c == q => quote = None,
+            ('"' | '\'', None) => quote = Some(c),
+            (',', None) => {
+                items.push(unquote(&item));
+                item.clear();
+                continue;
+            }
+            _ => {}
+        }
+        item.push(c);
+    }
+    items.push(unquote(&item));
+    items.retain(|item| !item.is_empty());
+
+    items
+}
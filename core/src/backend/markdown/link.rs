@@ -0,0 +1,34 @@
+use {
+    super::{FrontMatter, MarkdownBackend},
+    crate::{Result, data::Note, link, types::NoteId},
+    std::fs,
+};
+
+impl MarkdownBackend {
+    /// Reads the links out of every note instead of keeping an index, so
+    /// links written by other editors count too.
+    pub async fn fetch_backlinks(&mut self, note_id: NoteId) -> Result<Vec<Note>> {
+        let path = self.note_path(&note_id)?;
+        let note = self.read_note(&path)?;
+        let keys = [note.id.to_lowercase(), note.name.to_lowercase()];
+
+        let mut notes = Vec::new();
+        for path in self.active_notes()? {
+            let text = fs::read_to_string(&path)?;
+            let linked = link::keys(FrontMatter::split(&text).1)
+                .iter()
+                .any(|key| keys.contains(key));
+            if !linked {
+                continue;
+            }
+
+            let source = self.read_note(&path)?;
+            if source.id != note_id {
+                notes.push(source);
+            }
+        }
+        notes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(notes)
+    }
+}
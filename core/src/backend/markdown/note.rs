@@ -0,0 +1,227 @@
+use {
+    super::{
+        FrontMatter, MarkdownBackend, NOTE_EXTENSION, TRASH_ENTRY, TrashEntry, free_path,
+        is_note_file, modified_at, note_name, now, touch, visible_entries, write_json,
+    },
+    crate::{
+        Error, Result,
+        backend::local::find_snippets,
+        data::{Note, NoteSearchResult},
+        types::{DirectoryId, NoteId},
+    },
+    std::{
+        fs,
+        io::Write,
+        path::{Path, PathBuf},
+    },
+    uuid::Uuid,
+};
+
+impl MarkdownBackend {
+    pub async fn fetch_note_content(&mut self, note_id: NoteId) -> Result<String> {
+        let path = self.note_path(&note_id)?;
+        let text = fs::read_to_string(path)?;
+
+        Ok(FrontMatter::split(&text).1.to_owned())
+    }
+
+    /// Fetches a single note, including one that is in the trash.
+    pub async fn fetch_note(&mut self, note_id: NoteId) -> Result<Note> {
+        let path = self.note_path(&note_id)?;
+
+        self.read_note(&path)
+    }
+
+    pub async fn fetch_notes(&mut self, directory_id: DirectoryId) -> Result<Vec<Note>> {
+        let path = self.directory_path(&directory_id)?;
+
+        let mut notes = Vec::new();
+        for child in visible_entries(&path)? {
+            if is_note_file(&child) {
+                self.claim_note(&child)?;
+                notes.push(self.read_note(&child)?);
+            }
+        }
+
+        Ok(notes)
+    }
+
+    pub async fn add_note(&mut self, directory_id: DirectoryId, name: String) -> Result<Note> {
+        let folder = self.directory_path(&directory_id)?;
+        let path = free_path(&folder, &name, NOTE_EXTENSION, None)?;
+
+        let id = Uuid::now_v7().to_string();
+        fs::write(&path, FrontMatter::new(id.clone(), now()).render(""))?;
+        self.notes.insert(id, path.clone());
+
+        self.read_note(&path)
+    }
+
+    /// Moves the note file into the trash.
+    pub async fn remove_note(&mut self, note_id: NoteId) -> Result<()> {
+        let path = self.note_path(&note_id)?;
+
+        let slot = self.trash_dir().join(&note_id);
+        fs::create_dir_all(&slot)?;
+        write_json(
+            &slot.join(TRASH_ENTRY),
+            &TrashEntry {
+                parent_id: self.parent_id(&path)?,
+                deleted_at: now(),
+            },
+        )?;
+
+        let trashed = slot.join(path.file_name().unwrap_or_default());
+        fs::rename(&path, &trashed)?;
+        self.notes.insert(note_id, trashed);
+
+        Ok(())
+    }
+
+    pub async fn update_note_content(
+        &mut self,
+        note_id: NoteId,
+        content: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        let path = self.note_path(&note_id)?;
+        self.check_updated_at(&path, expected_updated_at)?;
+
+        let text = fs::read_to_string(&path)?;
+        let (front_matter, current) = FrontMatter::split(&text);
+        let updated_at = modified_at(&path)?;
+        self.record_revision(&note_id, current, &updated_at, &content)?;
+
+        fs::write(&path, front_matter.render(&content))?;
+
+        self.read_note(&path)
+    }
+
+    pub async fn rename_note(
+        &mut self,
+        note_id: NoteId,
+        name: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        let path = self.note_path(&note_id)?;
+        self.check_updated_at(&path, expected_updated_at)?;
+        let folder = path.parent().unwrap_or(&self.root).to_path_buf();
+
+        let target = free_path(&folder, &name, NOTE_EXTENSION, Some(&path))?;
+        self.relocate_note(note_id, &path, &target)
+    }
+
+    pub async fn move_note(
+        &mut self,
+        note_id: NoteId,
+        directory_id: DirectoryId,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        let path = self.note_path(&note_id)?;
+        self.check_updated_at(&path, expected_updated_at)?;
+        let folder = self.directory_path(&directory_id)?;
+
+        let target = free_path(&folder, &note_name(&path), NOTE_EXTENSION, Some(&path))?;
+        self.relocate_note(note_id, &path, &target)
+    }
+
+    pub async fn search_notes(&mut self, query: String) -> Result<Vec<NoteSearchResult>> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut results = Vec::new();
+        for path in self.active_notes()? {
+            let text = fs::read_to_string(&path)?;
+            let content = FrontMatter::split(&text).1;
+            if note_name(&path).to_lowercase().contains(&query)
+                || content.to_lowercase().contains(&query)
+            {
+                results.push(NoteSearchResult {
+                    note: self.read_note(&path)?,
+                    snippets: find_snippets(content, &query),
+                });
+            }
+        }
+        results.sort_by(|a, b| a.note.name.cmp(&b.note.name));
+
+        Ok(results)
+    }
+
+    /// Appends to `.glues/log`.
+    pub async fn log(&mut self, category: String, message: String) -> Result<()> {
+        let mut log = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.meta_dir().join("log"))?;
+        writeln!(log, "[{}] {category}: {message}", now())?;
+
+        Ok(())
+    }
+
+    fn relocate_note(&mut self, note_id: NoteId, path: &Path, target: &Path) -> Result<Note> {
+        if path != target {
+            fs::rename(path, target)?;
+        }
+        touch(target)?;
+        self.notes.insert(note_id, target.to_path_buf());
+
+        self.read_note(target)
+    }
+
+    /// Paths of the notes outside the trash, after a fresh scan.
+    pub(super) fn active_notes(&mut self) -> Result<Vec<PathBuf>> {
+        self.scan()?;
+
+        Ok(self
+            .notes
+            .values()
+            .filter(|path| !self.is_trashed(path))
+            .cloned()
+            .collect())
+    }
+
+    /// Fails with [`Error::Conflict`] when the note was changed since the
+    /// caller read it at `expected_updated_at`.
+    fn check_updated_at(&self, path: &Path, expected_updated_at: Option<String>) -> Result<()> {
+        let Some(expected) = expected_updated_at else {
+            return Ok(());
+        };
+
+        let updated_at = modified_at(path)?;
+        if updated_at != expected {
+            return Err(Error::Conflict(format!(
+                "note '{}' was changed at {updated_at} after it was read at {expected}",
+                note_name(path)
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn read_note(&self, path: &Path) -> Result<Note> {
+        let text = fs::read_to_string(path)?;
+        let (front_matter, _) = FrontMatter::split(&text);
+        let Some(id) = front_matter.id else {
+            return Err(Error::NotFound(format!(
+                "[markdown] note has no id: {}",
+                path.display()
+            )));
+        };
+        let updated_at = modified_at(path)?;
+        let mut tags = front_matter.tags;
+        tags.sort();
+
+        Ok(Note {
+            id,
+            directory_id: self.parent_id(path)?,
+            name: note_name(path),
+            created_at: front_matter
+                .created_at
+                .unwrap_or_else(|| updated_at.clone()),
+            updated_at,
+            tags,
+        })
+    }
+}
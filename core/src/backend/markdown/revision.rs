@@ -0,0 +1,136 @@
+use {
+    super::{MarkdownBackend, NOTE_EXTENSION, modified_at},
+    crate::{
+        Error, Result,
+        data::NoteRevision,
+        types::{NoteId, RevisionId},
+    },
+    chrono::NaiveDateTime,
+    std::{
+        fs::{self, File},
+        io::ErrorKind,
+        path::PathBuf,
+        time::SystemTime,
+    },
+    uuid::Uuid,
+};
+
+/// Number of revisions kept per note; older ones are dropped first.
+const MAX_REVISIONS: usize = 100;
+
+impl MarkdownBackend {
+    pub async fn list_note_revisions(&mut self, note_id: NoteId) -> Result<Vec<NoteRevision>> {
+        let mut revisions = Vec::new();
+        for path in self.revision_paths(&note_id)? {
+            revisions.push(NoteRevision {
+                id: super::note_name(&path),
+                note_id: note_id.clone(),
+                created_at: modified_at(&path)?,
+            });
+        }
+
+        Ok(revisions)
+    }
+
+    pub async fn fetch_note_revision(
+        &mut self,
+        note_id: NoteId,
+        revision_id: RevisionId,
+    ) -> Result<String> {
+        let path = self
+            .revisions_dir(&note_id)
+            .join(format!("{revision_id}.{NOTE_EXTENSION}"));
+
+        fs::read_to_string(&path).map_err(|error| match error.kind() {
+            ErrorKind::NotFound => {
+                Error::NotFound(format!("[markdown] revision not found: {revision_id}"))
+            }
+            _ => error.into(),
+        })
+    }
+
+    /// Stores `content` as the newest revision of the note unless it matches
+    /// the latest one. The first revision of a note also keeps the content it
+    /// had before, so the original text can always be restored.
+    pub(super) fn record_revision(
+        &mut self,
+        note_id: &NoteId,
+        current: &str,
+        updated_at: &str,
+        content: &str,
+    ) -> Result<()> {
+        let revisions = self.revision_paths(note_id)?;
+        match revisions.first() {
+            Some(latest) => {
+                if fs::read_to_string(latest)? == content {
+                    return Ok(());
+                }
+            }
+            None => {
+                if current == content {
+                    return Ok(());
+                } else if !current.is_empty() {
+                    self.write_revision(note_id, current, Some(updated_at))?;
+                }
+            }
+        }
+
+        self.write_revision(note_id, content, None)?;
+
+        for expired in self
+            .revision_paths(note_id)?
+            .into_iter()
+            .skip(MAX_REVISIONS)
+        {
+            fs::remove_file(expired)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_revision(
+        &self,
+        note_id: &NoteId,
+        content: &str,
+        created_at: Option<&str>,
+    ) -> Result<()> {
+        let folder = self.revisions_dir(note_id);
+        fs::create_dir_all(&folder)?;
+
+        let path = folder.join(format!("{}.{NOTE_EXTENSION}", Uuid::now_v7()));
+        fs::write(&path, content)?;
+        let created_at = created_at
+            .and_then(|created_at| {
+                NaiveDateTime::parse_from_str(created_at, "%Y-%m-%d %H:%M:%S%.f").ok()
+            })
+            .map(|created_at| SystemTime::from(created_at.and_utc()));
+        if let Some(created_at) = created_at {
+            File::options()
+                .write(true)
+                .open(&path)?
+                .set_modified(created_at)?;
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn revisions_dir(&self, note_id: &NoteId) -> PathBuf {
+        self.meta_dir().join("revisions").join(note_id)
+    }
+
+    /// Revision files of a note, newest first.
+    fn revision_paths(&self, note_id: &NoteId) -> Result<Vec<PathBuf>> {
+        let folder = self.revisions_dir(note_id);
+        if !folder.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut paths = fs::read_dir(folder)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        // Revision IDs are UUIDv7s, which sort by creation time.
+        paths.sort_by(|a, b| b.cmp(a));
+
+        Ok(paths)
+    }
+}
@@ -0,0 +1,92 @@
+use {
+    super::{FrontMatter, MarkdownBackend, now, read_json, write_json, write_keeping_mtime},
+    crate::{
+        Result,
+        backend::local::normalize_tag,
+        data::{Note, Tag},
+        types::NoteId,
+    },
+    std::{collections::BTreeSet, fs, path::PathBuf},
+    uuid::Uuid,
+};
+
+impl MarkdownBackend {
+    /// Tags used by any note, including those in the trash. Their IDs are
+    /// kept in `.glues/tags.json` while they are in use.
+    pub async fn fetch_tags(&mut self) -> Result<Vec<Tag>> {
+        self.scan()?;
+
+        let mut names = BTreeSet::new();
+        for path in self.notes.values() {
+            let text = fs::read_to_string(path)?;
+            names.extend(FrontMatter::split(&text).0.tags);
+        }
+
+        let registry = self.tags_file();
+        let known = read_json::<Vec<Tag>>(&registry)?.unwrap_or_default();
+        let tags = names
+            .into_iter()
+            .map(|name| {
+                known
+                    .iter()
+                    .find(|tag| tag.name == name)
+                    .cloned()
+                    .unwrap_or_else(|| Tag {
+                        id: Uuid::now_v7().to_string(),
+                        name,
+                        created_at: now(),
+                    })
+            })
+            .collect::<Vec<_>>();
+        write_json(&registry, &tags)?;
+
+        Ok(tags)
+    }
+
+    pub async fn add_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()> {
+        let name = normalize_tag(name)?;
+
+        self.edit_tags(&note_id, |tags| {
+            if !tags.contains(&name) {
+                tags.push(name);
+                tags.sort();
+            }
+        })
+    }
+
+    pub async fn remove_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()> {
+        let name = normalize_tag(name)?;
+
+        self.edit_tags(&note_id, |tags| tags.retain(|tag| tag != &name))
+    }
+
+    pub async fn fetch_notes_by_tag(&mut self, name: String) -> Result<Vec<Note>> {
+        let name = normalize_tag(name)?;
+
+        let mut notes = Vec::new();
+        for path in self.active_notes()? {
+            let note = self.read_note(&path)?;
+            if note.tags.contains(&name) {
+                notes.push(note);
+            }
+        }
+        notes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(notes)
+    }
+
+    /// Changes the tags in a note's front matter; like in the other
+    /// backends, this does not count as an edit of the note.
+    fn edit_tags(&mut self, note_id: &NoteId, edit: impl FnOnce(&mut Vec<String>)) -> Result<()> {
+        let path = self.note_path(note_id)?;
+        let text = fs::read_to_string(&path)?;
+        let (mut front_matter, body) = FrontMatter::split(&text);
+
+        edit(&mut front_matter.tags);
+        write_keeping_mtime(&path, &front_matter.render(body))
+    }
+
+    fn tags_file(&self) -> PathBuf {
+        self.meta_dir().join("tags.json")
+    }
+}
@@ -0,0 +1,137 @@
+use {
+    super::{
+        MarkdownBackend, NOTE_EXTENSION, TrashEntry, folder_name, free_path, note_name,
+        read_sidecar, read_trash_entry,
+    },
+    crate::{
+        Error, Result,
+        data::{Directory, Note, Trash},
+        types::{DirectoryId, NoteId},
+    },
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+impl MarkdownBackend {
+    /// Entries moved to the trash, most recently removed first. The contents
+    /// of a trashed folder stay inside it and are not listed on their own.
+    pub async fn fetch_trash(&mut self) -> Result<Trash> {
+        let mut entries = self.trash_slots()?;
+        entries.sort_by(|(a, _), (b, _)| b.deleted_at.cmp(&a.deleted_at));
+
+        let mut trash = Trash::default();
+        for (_, path) in entries {
+            if path.is_dir() {
+                trash.directories.push(self.read_directory(&path)?);
+            } else {
+                trash.notes.push(self.read_note(&path)?);
+            }
+        }
+
+        Ok(trash)
+    }
+
+    pub async fn restore_note(&mut self, note_id: NoteId) -> Result<Note> {
+        let path = self.note_path(&note_id)?;
+        if !self.is_trashed(&path) {
+            return Err(Error::InvalidState(format!(
+                "[restore_note] note is not in the trash: {note_id}"
+            )));
+        }
+
+        let folder = self.restore_target(&path)?;
+        let target = free_path(&folder, &note_name(&path), NOTE_EXTENSION, None)?;
+        fs::rename(&path, &target)?;
+        self.remove_empty_slot(&path)?;
+        self.notes.insert(note_id, target.clone());
+
+        self.read_note(&target)
+    }
+
+    pub async fn restore_directory(&mut self, directory_id: DirectoryId) -> Result<Directory> {
+        let path = self.directory_path(&directory_id)?;
+        if !self.is_trashed(&path) {
+            return Err(Error::InvalidState(format!(
+                "[restore_directory] directory is not in the trash: {directory_id}"
+            )));
+        }
+
+        let folder = self.restore_target(&path)?;
+        let target = free_path(&folder, &folder_name(&path), "", None)?;
+        fs::rename(&path, &target)?;
+        self.remove_empty_slot(&path)?;
+        self.directories.insert(directory_id, target.clone());
+
+        self.read_directory(&target)
+    }
+
+    /// Deletes the trash for good, along with the revisions of its notes.
+    pub async fn purge(&mut self) -> Result<()> {
+        self.scan()?;
+
+        let trashed = self
+            .notes
+            .iter()
+            .filter(|(_, path)| self.is_trashed(path))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        for note_id in trashed {
+            let revisions = self.revisions_dir(&note_id);
+            if revisions.is_dir() {
+                fs::remove_dir_all(revisions)?;
+            }
+        }
+
+        let trash_dir = self.trash_dir();
+        if trash_dir.is_dir() {
+            fs::remove_dir_all(trash_dir)?;
+        }
+        self.scan()
+    }
+
+    /// The folder a trashed entry goes back to: where it was removed from,
+    /// or the root when that folder is gone or in the trash itself.
+    fn restore_target(&mut self, path: &Path) -> Result<PathBuf> {
+        let parent_id = self.parent_id(path)?;
+        match self.directory_path(&parent_id) {
+            Ok(folder) if !self.is_trashed(&folder) => Ok(folder),
+            Ok(_) | Err(Error::NotFound(_)) => Ok(self.root.clone()),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn remove_empty_slot(&self, path: &Path) -> Result<()> {
+        let Some(slot) = path.parent() else {
+            return Ok(());
+        };
+        if slot.parent() == Some(self.trash_dir().as_path()) {
+            fs::remove_dir_all(slot)?;
+        }
+
+        Ok(())
+    }
+
+    /// The entry recorded for each top-level item in the trash and its path.
+    fn trash_slots(&self) -> Result<Vec<(TrashEntry, PathBuf)>> {
+        let trash_dir = self.trash_dir();
+        if !trash_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut slots = Vec::new();
+        for slot in fs::read_dir(trash_dir)? {
+            let slot = slot?.path();
+            let entry = read_trash_entry(&slot)?;
+            let item = super::visible_entries(&slot)?.into_iter().next();
+            if let Some(item) = item
+                && (item.is_file() || read_sidecar(&item)?.is_some())
+            {
+                slots.push((entry, item));
+            }
+        }
+
+        Ok(slots)
+    }
+}
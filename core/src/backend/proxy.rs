@@ -1,7 +1,15 @@
+pub mod change;
 pub mod client;
+pub mod error;
+pub mod protocol;
 pub mod request;
 pub mod response;
+pub mod scope;
 pub mod server;
 
+pub use change::{Change, ChangeFeed};
 pub use client::ProxyClient;
+pub use error::{ErrorCode, ProxyError};
+pub use protocol::{Capability, PROTOCOL_VERSION, ServerHello};
+pub use scope::{Access, AccessScope};
 pub use server::ProxyServer;
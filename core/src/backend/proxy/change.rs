@@ -0,0 +1,211 @@
+use super::request::ProxyRequest;
+use super::response::ResultPayload;
+use crate::{
+    Error, Result,
+    types::{DirectoryId, NoteId},
+};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+
+/// A change to the notebook made through a [`ProxyServer`], streamed to
+/// other clients so they can refresh.
+///
+/// [`ProxyServer`]: super::ProxyServer
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    /// A directory was created or restored from the trash.
+    DirectoryAdded {
+        directory_id: DirectoryId,
+        parent_id: DirectoryId,
+    },
+    DirectoryRemoved {
+        directory_id: DirectoryId,
+    },
+    DirectoryMoved {
+        directory_id: DirectoryId,
+        parent_id: DirectoryId,
+    },
+    DirectoryRenamed {
+        directory_id: DirectoryId,
+        name: String,
+    },
+    /// A note was created or restored from the trash.
+    NoteAdded {
+        note_id: NoteId,
+        directory_id: DirectoryId,
+    },
+    NoteRemoved {
+        note_id: NoteId,
+    },
+    NoteRenamed {
+        note_id: NoteId,
+        name: String,
+    },
+    NoteMoved {
+        note_id: NoteId,
+        directory_id: DirectoryId,
+    },
+    NoteContentUpdated {
+        note_id: NoteId,
+    },
+    NoteTagsChanged {
+        note_id: NoteId,
+    },
+    TrashEmptied,
+}
+
+impl Change {
+    /// Whether the change touches what is listed in the note tree, as opposed
+    /// to only a note's content or tags.
+    pub fn affects_tree(&self) -> bool {
+        !matches!(
+            self,
+            Change::NoteContentUpdated { .. } | Change::NoteTagsChanged { .. }
+        )
+    }
+}
+
+/// What is known about the change of a request before it runs; the IDs of
+/// created entries only come with the response.
+pub(super) enum PendingChange {
+    Known(Change),
+    Created,
+}
+
+impl PendingChange {
+    pub(super) fn of(req: &ProxyRequest) -> Option<Self> {
+        use ProxyRequest::*;
+
+        let change = match req {
+            AddDirectory { .. } | AddNote { .. } | RestoreNote { .. } | RestoreDirectory { .. } => {
+                return Some(Self::Created);
+            }
+            RemoveDirectory { directory_id } => Change::DirectoryRemoved {
+                directory_id: directory_id.clone(),
+            },
+            MoveDirectory {
+                directory_id,
+                parent_id,
+            } => Change::DirectoryMoved {
+                directory_id: directory_id.clone(),
+                parent_id: parent_id.clone(),
+            },
+            RenameDirectory { directory_id, name } => Change::DirectoryRenamed {
+                directory_id: directory_id.clone(),
+                name: name.clone(),
+            },
+            RemoveNote { note_id } => Change::NoteRemoved {
+                note_id: note_id.clone(),
+            },
+            RenameNote { note_id, name, .. } => Change::NoteRenamed {
+                note_id: note_id.clone(),
+                name: name.clone(),
+            },
+            MoveNote {
+                note_id,
+                directory_id,
+                ..
+            } => Change::NoteMoved {
+                note_id: note_id.clone(),
+                directory_id: directory_id.clone(),
+            },
+            UpdateNoteContent { note_id, .. } => Change::NoteContentUpdated {
+                note_id: note_id.clone(),
+            },
+            AddNoteTag { note_id, .. } | RemoveNoteTag { note_id, .. } => Change::NoteTagsChanged {
+                note_id: note_id.clone(),
+            },
+            Purge => Change::TrashEmptied,
+            _ => return None,
+        };
+
+        Some(Self::Known(change))
+    }
+
+    /// The change made once the request succeeded with `payload`.
+    pub(super) fn resolve(self, payload: &ResultPayload) -> Option<Change> {
+        match (self, payload) {
+            (Self::Known(change), _) => Some(change),
+            (Self::Created, ResultPayload::Directory(directory)) => Some(Change::DirectoryAdded {
+                directory_id: directory.id.clone(),
+                parent_id: directory.parent_id.clone(),
+            }),
+            (Self::Created, ResultPayload::Note(note)) => Some(Change::NoteAdded {
+                note_id: note.id.clone(),
+                directory_id: note.directory_id.clone(),
+            }),
+            (Self::Created, _) => None,
+        }
+    }
+}
+
+/// A subscription to the change feed of a proxy server, run in the
+/// background much like a [`SyncJob`].
+///
+/// [`SyncJob`]: crate::backend::SyncJob
+#[derive(Clone, Debug)]
+pub struct ChangeFeed {
+    pub url: String,
+    pub auth_token: Option<String>,
+}
+
+impl ChangeFeed {
+    /// Streams changes into `on_change` until the server closes the feed.
+    pub async fn run<F>(self, mut on_change: F) -> Result<()>
+    where
+        F: FnMut(Change) + Send,
+    {
+        let mut request = Client::new().get(&self.url);
+        if let Some(token) = self.auth_token.as_ref() {
+            request = request.bearer_auth(token);
+        }
+        let mut resp = request.send().await?;
+        match resp.status() {
+            StatusCode::UNAUTHORIZED => {
+                return Err(Error::Unauthorized(
+                    "proxy server rejected the authentication token".to_owned(),
+                ));
+            }
+            status if !status.is_success() => {
+                return Err(Error::Proxy(format!(
+                    "change feed failed with status {status}"
+                )));
+            }
+            _ => {}
+        }
+
+        let mut buffer = Vec::new();
+        while let Some(chunk) = resp.chunk().await? {
+            buffer.extend_from_slice(&chunk);
+            while let Some(end) = buffer.windows(2).position(|window| window == b"\n\n") {
+                let event = buffer.drain(..end + 2).collect::<Vec<_>>();
+
+                if let Some(change) = parse_event(&String::from_utf8_lossy(&event))? {
+                    on_change(change);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the `data` of a server-sent event; comments such as keep-alives
+/// carry none.
+fn parse_event(event: &str) -> Result<Option<Change>> {
+    let data = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(str::trim_start)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if data.is_empty() {
+        return Ok(None);
+    }
+
+    serde_json::from_str(&data)
+        .map(Some)
+        .map_err(|err| Error::InvalidResponse(format!("invalid change event: {err}")))
+}
@@ -1,64 +1,206 @@
+use super::change::ChangeFeed;
+use super::protocol::{Capability, PROTOCOL_VERSION, ServerHello};
 use super::request::ProxyRequest;
 use super::response::{ProxyResponse, ResultPayload};
 use crate::{
     Error, Result,
     backend::{CoreBackend, SyncJob},
-    data::{Directory, Note},
-    types::{DirectoryId, NoteId},
+    data::{Directory, Note, NoteRevision, NoteSearchResult, Tag, Trash},
+    types::{DirectoryId, NoteId, RevisionId},
 };
 use async_trait::async_trait;
 use reqwest::{Client, StatusCode};
+use std::collections::HashMap;
 
 pub struct ProxyClient {
     url: String,
     client: Client,
-    root_id: DirectoryId,
     auth_token: Option<String>,
+    server: ServerHello,
+    prefetched: Prefetched,
+}
+
+/// Tree listings fetched ahead of time so that opening a directory, which
+/// asks for its notes and its sub-directories, costs a single round-trip.
+///
+/// Only the latest listings are kept and each entry is handed out once, so
+/// changes by other clients are never hidden for long; everything is dropped
+/// as soon as the client sends a change or syncs.
+#[derive(Default)]
+struct Prefetched {
+    directories: HashMap<DirectoryId, Directory>,
+    children: HashMap<DirectoryId, Vec<Directory>>,
+    notes: HashMap<DirectoryId, Vec<Note>>,
+}
+
+impl Prefetched {
+    fn store(&mut self, parent_id: DirectoryId, children: Vec<Directory>, notes: Vec<Note>) {
+        self.directories = children
+            .iter()
+            .map(|directory| (directory.id.clone(), directory.clone()))
+            .collect();
+        self.children = HashMap::from([(parent_id.clone(), children)]);
+        self.notes = HashMap::from([(parent_id, notes)]);
+    }
 }
 
 impl ProxyClient {
+    /// Connects to a proxy server, agreeing on the protocol first.
+    ///
+    /// Servers from before the handshake existed are still accepted; requests
+    /// they cannot answer then fail with [`Error::Unsupported`].
     pub async fn connect<U: Into<String>>(url: U, auth_token: Option<String>) -> Result<Self> {
-        let url = url.into();
-        let client = Client::new();
-        let mut request = client.post(&url).json(&ProxyRequest::RootId);
-        if let Some(token) = auth_token.as_ref() {
-            request = request.bearer_auth(token);
+        let mut client = Self {
+            url: url.into(),
+            client: Client::new(),
+            auth_token,
+            server: ServerHello::legacy(DirectoryId::new()),
+            prefetched: Prefetched::default(),
+        };
+
+        let hello = ProxyRequest::Hello {
+            version: PROTOCOL_VERSION,
+            capabilities: Capability::ALL.to_vec(),
+        };
+        let resp = client.send(&hello).await?;
+        let legacy = matches!(
+            resp.status(),
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY
+        );
+        client.server = if legacy {
+            // older servers reject the unknown `Hello` method outright
+            match client.send(&ProxyRequest::RootId).await?.json().await? {
+                ProxyResponse::Ok(ResultPayload::Id(id)) => ServerHello::legacy(id),
+                ProxyResponse::Err(e) => return Err(e.into()),
+                ProxyResponse::Ok(_) => {
+                    return Err(Error::InvalidResponse("invalid response".to_owned()));
+                }
+            }
+        } else {
+            match resp.json().await? {
+                ProxyResponse::Ok(ResultPayload::Hello(hello)) => hello,
+                ProxyResponse::Err(e) => return Err(e.into()),
+                ProxyResponse::Ok(_) => {
+                    return Err(Error::InvalidResponse("invalid response".to_owned()));
+                }
+            }
+        };
+
+        Ok(client)
+    }
+
+    /// The server's stream of changes, served next to the proxy endpoint.
+    pub fn subscribe(&self) -> Result<ChangeFeed> {
+        self.require(Capability::Changes)?;
+
+        Ok(ChangeFeed {
+            url: format!("{}/events", self.url.trim_end_matches('/')),
+            auth_token: self.auth_token.clone(),
+        })
+    }
+
+    /// What the server said about itself when the client connected.
+    pub fn server(&self) -> &ServerHello {
+        &self.server
+    }
+
+    fn require(&self, capability: Capability) -> Result<()> {
+        if self.server.supports(capability) {
+            return Ok(());
         }
-        let resp = request.send().await?;
-        if resp.status() == StatusCode::UNAUTHORIZED {
-            return Err(Error::Proxy(
-                "proxy server rejected the authentication token".to_owned(),
-            ));
+
+        Err(Error::Unsupported(format!(
+            "the proxy server does not support {}; upgrade glues server to use it",
+            capability.name()
+        )))
+    }
+
+    /// Reads the note returned by a rename, move or content update; servers
+    /// from before conflict checks answer with nothing, so it is refetched.
+    async fn changed_note(&mut self, note_id: NoteId, response: ProxyResponse) -> Result<Note> {
+        match response {
+            ProxyResponse::Ok(ResultPayload::Note(note)) => Ok(note),
+            ProxyResponse::Ok(ResultPayload::Unit) => self.fetch_note(note_id).await,
+            ProxyResponse::Err(e) => Err(e.into()),
+            ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
-        let resp: ProxyResponse = resp.json().await?;
-        let root_id = match resp {
-            ProxyResponse::Ok(ResultPayload::Id(id)) => id,
-            ProxyResponse::Err(e) => return Err(Error::Proxy(e)),
+    }
+
+    /// Sends `requests` in one round-trip and returns a response for each.
+    pub async fn batch(&mut self, requests: Vec<ProxyRequest>) -> Result<Vec<ProxyResponse>> {
+        self.require(Capability::Batch)?;
+
+        let len = requests.len();
+        match self.rpc(ProxyRequest::Batch(requests)).await? {
+            ProxyResponse::Ok(ResultPayload::Batch(responses)) if responses.len() == len => {
+                Ok(responses)
+            }
+            ProxyResponse::Err(e) => Err(e.into()),
+            ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
+        }
+    }
+
+    /// Loads the sub-directories and notes of `directory_id` together, in a
+    /// single round-trip when the server supports batches.
+    async fn prefetch(&mut self, directory_id: DirectoryId) -> Result<()> {
+        let requests = [
+            ProxyRequest::FetchDirectories {
+                parent_id: directory_id.clone(),
+            },
+            ProxyRequest::FetchNotes {
+                directory_id: directory_id.clone(),
+            },
+        ];
+        let (directories, notes) = if self.server.supports(Capability::Batch) {
+            let responses = self.batch(requests.into()).await?;
+            let [directories, notes] = <[ProxyResponse; 2]>::try_from(responses)
+                .map_err(|_| Error::InvalidResponse("invalid response".to_owned()))?;
+            (directories, notes)
+        } else {
+            let [directories, notes] = requests;
+            (self.rpc(directories).await?, self.rpc(notes).await?)
+        };
+
+        let directories = match directories {
+            ProxyResponse::Ok(ResultPayload::Directories(dirs)) => dirs,
+            ProxyResponse::Err(e) => return Err(e.into()),
+            ProxyResponse::Ok(_) => {
+                return Err(Error::InvalidResponse("invalid response".to_owned()));
+            }
+        };
+        let notes = match notes {
+            ProxyResponse::Ok(ResultPayload::Notes(notes)) => notes,
+            ProxyResponse::Err(e) => return Err(e.into()),
             ProxyResponse::Ok(_) => {
                 return Err(Error::InvalidResponse("invalid response".to_owned()));
             }
         };
 
-        Ok(Self {
-            url,
-            client,
-            root_id,
-            auth_token,
-        })
+        self.prefetched.store(directory_id, directories, notes);
+        Ok(())
     }
 
-    async fn rpc(&self, req: ProxyRequest) -> Result<ProxyResponse> {
-        let mut request = self.client.post(&self.url).json(&req);
+    async fn send(&self, req: &ProxyRequest) -> Result<reqwest::Response> {
+        let mut request = self.client.post(&self.url).json(req);
         if let Some(token) = self.auth_token.as_ref() {
             request = request.bearer_auth(token);
         }
         let resp = request.send().await?;
         if resp.status() == StatusCode::UNAUTHORIZED {
-            return Err(Error::Proxy(
+            return Err(Error::Unauthorized(
                 "proxy server rejected the authentication token".to_owned(),
             ));
         }
-        let resp: ProxyResponse = resp.json().await?;
+
+        Ok(resp)
+    }
+
+    async fn rpc(&mut self, req: ProxyRequest) -> Result<ProxyResponse> {
+        if req.is_write() || matches!(req, ProxyRequest::Sync) {
+            self.prefetched = Prefetched::default();
+        }
+
+        let resp: ProxyResponse = self.send(&req).await?.json().await?;
         Ok(resp)
     }
 }
@@ -66,29 +208,33 @@ impl ProxyClient {
 #[async_trait]
 impl CoreBackend for ProxyClient {
     fn root_id(&self) -> DirectoryId {
-        self.root_id.clone()
+        self.server.root_id.clone()
     }
 
     async fn fetch_directory(&mut self, directory_id: DirectoryId) -> Result<Directory> {
+        if let Some(directory) = self.prefetched.directories.remove(&directory_id) {
+            return Ok(directory);
+        }
+
         match self
             .rpc(ProxyRequest::FetchDirectory { directory_id })
             .await?
         {
             ProxyResponse::Ok(ResultPayload::Directory(dir)) => Ok(dir),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
+            ProxyResponse::Err(e) => Err(e.into()),
             ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
     }
 
     async fn fetch_directories(&mut self, parent_id: DirectoryId) -> Result<Vec<Directory>> {
-        match self
-            .rpc(ProxyRequest::FetchDirectories { parent_id })
-            .await?
-        {
-            ProxyResponse::Ok(ResultPayload::Directories(dirs)) => Ok(dirs),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
-            ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
+        if !self.prefetched.children.contains_key(&parent_id) {
+            self.prefetch(parent_id.clone()).await?;
         }
+
+        self.prefetched
+            .children
+            .remove(&parent_id)
+            .ok_or_else(|| Error::InvalidResponse("invalid response".to_owned()))
     }
 
     async fn add_directory(&mut self, parent_id: DirectoryId, name: String) -> Result<Directory> {
@@ -97,7 +243,7 @@ impl CoreBackend for ProxyClient {
             .await?
         {
             ProxyResponse::Ok(ResultPayload::Directory(dir)) => Ok(dir),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
+            ProxyResponse::Err(e) => Err(e.into()),
             ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
     }
@@ -108,7 +254,7 @@ impl CoreBackend for ProxyClient {
             .await?
         {
             ProxyResponse::Ok(ResultPayload::Unit) => Ok(()),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
+            ProxyResponse::Err(e) => Err(e.into()),
             ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
     }
@@ -126,7 +272,7 @@ impl CoreBackend for ProxyClient {
             .await?
         {
             ProxyResponse::Ok(ResultPayload::Unit) => Ok(()),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
+            ProxyResponse::Err(e) => Err(e.into()),
             ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
     }
@@ -137,23 +283,34 @@ impl CoreBackend for ProxyClient {
             .await?
         {
             ProxyResponse::Ok(ResultPayload::Unit) => Ok(()),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
+            ProxyResponse::Err(e) => Err(e.into()),
             ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
     }
 
-    async fn fetch_notes(&mut self, directory_id: DirectoryId) -> Result<Vec<Note>> {
-        match self.rpc(ProxyRequest::FetchNotes { directory_id }).await? {
-            ProxyResponse::Ok(ResultPayload::Notes(notes)) => Ok(notes),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
+    async fn fetch_note(&mut self, note_id: NoteId) -> Result<Note> {
+        match self.rpc(ProxyRequest::FetchNote { note_id }).await? {
+            ProxyResponse::Ok(ResultPayload::Note(note)) => Ok(note),
+            ProxyResponse::Err(e) => Err(e.into()),
             ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
     }
 
+    async fn fetch_notes(&mut self, directory_id: DirectoryId) -> Result<Vec<Note>> {
+        if !self.prefetched.notes.contains_key(&directory_id) {
+            self.prefetch(directory_id.clone()).await?;
+        }
+
+        self.prefetched
+            .notes
+            .remove(&directory_id)
+            .ok_or_else(|| Error::InvalidResponse("invalid response".to_owned()))
+    }
+
     async fn fetch_note_content(&mut self, note_id: NoteId) -> Result<String> {
         match self.rpc(ProxyRequest::FetchNoteContent { note_id }).await? {
             ProxyResponse::Ok(ResultPayload::Text(text)) => Ok(text),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
+            ProxyResponse::Err(e) => Err(e.into()),
             ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
     }
@@ -164,7 +321,7 @@ impl CoreBackend for ProxyClient {
             .await?
         {
             ProxyResponse::Ok(ResultPayload::Note(note)) => Ok(note),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
+            ProxyResponse::Err(e) => Err(e.into()),
             ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
     }
@@ -172,40 +329,197 @@ impl CoreBackend for ProxyClient {
     async fn remove_note(&mut self, note_id: NoteId) -> Result<()> {
         match self.rpc(ProxyRequest::RemoveNote { note_id }).await? {
             ProxyResponse::Ok(ResultPayload::Unit) => Ok(()),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
+            ProxyResponse::Err(e) => Err(e.into()),
             ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
     }
 
-    async fn rename_note(&mut self, note_id: NoteId, name: String) -> Result<()> {
-        match self.rpc(ProxyRequest::RenameNote { note_id, name }).await? {
+    async fn rename_note(
+        &mut self,
+        note_id: NoteId,
+        name: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        let response = self
+            .rpc(ProxyRequest::RenameNote {
+                note_id: note_id.clone(),
+                name,
+                expected_updated_at,
+            })
+            .await?;
+
+        self.changed_note(note_id, response).await
+    }
+
+    async fn update_note_content(
+        &mut self,
+        note_id: NoteId,
+        content: String,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        let response = self
+            .rpc(ProxyRequest::UpdateNoteContent {
+                note_id: note_id.clone(),
+                content,
+                expected_updated_at,
+            })
+            .await?;
+
+        self.changed_note(note_id, response).await
+    }
+
+    async fn move_note(
+        &mut self,
+        note_id: NoteId,
+        directory_id: DirectoryId,
+        expected_updated_at: Option<String>,
+    ) -> Result<Note> {
+        let response = self
+            .rpc(ProxyRequest::MoveNote {
+                note_id: note_id.clone(),
+                directory_id,
+                expected_updated_at,
+            })
+            .await?;
+
+        self.changed_note(note_id, response).await
+    }
+
+    async fn list_note_revisions(&mut self, note_id: NoteId) -> Result<Vec<NoteRevision>> {
+        self.require(Capability::Revisions)?;
+
+        match self
+            .rpc(ProxyRequest::ListNoteRevisions { note_id })
+            .await?
+        {
+            ProxyResponse::Ok(ResultPayload::Revisions(revisions)) => Ok(revisions),
+            ProxyResponse::Err(e) => Err(e.into()),
+            ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
+        }
+    }
+
+    async fn fetch_note_revision(
+        &mut self,
+        note_id: NoteId,
+        revision_id: RevisionId,
+    ) -> Result<String> {
+        self.require(Capability::Revisions)?;
+
+        match self
+            .rpc(ProxyRequest::FetchNoteRevision {
+                note_id,
+                revision_id,
+            })
+            .await?
+        {
+            ProxyResponse::Ok(ResultPayload::Text(content)) => Ok(content),
+            ProxyResponse::Err(e) => Err(e.into()),
+            ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
+        }
+    }
+
+    async fn search_notes(&mut self, query: String) -> Result<Vec<NoteSearchResult>> {
+        self.require(Capability::Search)?;
+
+        match self.rpc(ProxyRequest::SearchNotes { query }).await? {
+            ProxyResponse::Ok(ResultPayload::SearchResults(results)) => Ok(results),
+            ProxyResponse::Err(e) => Err(e.into()),
+            ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
+        }
+    }
+
+    async fn fetch_backlinks(&mut self, note_id: NoteId) -> Result<Vec<Note>> {
+        self.require(Capability::Links)?;
+
+        match self.rpc(ProxyRequest::FetchBacklinks { note_id }).await? {
+            ProxyResponse::Ok(ResultPayload::Notes(notes)) => Ok(notes),
+            ProxyResponse::Err(e) => Err(e.into()),
+            ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
+        }
+    }
+
+    async fn fetch_tags(&mut self) -> Result<Vec<Tag>> {
+        self.require(Capability::Tags)?;
+
+        match self.rpc(ProxyRequest::FetchTags).await? {
+            ProxyResponse::Ok(ResultPayload::Tags(tags)) => Ok(tags),
+            ProxyResponse::Err(e) => Err(e.into()),
+            ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
+        }
+    }
+
+    async fn add_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()> {
+        self.require(Capability::Tags)?;
+
+        match self.rpc(ProxyRequest::AddNoteTag { note_id, name }).await? {
             ProxyResponse::Ok(ResultPayload::Unit) => Ok(()),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
+            ProxyResponse::Err(e) => Err(e.into()),
             ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
     }
 
-    async fn update_note_content(&mut self, note_id: NoteId, content: String) -> Result<()> {
+    async fn remove_note_tag(&mut self, note_id: NoteId, name: String) -> Result<()> {
+        self.require(Capability::Tags)?;
+
         match self
-            .rpc(ProxyRequest::UpdateNoteContent { note_id, content })
+            .rpc(ProxyRequest::RemoveNoteTag { note_id, name })
             .await?
         {
             ProxyResponse::Ok(ResultPayload::Unit) => Ok(()),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
+            ProxyResponse::Err(e) => Err(e.into()),
+            ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
+        }
+    }
+
+    async fn fetch_notes_by_tag(&mut self, name: String) -> Result<Vec<Note>> {
+        self.require(Capability::Tags)?;
+
+        match self.rpc(ProxyRequest::FetchNotesByTag { name }).await? {
+            ProxyResponse::Ok(ResultPayload::Notes(notes)) => Ok(notes),
+            ProxyResponse::Err(e) => Err(e.into()),
+            ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
+        }
+    }
+
+    async fn fetch_trash(&mut self) -> Result<Trash> {
+        self.require(Capability::Trash)?;
+
+        match self.rpc(ProxyRequest::FetchTrash).await? {
+            ProxyResponse::Ok(ResultPayload::Trash(trash)) => Ok(trash),
+            ProxyResponse::Err(e) => Err(e.into()),
+            ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
+        }
+    }
+
+    async fn restore_note(&mut self, note_id: NoteId) -> Result<Note> {
+        self.require(Capability::Trash)?;
+
+        match self.rpc(ProxyRequest::RestoreNote { note_id }).await? {
+            ProxyResponse::Ok(ResultPayload::Note(note)) => Ok(note),
+            ProxyResponse::Err(e) => Err(e.into()),
             ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
     }
 
-    async fn move_note(&mut self, note_id: NoteId, directory_id: DirectoryId) -> Result<()> {
+    async fn restore_directory(&mut self, directory_id: DirectoryId) -> Result<Directory> {
+        self.require(Capability::Trash)?;
+
         match self
-            .rpc(ProxyRequest::MoveNote {
-                note_id,
-                directory_id,
-            })
+            .rpc(ProxyRequest::RestoreDirectory { directory_id })
             .await?
         {
+            ProxyResponse::Ok(ResultPayload::Directory(directory)) => Ok(directory),
+            ProxyResponse::Err(e) => Err(e.into()),
+            ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
+        }
+    }
+
+    async fn purge(&mut self) -> Result<()> {
+        self.require(Capability::Trash)?;
+
+        match self.rpc(ProxyRequest::Purge).await? {
             ProxyResponse::Ok(ResultPayload::Unit) => Ok(()),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
+            ProxyResponse::Err(e) => Err(e.into()),
             ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
     }
@@ -213,7 +527,7 @@ impl CoreBackend for ProxyClient {
     async fn log(&mut self, category: String, message: String) -> Result<()> {
         match self.rpc(ProxyRequest::Log { category, message }).await? {
             ProxyResponse::Ok(ResultPayload::Unit) => Ok(()),
-            ProxyResponse::Err(e) => Err(Error::Proxy(e)),
+            ProxyResponse::Err(e) => Err(e.into()),
             ProxyResponse::Ok(_) => Err(Error::InvalidResponse("invalid response".to_owned())),
         }
     }
@@ -224,4 +538,8 @@ impl CoreBackend for ProxyClient {
             auth_token: self.auth_token.clone(),
         })
     }
+
+    fn change_feed(&self) -> Option<ChangeFeed> {
+        self.subscribe().ok()
+    }
 }
@@ -0,0 +1,144 @@
+use crate::Error;
+use gluesql::core::row_conversion::RowConversionError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Kinds of failure a proxy server reports, mirroring [`Error`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    NotFound,
+    InvalidState,
+    InvalidResponse,
+    Unauthorized,
+    Forbidden,
+    /// The entry changed since the client last read it.
+    Conflict,
+    Unsupported,
+    SchemaVersion,
+    Todo,
+    /// The storage behind the server failed.
+    Backend,
+    /// A code introduced by a newer server, or a plain-text error from a
+    /// server that predates error codes.
+    #[serde(other)]
+    Unknown,
+}
+
+/// An error returned in [`ProxyResponse::Err`].
+///
+/// Older servers answer with a bare message, which is read back as
+/// [`ErrorCode::Unknown`].
+///
+/// [`ProxyResponse::Err`]: super::response::ProxyResponse::Err
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(from = "ProxyErrorRepr")]
+pub struct ProxyError {
+    pub code: ErrorCode,
+    pub message: String,
+    /// Where a [`ErrorCode::Backend`] failure came from, e.g. `gluesql`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+impl ProxyError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    fn backend(source: &str, message: String) -> Self {
+        Self {
+            code: ErrorCode::Backend,
+            message,
+            details: Some(source.to_owned()),
+        }
+    }
+}
+
+impl From<Error> for ProxyError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotFound(message) => Self::new(ErrorCode::NotFound, message),
+            Error::InvalidState(message) => Self::new(ErrorCode::InvalidState, message),
+            Error::InvalidResponse(message) => Self::new(ErrorCode::InvalidResponse, message),
+            Error::Unauthorized(message) => Self::new(ErrorCode::Unauthorized, message),
+            Error::Forbidden(message) => Self::new(ErrorCode::Forbidden, message),
+            Error::Conflict(message) => Self::new(ErrorCode::Conflict, message),
+            Error::Unsupported(message) => Self::new(ErrorCode::Unsupported, message),
+            Error::SchemaVersion(message) => Self::new(ErrorCode::SchemaVersion, message),
+            Error::Todo(message) => Self::new(ErrorCode::Todo, message),
+            Error::BackendError(message) => Self::new(ErrorCode::Backend, message),
+            // a lookup that matched no row, e.g. a note purged by another client
+            Error::RowConversion(RowConversionError::NotFound) => Self::new(
+                ErrorCode::NotFound,
+                RowConversionError::NotFound.to_string(),
+            ),
+            Error::GlueSql(error) => Self::backend("gluesql", error.to_string()),
+            Error::RowConversion(error) => Self::backend("row conversion", error.to_string()),
+            Error::Io(error) => Self::backend("io", error.to_string()),
+            Error::Reqwest(error) => Self::backend("reqwest", error.to_string()),
+            Error::Proxy(message) => Self::backend("proxy", message),
+        }
+    }
+}
+
+impl From<ProxyError> for Error {
+    fn from(error: ProxyError) -> Self {
+        let ProxyError { code, message, .. } = error;
+        match code {
+            ErrorCode::NotFound => Error::NotFound(message),
+            ErrorCode::InvalidState => Error::InvalidState(message),
+            ErrorCode::InvalidResponse => Error::InvalidResponse(message),
+            ErrorCode::Unauthorized => Error::Unauthorized(message),
+            ErrorCode::Forbidden => Error::Forbidden(message),
+            ErrorCode::Conflict => Error::Conflict(message),
+            ErrorCode::Unsupported => Error::Unsupported(message),
+            ErrorCode::SchemaVersion => Error::SchemaVersion(message),
+            ErrorCode::Todo => Error::Todo(message),
+            ErrorCode::Backend => Error::BackendError(message),
+            ErrorCode::Unknown => Error::Proxy(message),
+        }
+    }
+}
+
+/// Formats like the [`Error`] the client turns it into.
+impl fmt::Display for ProxyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Error::from(self.clone()).fmt(f)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ProxyErrorRepr {
+    Message(String),
+    Structured {
+        code: ErrorCode,
+        message: String,
+        #[serde(default)]
+        details: Option<String>,
+    },
+}
+
+impl From<ProxyErrorRepr> for ProxyError {
+    fn from(repr: ProxyErrorRepr) -> Self {
+        match repr {
+            ProxyErrorRepr::Message(message) => Self::new(ErrorCode::Unknown, message),
+            ProxyErrorRepr::Structured {
+                code,
+                message,
+                details,
+            } => Self {
+                code,
+                message,
+                details,
+            },
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::types::DirectoryId;
+use serde::{Deserialize, Serialize};
+
+/// Version of the proxy protocol spoken by this build. Bump it whenever a
+/// request or response changes shape.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Oldest client protocol version the server still answers.
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Optional groups of requests a proxy server may support.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum Capability {
+    Sync,
+    Search,
+    Tags,
+    Trash,
+    Revisions,
+    /// Backlinks between notes.
+    Links,
+    Batch,
+    /// The `GET /events` change feed.
+    Changes,
+    /// A capability introduced by a newer peer.
+    #[serde(other)]
+    Unknown,
+}
+
+impl Capability {
+    /// Capabilities of this build.
+    pub const ALL: [Capability; 8] = [
+        Capability::Sync,
+        Capability::Search,
+        Capability::Tags,
+        Capability::Trash,
+        Capability::Revisions,
+        Capability::Links,
+        Capability::Batch,
+        Capability::Changes,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Capability::Sync => "sync",
+            Capability::Search => "search",
+            Capability::Tags => "tags",
+            Capability::Trash => "trash",
+            Capability::Revisions => "revisions",
+            Capability::Links => "backlinks",
+            Capability::Batch => "batched requests",
+            Capability::Changes => "the change feed",
+            Capability::Unknown => "unknown",
+        }
+    }
+}
+
+/// The server's answer to [`ProxyRequest::Hello`].
+///
+/// [`ProxyRequest::Hello`]: super::request::ProxyRequest::Hello
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ServerHello {
+    pub version: u32,
+    pub capabilities: Vec<Capability>,
+    pub root_id: DirectoryId,
+}
+
+impl ServerHello {
+    /// What a server from before the handshake existed is assumed to offer.
+    pub fn legacy(root_id: DirectoryId) -> Self {
+        Self {
+            version: 0,
+            capabilities: vec![Capability::Sync],
+            root_id,
+        }
+    }
+
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.capabilities.contains(&capability)
+    }
+}
@@ -1,9 +1,16 @@
-use crate::types::{DirectoryId, NoteId};
+use super::protocol::Capability;
+use crate::types::{DirectoryId, NoteId, RevisionId};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "method", content = "data")]
 pub enum ProxyRequest {
+    /// Opens a session by exchanging protocol versions and capabilities.
+    Hello {
+        version: u32,
+        capabilities: Vec<Capability>,
+    },
     RootId,
     FetchDirectory {
         directory_id: DirectoryId,
@@ -26,6 +33,9 @@ pub enum ProxyRequest {
         directory_id: DirectoryId,
         name: String,
     },
+    FetchNote {
+        note_id: NoteId,
+    },
     FetchNotes {
         directory_id: DirectoryId,
     },
@@ -42,18 +52,91 @@ pub enum ProxyRequest {
     RenameNote {
         note_id: NoteId,
         name: String,
+        /// Rejects the request with a conflict when the note changed since.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expected_updated_at: Option<String>,
     },
     UpdateNoteContent {
         note_id: NoteId,
         content: String,
+        /// Rejects the request with a conflict when the note changed since.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expected_updated_at: Option<String>,
     },
     MoveNote {
         note_id: NoteId,
         directory_id: DirectoryId,
+        /// Rejects the request with a conflict when the note changed since.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expected_updated_at: Option<String>,
+    },
+    ListNoteRevisions {
+        note_id: NoteId,
+    },
+    FetchNoteRevision {
+        note_id: NoteId,
+        revision_id: RevisionId,
+    },
+    SearchNotes {
+        query: String,
+    },
+    FetchBacklinks {
+        note_id: NoteId,
+    },
+    FetchTags,
+    AddNoteTag {
+        note_id: NoteId,
+        name: String,
     },
+    RemoveNoteTag {
+        note_id: NoteId,
+        name: String,
+    },
+    FetchNotesByTag {
+        name: String,
+    },
+    FetchTrash,
+    RestoreNote {
+        note_id: NoteId,
+    },
+    RestoreDirectory {
+        directory_id: DirectoryId,
+    },
+    Purge,
     Log {
         category: String,
         message: String,
     },
     Sync,
+    /// Runs the requests in order and answers with a [`ResultPayload::Batch`]
+    /// holding one response each; batches cannot be nested.
+    ///
+    /// [`ResultPayload::Batch`]: super::response::ResultPayload::Batch
+    Batch(Vec<ProxyRequest>),
+}
+
+impl ProxyRequest {
+    /// Whether the request changes the notebook.
+    pub fn is_write(&self) -> bool {
+        use ProxyRequest::*;
+
+        match self {
+            AddDirectory { .. }
+            | RemoveDirectory { .. }
+            | MoveDirectory { .. }
+            | RenameDirectory { .. }
+            | AddNote { .. }
+            | RemoveNote { .. }
+            | RenameNote { .. }
+            | UpdateNoteContent { .. }
+            | MoveNote { .. }
+            | AddNoteTag { .. }
+            | RemoveNoteTag { .. }
+            | RestoreNote { .. }
+            | RestoreDirectory { .. }
+            | Purge => true,
+            Batch(requests) => requests.iter().any(ProxyRequest::is_write),
+            _ => false,
+        }
+    }
 }
@@ -1,22 +1,32 @@
-use crate::data::{Directory, Note};
+use super::error::ProxyError;
+use super::protocol::ServerHello;
+use crate::data::{Directory, Note, NoteRevision, NoteSearchResult, Tag, Trash};
 use crate::types::DirectoryId;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "status", content = "data")]
 pub enum ProxyResponse {
     Ok(ResultPayload),
-    Err(String),
+    Err(ProxyError),
 }
 
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", content = "value")]
 pub enum ResultPayload {
+    Hello(ServerHello),
     Id(DirectoryId),
     Directory(Directory),
     Directories(Vec<Directory>),
     Note(Note),
     Notes(Vec<Note>),
+    Revisions(Vec<NoteRevision>),
+    SearchResults(Vec<NoteSearchResult>),
+    Tags(Vec<Tag>),
+    Trash(Trash),
     Text(String),
     Unit,
+    Batch(Vec<ProxyResponse>),
 }
@@ -0,0 +1,325 @@
+use {
+    super::{
+        change::Change,
+        request::ProxyRequest,
+        response::{ProxyResponse, ResultPayload},
+        server::ProxyServer,
+    },
+    crate::{
+        Error, Result,
+        backend::CoreBackend,
+        types::{DirectoryId, NoteId},
+    },
+    serde::{Deserialize, Serialize},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Access {
+    ReadOnly,
+    ReadWrite,
+}
+
+/// What a caller of [`ProxyServer::handle`] may see and change.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessScope {
+    pub access: Access,
+    /// Roots of the directory subtrees the caller may use; empty grants the
+    /// whole notebook.
+    #[serde(default)]
+    pub directories: Vec<DirectoryId>,
+}
+
+impl AccessScope {
+    /// Read-write access to the whole notebook.
+    pub fn full() -> Self {
+        Self {
+            access: Access::ReadWrite,
+            directories: Vec::new(),
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.access == Access::ReadWrite && self.directories.is_empty()
+    }
+}
+
+/// Where a directory sits relative to the subtrees of a scope.
+#[derive(PartialEq)]
+enum Visibility {
+    /// Inside one of the subtrees.
+    Inside,
+    /// Above a subtree; only its name and the path down are visible.
+    Ancestor,
+    Hidden,
+}
+
+impl ProxyServer {
+    pub(super) async fn handle_scoped(
+        &mut self,
+        scope: &AccessScope,
+        req: ProxyRequest,
+    ) -> Result<ProxyResponse> {
+        use ProxyRequest::*;
+
+        if scope.access == Access::ReadOnly && req.is_write() {
+            return Err(Error::Forbidden("token is read-only".to_owned()));
+        }
+        if scope.directories.is_empty() {
+            return Ok(self.dispatch(req).await);
+        }
+
+        match &req {
+            Hello { .. } | RootId | Log { .. } | Sync | Batch(_) => {}
+            FetchDirectory { directory_id } => {
+                if self.visibility(scope, directory_id).await? == Visibility::Hidden {
+                    return Err(out_of_scope());
+                }
+            }
+            FetchDirectories { parent_id } => match self.visibility(scope, parent_id).await? {
+                Visibility::Inside => {}
+                Visibility::Ancestor => {
+                    let ProxyResponse::Ok(ResultPayload::Directories(directories)) =
+                        self.dispatch(req).await
+                    else {
+                        return Err(Error::InvalidResponse("invalid response".to_owned()));
+                    };
+
+                    let mut visible = Vec::new();
+                    for directory in directories {
+                        if self.visibility(scope, &directory.id).await? != Visibility::Hidden {
+                            visible.push(directory);
+                        }
+                    }
+
+                    return Ok(ProxyResponse::Ok(ResultPayload::Directories(visible)));
+                }
+                Visibility::Hidden => return Err(out_of_scope()),
+            },
+            FetchNotes { directory_id } => match self.visibility(scope, directory_id).await? {
+                Visibility::Inside => {}
+                Visibility::Ancestor => {
+                    return Ok(ProxyResponse::Ok(ResultPayload::Notes(Vec::new())));
+                }
+                Visibility::Hidden => return Err(out_of_scope()),
+            },
+            FetchNote { note_id }
+            | FetchNoteContent { note_id }
+            | RemoveNote { note_id }
+            | RenameNote { note_id, .. }
+            | UpdateNoteContent { note_id, .. }
+            | ListNoteRevisions { note_id }
+            | FetchNoteRevision { note_id, .. }
+            | AddNoteTag { note_id, .. }
+            | RemoveNoteTag { note_id, .. }
+            | RestoreNote { note_id } => {
+                self.require_note(scope, note_id).await?;
+            }
+            AddDirectory {
+                parent_id: directory_id,
+                ..
+            }
+            | RemoveDirectory { directory_id }
+            | RenameDirectory { directory_id, .. }
+            | AddNote { directory_id, .. }
+            | RestoreDirectory { directory_id } => {
+                self.require_directory(scope, directory_id).await?;
+            }
+            MoveDirectory {
+                directory_id,
+                parent_id,
+            } => {
+                self.require_directory(scope, directory_id).await?;
+                self.require_directory(scope, parent_id).await?;
+            }
+            MoveNote {
+                note_id,
+                directory_id,
+                ..
+            } => {
+                self.require_note(scope, note_id).await?;
+                self.require_directory(scope, directory_id).await?;
+            }
+            SearchNotes { .. } => {
+                let ProxyResponse::Ok(ResultPayload::SearchResults(results)) =
+                    self.dispatch(req).await
+                else {
+                    return Err(Error::InvalidResponse("invalid response".to_owned()));
+                };
+
+                let mut visible = Vec::new();
+                for result in results {
+                    if self.is_inside(scope, &result.note.directory_id).await? {
+                        visible.push(result);
+                    }
+                }
+
+                return Ok(ProxyResponse::Ok(ResultPayload::SearchResults(visible)));
+            }
+            FetchBacklinks { note_id } => {
+                self.require_note(scope, note_id).await?;
+
+                let ProxyResponse::Ok(ResultPayload::Notes(notes)) = self.dispatch(req).await
+                else {
+                    return Err(Error::InvalidResponse("invalid response".to_owned()));
+                };
+
+                let mut visible = Vec::new();
+                for note in notes {
+                    if self.is_inside(scope, &note.directory_id).await? {
+                        visible.push(note);
+                    }
+                }
+
+                return Ok(ProxyResponse::Ok(ResultPayload::Notes(visible)));
+            }
+            FetchNotesByTag { .. } => {
+                let ProxyResponse::Ok(ResultPayload::Notes(notes)) = self.dispatch(req).await
+                else {
+                    return Err(Error::InvalidResponse("invalid response".to_owned()));
+                };
+
+                let mut visible = Vec::new();
+                for note in notes {
+                    if self.is_inside(scope, &note.directory_id).await? {
+                        visible.push(note);
+                    }
+                }
+
+                return Ok(ProxyResponse::Ok(ResultPayload::Notes(visible)));
+            }
+            FetchTags => {
+                let tags = self.db.fetch_tags().await?;
+
+                let mut visible = Vec::new();
+                for tag in tags {
+                    for note in self.db.fetch_notes_by_tag(tag.name.clone()).await? {
+                        if self.is_inside(scope, &note.directory_id).await? {
+                            visible.push(tag);
+                            break;
+                        }
+                    }
+                }
+
+                return Ok(ProxyResponse::Ok(ResultPayload::Tags(visible)));
+            }
+            FetchTrash => {
+                let mut trash = self.db.fetch_trash().await?;
+
+                let mut directories = Vec::new();
+                for directory in trash.directories {
+                    if self.is_inside(scope, &directory.id).await? {
+                        directories.push(directory);
+                    }
+                }
+                let mut notes = Vec::new();
+                for note in trash.notes {
+                    if self.is_inside(scope, &note.directory_id).await? {
+                        notes.push(note);
+                    }
+                }
+                trash.directories = directories;
+                trash.notes = notes;
+
+                return Ok(ProxyResponse::Ok(ResultPayload::Trash(trash)));
+            }
+            Purge => {
+                return Err(Error::Forbidden(
+                    "emptying the trash needs access to the whole notebook".to_owned(),
+                ));
+            }
+        }
+
+        Ok(self.dispatch(req).await)
+    }
+
+    /// Whether a caller limited to `scope` may learn about `change`.
+    pub async fn can_see(&mut self, scope: &AccessScope, change: &Change) -> bool {
+        if scope.directories.is_empty() {
+            return true;
+        }
+
+        match change {
+            Change::DirectoryAdded { directory_id, .. }
+            | Change::DirectoryRemoved { directory_id }
+            | Change::DirectoryMoved { directory_id, .. }
+            | Change::DirectoryRenamed { directory_id, .. } => matches!(
+                self.visibility(scope, directory_id).await,
+                Ok(Visibility::Inside | Visibility::Ancestor)
+            ),
+            Change::NoteAdded { note_id, .. }
+            | Change::NoteRemoved { note_id }
+            | Change::NoteRenamed { note_id, .. }
+            | Change::NoteMoved { note_id, .. }
+            | Change::NoteContentUpdated { note_id }
+            | Change::NoteTagsChanged { note_id } => {
+                matches!(self.require_note(scope, note_id).await, Ok(()))
+            }
+            Change::TrashEmptied => false,
+        }
+    }
+
+    async fn require_directory(
+        &mut self,
+        scope: &AccessScope,
+        directory_id: &DirectoryId,
+    ) -> Result<()> {
+        if self.is_inside(scope, directory_id).await? {
+            Ok(())
+        } else {
+            Err(out_of_scope())
+        }
+    }
+
+    async fn require_note(&mut self, scope: &AccessScope, note_id: &NoteId) -> Result<()> {
+        let note = self.db.fetch_note(note_id.clone()).await?;
+
+        self.require_directory(scope, &note.directory_id).await
+    }
+
+    async fn is_inside(&mut self, scope: &AccessScope, directory_id: &DirectoryId) -> Result<bool> {
+        let path = self.path_to_root(directory_id).await?;
+
+        Ok(path.iter().any(|id| scope.directories.contains(id)))
+    }
+
+    async fn visibility(
+        &mut self,
+        scope: &AccessScope,
+        directory_id: &DirectoryId,
+    ) -> Result<Visibility> {
+        if self.is_inside(scope, directory_id).await? {
+            return Ok(Visibility::Inside);
+        }
+
+        for root in &scope.directories {
+            if self.path_to_root(root).await?.contains(directory_id) {
+                return Ok(Visibility::Ancestor);
+            }
+        }
+
+        Ok(Visibility::Hidden)
+    }
+
+    /// IDs from `directory_id` up to and including the notebook root.
+    async fn path_to_root(&mut self, directory_id: &DirectoryId) -> Result<Vec<DirectoryId>> {
+        let root_id = self.db.root_id();
+        let mut path = vec![directory_id.clone()];
+        let mut current = directory_id.clone();
+        while current != root_id {
+            let directory = self.db.fetch_directory(current).await?;
+            if directory.parent_id == directory.id || path.contains(&directory.parent_id) {
+                break;
+            }
+
+            current = directory.parent_id;
+            path.push(current.clone());
+        }
+
+        Ok(path)
+    }
+}
+
+fn out_of_scope() -> Error {
+    Error::Forbidden("outside of the token's directories".to_owned())
+}
@@ -1,18 +1,33 @@
+use super::change::{Change, PendingChange};
+use super::error::{ErrorCode, ProxyError};
+use super::protocol::{Capability, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION, ServerHello};
 use super::request::ProxyRequest;
 use super::response::{ProxyResponse, ResultPayload};
+use super::scope::AccessScope;
 use crate::{
     Error,
     backend::{BackendBox, CoreBackend, SyncJob},
 };
-use tokio::task;
+use tokio::{sync::broadcast, task};
+
+/// How many changes a slow subscriber may fall behind before it misses some.
+const CHANGE_CAPACITY: usize = 256;
 
 pub struct ProxyServer {
     pub db: BackendBox,
+    changes: broadcast::Sender<Change>,
 }
 
 impl ProxyServer {
     pub fn new(db: BackendBox) -> Self {
-        Self { db }
+        let (changes, _) = broadcast::channel(CHANGE_CAPACITY);
+
+        Self { db, changes }
+    }
+
+    /// Receives every change made through [`ProxyServer::handle`] from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<Change> {
+        self.changes.subscribe()
     }
 
     async fn run_sync_job(job: SyncJob) -> Result<(), Error> {
@@ -21,28 +36,78 @@ impl ProxyServer {
             .map_err(|err| Error::BackendError(format!("sync task panicked: {err}")))?
     }
 
-    pub async fn handle(&mut self, req: ProxyRequest) -> ProxyResponse {
+    /// Runs `req` on behalf of a caller limited to `scope`. Requests outside
+    /// the scope are rejected before they reach the backend, and listings are
+    /// trimmed to what the scope may see.
+    pub async fn handle(&mut self, scope: &AccessScope, req: ProxyRequest) -> ProxyResponse {
+        let ProxyRequest::Batch(requests) = req else {
+            return self.handle_one(scope, req).await;
+        };
+
+        let mut responses = Vec::with_capacity(requests.len());
+        for req in requests {
+            responses.push(self.handle_one(scope, req).await);
+        }
+
+        ProxyResponse::Ok(ResultPayload::Batch(responses))
+    }
+
+    async fn handle_one(&mut self, scope: &AccessScope, req: ProxyRequest) -> ProxyResponse {
+        let pending = PendingChange::of(&req);
+        let response = if scope.is_full() {
+            self.dispatch(req).await
+        } else {
+            match self.handle_scoped(scope, req).await {
+                Ok(response) => response,
+                Err(e) => ProxyResponse::Err(e.into()),
+            }
+        };
+
+        if let (Some(pending), ProxyResponse::Ok(payload)) = (pending, &response)
+            && let Some(change) = pending.resolve(payload)
+        {
+            // nobody listening is not an error
+            let _ = self.changes.send(change);
+        }
+
+        response
+    }
+
+    pub(super) async fn dispatch(&mut self, req: ProxyRequest) -> ProxyResponse {
         use ProxyRequest::*;
         match req {
+            Hello { version, .. } if version < MIN_PROTOCOL_VERSION => {
+                ProxyResponse::Err(ProxyError::new(
+                    ErrorCode::Unsupported,
+                    format!(
+                        "client protocol v{version} is too old for this server (v{PROTOCOL_VERSION}); upgrade glues"
+                    ),
+                ))
+            }
+            Hello { .. } => ProxyResponse::Ok(ResultPayload::Hello(ServerHello {
+                version: PROTOCOL_VERSION,
+                capabilities: Capability::ALL.to_vec(),
+                root_id: self.db.root_id(),
+            })),
             RootId => ProxyResponse::Ok(ResultPayload::Id(self.db.root_id())),
             FetchDirectory { directory_id } => match self.db.fetch_directory(directory_id).await {
                 Ok(dir) => ProxyResponse::Ok(ResultPayload::Directory(dir)),
-                Err(e) => ProxyResponse::Err(e.to_string()),
+                Err(e) => ProxyResponse::Err(e.into()),
             },
             FetchDirectories { parent_id } => match self.db.fetch_directories(parent_id).await {
                 Ok(dirs) => ProxyResponse::Ok(ResultPayload::Directories(dirs)),
-                Err(e) => ProxyResponse::Err(e.to_string()),
+                Err(e) => ProxyResponse::Err(e.into()),
             },
             AddDirectory { parent_id, name } => {
                 match self.db.add_directory(parent_id, name).await {
                     Ok(dir) => ProxyResponse::Ok(ResultPayload::Directory(dir)),
-                    Err(e) => ProxyResponse::Err(e.to_string()),
+                    Err(e) => ProxyResponse::Err(e.into()),
                 }
             }
             RemoveDirectory { directory_id } => {
                 match self.db.remove_directory(directory_id).await {
                     Ok(()) => ProxyResponse::Ok(ResultPayload::Unit),
-                    Err(e) => ProxyResponse::Err(e.to_string()),
+                    Err(e) => ProxyResponse::Err(e.into()),
                 }
             }
             MoveDirectory {
@@ -50,58 +115,138 @@ impl ProxyServer {
                 parent_id,
             } => match self.db.move_directory(directory_id, parent_id).await {
                 Ok(()) => ProxyResponse::Ok(ResultPayload::Unit),
-                Err(e) => ProxyResponse::Err(e.to_string()),
+                Err(e) => ProxyResponse::Err(e.into()),
             },
             RenameDirectory { directory_id, name } => {
                 match self.db.rename_directory(directory_id, name).await {
                     Ok(()) => ProxyResponse::Ok(ResultPayload::Unit),
-                    Err(e) => ProxyResponse::Err(e.to_string()),
+                    Err(e) => ProxyResponse::Err(e.into()),
                 }
             }
+            FetchNote { note_id } => match self.db.fetch_note(note_id).await {
+                Ok(note) => ProxyResponse::Ok(ResultPayload::Note(note)),
+                Err(e) => ProxyResponse::Err(e.into()),
+            },
             FetchNotes { directory_id } => match self.db.fetch_notes(directory_id).await {
                 Ok(notes) => ProxyResponse::Ok(ResultPayload::Notes(notes)),
-                Err(e) => ProxyResponse::Err(e.to_string()),
+                Err(e) => ProxyResponse::Err(e.into()),
             },
             FetchNoteContent { note_id } => match self.db.fetch_note_content(note_id).await {
                 Ok(content) => ProxyResponse::Ok(ResultPayload::Text(content)),
-                Err(e) => ProxyResponse::Err(e.to_string()),
+                Err(e) => ProxyResponse::Err(e.into()),
             },
             AddNote { directory_id, name } => match self.db.add_note(directory_id, name).await {
                 Ok(note) => ProxyResponse::Ok(ResultPayload::Note(note)),
-                Err(e) => ProxyResponse::Err(e.to_string()),
+                Err(e) => ProxyResponse::Err(e.into()),
             },
             RemoveNote { note_id } => match self.db.remove_note(note_id).await {
                 Ok(()) => ProxyResponse::Ok(ResultPayload::Unit),
-                Err(e) => ProxyResponse::Err(e.to_string()),
+                Err(e) => ProxyResponse::Err(e.into()),
             },
-            RenameNote { note_id, name } => match self.db.rename_note(note_id, name).await {
-                Ok(()) => ProxyResponse::Ok(ResultPayload::Unit),
-                Err(e) => ProxyResponse::Err(e.to_string()),
+            RenameNote {
+                note_id,
+                name,
+                expected_updated_at,
+            } => match self
+                .db
+                .rename_note(note_id, name, expected_updated_at)
+                .await
+            {
+                Ok(note) => ProxyResponse::Ok(ResultPayload::Note(note)),
+                Err(e) => ProxyResponse::Err(e.into()),
+            },
+            UpdateNoteContent {
+                note_id,
+                content,
+                expected_updated_at,
+            } => match self
+                .db
+                .update_note_content(note_id, content, expected_updated_at)
+                .await
+            {
+                Ok(note) => ProxyResponse::Ok(ResultPayload::Note(note)),
+                Err(e) => ProxyResponse::Err(e.into()),
             },
-            UpdateNoteContent { note_id, content } => {
-                match self.db.update_note_content(note_id, content).await {
-                    Ok(()) => ProxyResponse::Ok(ResultPayload::Unit),
-                    Err(e) => ProxyResponse::Err(e.to_string()),
-                }
-            }
             MoveNote {
                 note_id,
                 directory_id,
-            } => match self.db.move_note(note_id, directory_id).await {
+                expected_updated_at,
+            } => match self
+                .db
+                .move_note(note_id, directory_id, expected_updated_at)
+                .await
+            {
+                Ok(note) => ProxyResponse::Ok(ResultPayload::Note(note)),
+                Err(e) => ProxyResponse::Err(e.into()),
+            },
+            ListNoteRevisions { note_id } => match self.db.list_note_revisions(note_id).await {
+                Ok(revisions) => ProxyResponse::Ok(ResultPayload::Revisions(revisions)),
+                Err(e) => ProxyResponse::Err(e.into()),
+            },
+            FetchNoteRevision {
+                note_id,
+                revision_id,
+            } => match self.db.fetch_note_revision(note_id, revision_id).await {
+                Ok(content) => ProxyResponse::Ok(ResultPayload::Text(content)),
+                Err(e) => ProxyResponse::Err(e.into()),
+            },
+            SearchNotes { query } => match self.db.search_notes(query).await {
+                Ok(results) => ProxyResponse::Ok(ResultPayload::SearchResults(results)),
+                Err(e) => ProxyResponse::Err(e.into()),
+            },
+            FetchBacklinks { note_id } => match self.db.fetch_backlinks(note_id).await {
+                Ok(notes) => ProxyResponse::Ok(ResultPayload::Notes(notes)),
+                Err(e) => ProxyResponse::Err(e.into()),
+            },
+            FetchTags => match self.db.fetch_tags().await {
+                Ok(tags) => ProxyResponse::Ok(ResultPayload::Tags(tags)),
+                Err(e) => ProxyResponse::Err(e.into()),
+            },
+            AddNoteTag { note_id, name } => match self.db.add_note_tag(note_id, name).await {
+                Ok(()) => ProxyResponse::Ok(ResultPayload::Unit),
+                Err(e) => ProxyResponse::Err(e.into()),
+            },
+            RemoveNoteTag { note_id, name } => match self.db.remove_note_tag(note_id, name).await {
+                Ok(()) => ProxyResponse::Ok(ResultPayload::Unit),
+                Err(e) => ProxyResponse::Err(e.into()),
+            },
+            FetchNotesByTag { name } => match self.db.fetch_notes_by_tag(name).await {
+                Ok(notes) => ProxyResponse::Ok(ResultPayload::Notes(notes)),
+                Err(e) => ProxyResponse::Err(e.into()),
+            },
+            FetchTrash => match self.db.fetch_trash().await {
+                Ok(trash) => ProxyResponse::Ok(ResultPayload::Trash(trash)),
+                Err(e) => ProxyResponse::Err(e.into()),
+            },
+            RestoreNote { note_id } => match self.db.restore_note(note_id).await {
+                Ok(note) => ProxyResponse::Ok(ResultPayload::Note(note)),
+                Err(e) => ProxyResponse::Err(e.into()),
+            },
+            RestoreDirectory { directory_id } => {
+                match self.db.restore_directory(directory_id).await {
+                    Ok(directory) => ProxyResponse::Ok(ResultPayload::Directory(directory)),
+                    Err(e) => ProxyResponse::Err(e.into()),
+                }
+            }
+            Purge => match self.db.purge().await {
                 Ok(()) => ProxyResponse::Ok(ResultPayload::Unit),
-                Err(e) => ProxyResponse::Err(e.to_string()),
+                Err(e) => ProxyResponse::Err(e.into()),
             },
             Log { category, message } => match self.db.log(category, message).await {
                 Ok(()) => ProxyResponse::Ok(ResultPayload::Unit),
-                Err(e) => ProxyResponse::Err(e.to_string()),
+                Err(e) => ProxyResponse::Err(e.into()),
             },
             Sync => match self.db.sync_job() {
                 Some(job) => match Self::run_sync_job(job).await {
                     Ok(()) => ProxyResponse::Ok(ResultPayload::Unit),
-                    Err(e) => ProxyResponse::Err(e.to_string()),
+                    Err(e) => ProxyResponse::Err(e.into()),
                 },
                 None => ProxyResponse::Ok(ResultPayload::Unit),
             },
+            Batch(_) => ProxyResponse::Err(ProxyError::new(
+                ErrorCode::InvalidState,
+                "batches cannot be nested",
+            )),
         }
     }
 }
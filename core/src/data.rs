@@ -1,18 +1,22 @@
 use {
-    crate::types::{DirectoryId, NoteId},
+    crate::types::{DirectoryId, NoteId, RevisionId, TagId},
     serde::{Deserialize, Serialize},
 };
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Note {
     pub id: NoteId,
     pub directory_id: DirectoryId,
     pub name: String,
     pub created_at: String,
     pub updated_at: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Directory {
     pub id: DirectoryId,
     pub parent_id: DirectoryId,
@@ -20,3 +24,42 @@ pub struct Directory {
     pub created_at: String,
     pub updated_at: String,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Tag {
+    pub id: TagId,
+    pub name: String,
+    pub created_at: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NoteRevision {
+    pub id: RevisionId,
+    pub note_id: NoteId,
+    pub created_at: String,
+}
+
+/// Top-level entries of the trash; the contents of a trashed directory are
+/// restored and purged together with it.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Trash {
+    pub directories: Vec<Directory>,
+    pub notes: Vec<Note>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NoteSearchResult {
+    pub note: Note,
+    pub snippets: Vec<SearchSnippet>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SearchSnippet {
+    pub line_number: usize,
+    pub text: String,
+}
@@ -5,6 +5,9 @@ pub enum Error {
     #[error("gluesql: {0}")]
     GlueSql(#[from] gluesql::prelude::Error),
 
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
     #[error("reqwest: {0}")]
     Reqwest(#[from] reqwest::Error),
 
@@ -20,9 +23,24 @@ pub enum Error {
     #[error("todo: {0}")]
     Todo(String),
 
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("conflict: {0}")]
+    Conflict(String),
+
     #[error("proxy: {0}")]
     Proxy(String),
 
+    #[error("unsupported: {0}")]
+    Unsupported(String),
+
+    #[error("schema version: {0}")]
+    SchemaVersion(String),
+
     #[error("backend: {0}")]
     BackendError(String),
 
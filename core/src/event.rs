@@ -1,7 +1,9 @@
 use {
     crate::{
+        backend::proxy::Change,
         data::{Directory, Note},
-        types::{DirectoryId, NoteId},
+        journal::JournalDay,
+        types::{DirectoryId, NoteId, RevisionId},
     },
     strum_macros::Display,
 };
@@ -25,6 +27,7 @@ pub enum EntryEvent {
     OpenMemory,
     OpenRedb(String),
     OpenFile(String),
+    OpenMarkdown(String),
     OpenGit {
         path: String,
         remote: String,
@@ -57,11 +60,28 @@ pub enum NotebookEvent {
     ShowNoteActionsDialog,
     CloseNoteActionsDialog,
 
+    ShowNoteRevisions,
+    LoadNoteRevision {
+        note_id: NoteId,
+        revision_id: RevisionId,
+    },
+    RestoreNoteRevision {
+        note_id: NoteId,
+        revision_id: RevisionId,
+    },
+
     ShowDirectoryActionsDialog,
     CloseDirectoryActionsDialog,
 
     AddNote(String),
+    /// Lists the notes that can be used as templates.
+    ShowTemplates,
+    AddNoteFromTemplate {
+        template: Note,
+        name: String,
+    },
     AddDirectory(String),
+    ImportFolder(String),
 
     MoveNote(DirectoryId),
     MoveDirectory(DirectoryId),
@@ -70,7 +90,42 @@ pub enum NotebookEvent {
     EditNote,
     ViewNote,
 
-    UpdateNoteContent { note_id: NoteId, content: String },
+    UpdateNoteContent {
+        note_id: NoteId,
+        content: String,
+    },
+    /// Resolves a save conflict by taking the stored note, dropping edits.
+    ReloadNote(NoteId),
+    /// Resolves a save conflict by writing `content` over the stored note.
+    OverwriteNoteContent {
+        note_id: NoteId,
+        content: String,
+    },
+    /// Resolves a save conflict by keeping `content` in a new note next to
+    /// the stored one, which is then reloaded.
+    SaveNoteCopy {
+        note_id: NoteId,
+        content: String,
+    },
+
+    SearchNotes(String),
+    JumpToNote(Note),
+    /// Opens the target of a `[[target]]` link, a note ID or name.
+    FollowLink(String),
+    /// Lists the notes linking to the edited or selected note.
+    ShowBacklinks,
+    /// Opens a daily journal note, adding it when missing.
+    OpenJournal(JournalDay),
+
+    RestoreNote(NoteId),
+    RestoreDirectory(DirectoryId),
+    EmptyTrash,
+
+    /// Changes made by other clients, from the backend's change feed.
+    ApplyChanges(Vec<Change>),
+    /// Compares the notebook with the storage, for changes made outside of
+    /// Glues on backends without a change feed.
+    CheckChanges,
 
     CloseEntryDialog,
 }
@@ -82,6 +137,7 @@ pub enum KeyEvent {
     C,
     D,
     E,
+    F,
     G,
     H,
     I,
@@ -116,6 +172,7 @@ pub enum KeyEvent {
     DollarSign,
     Caret,
     QuestionMark,
+    Slash,
     AngleBracketOpen,
     AngleBracketClose,
     Num(NumKey),
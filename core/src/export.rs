@@ -0,0 +1,122 @@
+use {
+    crate::{
+        Error, Result,
+        backend::CoreBackend,
+        data::{Directory, Note},
+    },
+    std::{collections::HashSet, fs, path::Path},
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExportOptions {
+    /// Prepend a YAML front matter block with the note id, timestamps and tags.
+    pub front_matter: bool,
+}
+
+/// Counts of the entries written by [`export_markdown`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExportSummary {
+    pub directories: usize,
+    pub notes: usize,
+}
+
+/// Writes the whole notebook of `backend` into `dest` as plain Markdown.
+///
+/// Every directory becomes a folder and every note a `<name>.md` file; the
+/// root directory maps to `dest` itself. `dest` is created when missing and
+/// must otherwise be empty, so an export never overwrites existing files.
+pub async fn export_markdown<B>(
+    backend: &mut B,
+    dest: &Path,
+    options: ExportOptions,
+) -> Result<ExportSummary>
+where
+    B: CoreBackend + ?Sized,
+{
+    if dest.exists() && fs::read_dir(dest)?.next().is_some() {
+        return Err(Error::InvalidState(format!(
+            "export destination is not empty: {}",
+            dest.display()
+        )));
+    }
+    fs::create_dir_all(dest)?;
+
+    let mut summary = ExportSummary::default();
+    let mut pending = vec![(backend.root_id(), dest.to_path_buf())];
+    while let Some((directory_id, path)) = pending.pop() {
+        let mut taken = HashSet::new();
+
+        for note in backend.fetch_notes(directory_id.clone()).await? {
+            let content = backend.fetch_note_content(note.id.clone()).await?;
+            let file_name = unique_name(&mut taken, &note.name, ".md");
+            let body = if options.front_matter {
+                format!("{}{content}", front_matter(&note))
+            } else {
+                content
+            };
+
+            fs::write(path.join(file_name), body)?;
+            summary.notes += 1;
+        }
+
+        for Directory { id, name, .. } in backend.fetch_directories(directory_id).await? {
+            let child = path.join(unique_name(&mut taken, &name, ""));
+            fs::create_dir(&child)?;
+            pending.push((id, child));
+            summary.directories += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+fn front_matter(note: &Note) -> String {
+    let mut front_matter = format!(
+        "---\nid: {}\ncreated_at: {}\nupdated_at: {}\n",
+        note.id, note.created_at, note.updated_at
+    );
+    if !note.tags.is_empty() {
+        let tags = note
+            .tags
+            .iter()
+            .map(|tag| format!("\"{}\"", tag.replace('\\', "\\\\").replace('"', "\\\"")))
+            .collect::<Vec<_>>()
+            .join(", ");
+        front_matter.push_str(&format!("tags: [{tags}]\n"));
+    }
+    front_matter.push_str("---\n\n");
+
+    front_matter
+}
+
+/// Turns an entry name into a file name that is valid on common file systems
+/// and not yet used in the same folder, appending ` (2)`, ` (3)`, … on clashes.
+pub(crate) fn unique_name(taken: &mut HashSet<String>, name: &str, extension: &str) -> String {
+    let stem = sanitize(name);
+    let mut file_name = format!("{stem}{extension}");
+    let mut n = 2;
+    while !taken.insert(file_name.to_lowercase()) {
+        file_name = format!("{stem} ({n}){extension}");
+        n += 1;
+    }
+
+    file_name
+}
+
+pub(crate) fn sanitize(name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect::<String>();
+    let name = name.trim().trim_end_matches('.');
+
+    if name.is_empty() {
+        "Untitled".to_owned()
+    } else {
+        name.to_owned()
+    }
+}
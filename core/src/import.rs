@@ -0,0 +1,142 @@
+mod obsidian;
+
+pub use obsidian::import_obsidian;
+
+use {
+    crate::{
+        Result,
+        backend::CoreBackend,
+        types::{DirectoryId, NoteId},
+    },
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+/// File extensions that are imported as notes.
+const NOTE_EXTENSIONS: [&str; 3] = ["md", "markdown", "txt"];
+
+/// Counts of the entries created by [`import_markdown`] and [`import_obsidian`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub directories: usize,
+    pub notes: usize,
+    /// Files that were left out because they are not UTF-8 Markdown or text.
+    pub skipped: usize,
+    /// Wiki links rewritten to point at note IDs.
+    pub links: usize,
+}
+
+/// Recreates the folder at `src` below `directory_id`.
+///
+/// Sub-folders become directories and `.md`, `.markdown` and `.txt` files
+/// become notes named after the file stem. Hidden entries such as `.git` are
+/// ignored.
+pub async fn import_markdown<B>(
+    backend: &mut B,
+    src: &Path,
+    directory_id: DirectoryId,
+) -> Result<ImportSummary>
+where
+    B: CoreBackend + ?Sized,
+{
+    let mut summary = ImportSummary::default();
+    let notes = create_tree(backend, src, directory_id, &NOTE_EXTENSIONS, &mut summary).await?;
+    for ImportedNote { id, content, .. } in notes {
+        write_content(backend, id, content).await?;
+    }
+
+    Ok(summary)
+}
+
+/// A note created by [`create_tree`] whose content is not written yet.
+struct ImportedNote {
+    id: NoteId,
+    /// Path relative to the imported folder, without the file extension.
+    path: PathBuf,
+    content: String,
+}
+
+/// Creates the directories and empty notes for every supported file below
+/// `src`, returning the notes so callers can transform their content first.
+async fn create_tree<B>(
+    backend: &mut B,
+    src: &Path,
+    directory_id: DirectoryId,
+    extensions: &[&str],
+    summary: &mut ImportSummary,
+) -> Result<Vec<ImportedNote>>
+where
+    B: CoreBackend + ?Sized,
+{
+    let mut notes = Vec::new();
+    let mut pending = vec![(src.to_path_buf(), directory_id)];
+    while let Some((path, directory_id)) = pending.pop() {
+        let mut entries = fs::read_dir(&path)?.collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries {
+            let path = entry.path();
+            let file_name = entry.file_name();
+            let Some(file_name) = file_name.to_str() else {
+                summary.skipped += 1;
+                continue;
+            };
+            if file_name.starts_with('.') {
+                continue;
+            }
+
+            if entry.file_type()?.is_dir() {
+                let directory = backend
+                    .add_directory(directory_id.clone(), file_name.to_owned())
+                    .await?;
+                pending.push((path, directory.id));
+                summary.directories += 1;
+                continue;
+            }
+
+            let (Some(stem), Some(extension)) = (
+                path.file_stem().and_then(|stem| stem.to_str()),
+                path.extension().and_then(|extension| extension.to_str()),
+            ) else {
+                summary.skipped += 1;
+                continue;
+            };
+            if !extensions.contains(&extension.to_lowercase().as_str()) {
+                summary.skipped += 1;
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path) else {
+                summary.skipped += 1;
+                continue;
+            };
+
+            let note = backend
+                .add_note(directory_id.clone(), stem.to_owned())
+                .await?;
+            let path = path.strip_prefix(src).unwrap_or(&path).with_extension("");
+            notes.push(ImportedNote {
+                id: note.id,
+                path,
+                content,
+            });
+            summary.notes += 1;
+        }
+    }
+
+    Ok(notes)
+}
+
+async fn write_content<B>(backend: &mut B, note_id: NoteId, content: String) -> Result<()>
+where
+    B: CoreBackend + ?Sized,
+{
+    if content.is_empty() {
+        return Ok(());
+    }
+
+    backend.update_note_content(note_id, content, None).await?;
+
+    Ok(())
+}
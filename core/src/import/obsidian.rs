@@ -0,0 +1,164 @@
+use {
+    super::{ImportSummary, ImportedNote, create_tree, write_content},
+    crate::{
+        Result,
+        backend::CoreBackend,
+        types::{DirectoryId, NoteId},
+    },
+    std::{collections::HashMap, path::Path},
+};
+
+/// Imports an Obsidian vault below `directory_id`.
+///
+/// Vault folders become directories and Markdown files become notes, while
+/// hidden folders such as `.obsidian/` and `.trash/` are left out. Wiki links
+/// that resolve to an imported note are rewritten from `[[Target|label]]` to
+/// `[[<note id>|label]]`, so they keep working after the target is renamed or
+/// moved. Links to attachments or missing notes are kept as they are.
+pub async fn import_obsidian<B>(
+    backend: &mut B,
+    vault: &Path,
+    directory_id: DirectoryId,
+) -> Result<ImportSummary>
+where
+    B: CoreBackend + ?Sized,
+{
+    let mut summary = ImportSummary::default();
+    let notes = create_tree(backend, vault, directory_id, &["md"], &mut summary).await?;
+
+    let index = LinkIndex::new(&notes);
+    for ImportedNote { id, content, .. } in &notes {
+        let (content, links) = rewrite_links(content, &index);
+        summary.links += links;
+        write_content(backend, id.clone(), content).await?;
+    }
+
+    Ok(summary)
+}
+
+/// Resolves wiki link targets the way Obsidian does: by vault-relative path
+/// first, then by note name, preferring the note closest to the vault root.
+struct LinkIndex {
+    by_path: HashMap<String, NoteId>,
+    by_name: HashMap<String, (usize, NoteId)>,
+}
+
+impl LinkIndex {
+    fn new(notes: &[ImportedNote]) -> Self {
+        let mut by_path = HashMap::new();
+        let mut by_name = HashMap::<String, (usize, NoteId)>::new();
+
+        for note in notes {
+            let components = note
+                .path
+                .iter()
+                .filter_map(|component| component.to_str())
+                .collect::<Vec<_>>();
+            let Some(name) = components.last() else {
+                continue;
+            };
+
+            by_path.insert(components.join("/").to_lowercase(), note.id.clone());
+
+            let depth = components.len();
+            let name = name.to_lowercase();
+            match by_name.get(&name) {
+                Some((existing, _)) if *existing <= depth => {}
+                _ => {
+                    by_name.insert(name, (depth, note.id.clone()));
+                }
+            }
+        }
+
+        Self { by_path, by_name }
+    }
+
+    fn resolve(&self, target: &str) -> Option<&NoteId> {
+        let target = target
+            .trim()
+            .trim_start_matches("./")
+            .trim_start_matches('/');
+        let target = target
+            .strip_suffix(".md")
+            .unwrap_or(target)
+            .replace('\\', "/")
+            .to_lowercase();
+
+        self.by_path.get(&target).or_else(|| {
+            let name = target.rsplit('/').next().unwrap_or(&target);
+            self.by_name.get(name).map(|(_, id)| id)
+        })
+    }
+}
+
+/// Rewrites every resolvable wiki link outside fenced code blocks and
+/// returns the new content together with the number of rewritten links.
+fn rewrite_links(content: &str, index: &LinkIndex) -> (String, usize) {
+    let mut output = String::with_capacity(content.len());
+    let mut rewritten = 0;
+    let mut in_code_block = false;
+
+    for line in content.split_inclusive('\n') {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        }
+
+        if in_code_block {
+            output.push_str(line);
+            continue;
+        }
+
+        let mut rest = line;
+        while let Some(start) = rest.find("[[") {
+            let Some(len) = rest[start + 2..].find("]]") else {
+                break;
+            };
+            let inner = &rest[start + 2..start + 2 + len];
+
+            output.push_str(&rest[..start]);
+            match rewrite_link(inner, index) {
+                Some(link) => {
+                    output.push_str(&link);
+                    rewritten += 1;
+                }
+                None => {
+                    output.push_str(&rest[start..start + 4 + len]);
+                }
+            }
+            rest = &rest[start + 4 + len..];
+        }
+        output.push_str(rest);
+    }
+
+    (output, rewritten)
+}
+
+/// Turns the inside of `[[target#heading|label]]` into a link to the note ID,
+/// keeping the heading and using the original target as the label when none
+/// is given.
+fn rewrite_link(inner: &str, index: &LinkIndex) -> Option<String> {
+    let (target, label) = match inner.split_once('|') {
+        Some((target, label)) => (target, Some(label)),
+        None => (inner, None),
+    };
+    // links inside Markdown tables escape the label separator as `\|`
+    let (target, separator) = match target.strip_suffix('\\') {
+        Some(target) => (target, "\\|"),
+        None => (target, "|"),
+    };
+    let (name, heading) = match target.split_once('#') {
+        Some((name, heading)) => (name, Some(heading)),
+        None => (target, None),
+    };
+    if name.trim().is_empty() {
+        return None;
+    }
+
+    let id = index.resolve(name)?;
+    let heading = heading
+        .map(|heading| format!("#{heading}"))
+        .unwrap_or_default();
+    let label = label.unwrap_or(target);
+
+    Some(format!("[[{id}{heading}{separator}{label}]]"))
+}
@@ -0,0 +1,100 @@
+//! Daily journal notes.
+//!
+//! Each day gets a note named `YYYY-MM-DD`, kept at
+//! `Journal/YYYY/MM/YYYY-MM-DD` below the root directory. The folders and
+//! the note are created the first time the day is opened.
+
+use {
+    crate::{Result, backend::CoreBackend, data::Note, types::DirectoryId},
+    chrono::{Local, NaiveDate},
+};
+
+/// Name of the top-level directory holding the journal.
+pub const JOURNAL_DIRECTORY: &str = "Journal";
+
+/// Which day's note to open, relative to the journal note at hand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JournalDay {
+    Today,
+    /// The day before the open journal note, or before today.
+    Previous,
+    /// The day after the open journal note, or after today.
+    Next,
+}
+
+impl JournalDay {
+    /// Resolves the day from `current`, the date of the journal note being
+    /// viewed, if any.
+    pub fn date(self, current: Option<NaiveDate>) -> NaiveDate {
+        let base = current.unwrap_or_else(today);
+
+        match self {
+            Self::Today => today(),
+            Self::Previous => base.pred_opt().unwrap_or(base),
+            Self::Next => base.succ_opt().unwrap_or(base),
+        }
+    }
+}
+
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Name of the journal note of `date`.
+pub fn note_name(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Path of the journal note of `date` below the root, e.g.
+/// `Journal/2024/05/2024-05-01`.
+pub fn note_path(date: NaiveDate) -> String {
+    format!(
+        "{JOURNAL_DIRECTORY}/{}/{}",
+        date.format("%Y/%m"),
+        note_name(date)
+    )
+}
+
+/// The date of a journal note, read from its name.
+pub fn date_of(note: &Note) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&note.name, "%Y-%m-%d").ok()
+}
+
+/// Finds the journal note of `date`, adding it and its folders when missing.
+pub async fn find_or_create<B: CoreBackend + ?Sized>(db: &mut B, date: NaiveDate) -> Result<Note> {
+    let mut directory_id = db.root_id();
+    for name in [
+        JOURNAL_DIRECTORY.to_owned(),
+        date.format("%Y").to_string(),
+        date.format("%m").to_string(),
+    ] {
+        directory_id = find_or_add_directory(db, directory_id, name).await?;
+    }
+
+    let name = note_name(date);
+    let note = db
+        .fetch_notes(directory_id.clone())
+        .await?
+        .into_iter()
+        .find(|note| note.name == name);
+    match note {
+        Some(note) => Ok(note),
+        None => db.add_note(directory_id, name).await,
+    }
+}
+
+async fn find_or_add_directory<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    parent_id: DirectoryId,
+    name: String,
+) -> Result<DirectoryId> {
+    let directory = db
+        .fetch_directories(parent_id.clone())
+        .await?
+        .into_iter()
+        .find(|directory| directory.name == name);
+    match directory {
+        Some(directory) => Ok(directory.id),
+        None => Ok(db.add_directory(parent_id, name).await?.id),
+    }
+}
@@ -5,7 +5,12 @@ mod glues;
 mod schema;
 
 pub mod data;
+pub mod export;
+pub mod import;
+pub mod journal;
+pub mod link;
 pub mod state;
+pub mod template;
 pub mod transition;
 pub mod types;
 
@@ -13,6 +18,7 @@ pub use backend::CoreBackend;
 pub use error::Error;
 pub use event::{EntryEvent, Event, KeyEvent, NotebookEvent, NumKey};
 pub use glues::Glues;
+pub use schema::SCHEMA_VERSION;
 pub use transition::{EntryTransition, NotebookTransition, Transition};
 
 type Result<T> = std::result::Result<T, Error>;
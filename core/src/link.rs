@@ -0,0 +1,98 @@
+//! Wiki links between notes.
+//!
+//! A link is written as `[[target]]` or `[[target|label]]`, where the target
+//! is a note ID or a note name and may carry a `#heading`. Links point at IDs
+//! when they can, so they survive renames and moves; names are resolved when
+//! the link is followed.
+
+/// A wiki link within a single line of note content.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Link {
+    /// The note ID or name the link points at, without heading or label.
+    pub target: String,
+    /// Char offsets of the link in its line, brackets included.
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Link {
+    /// The key the link is indexed by; targets are matched ignoring case.
+    pub fn key(&self) -> String {
+        self.target.to_lowercase()
+    }
+}
+
+/// Links of a single line, in order.
+pub fn parse_line(line: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut offset = 0;
+    let mut rest = line;
+    while let Some(start) = rest.find("[[") {
+        let Some(len) = rest[start + 2..].find("]]") else {
+            break;
+        };
+        let inner = &rest[start + 2..start + 2 + len];
+        let end = start + 4 + len;
+
+        if let Some(target) = target(inner) {
+            let start_char = offset + rest[..start].chars().count();
+            links.push(Link {
+                target,
+                start: start_char,
+                end: start_char + rest[start..end].chars().count(),
+            });
+        }
+        offset += rest[..end].chars().count();
+        rest = &rest[end..];
+    }
+
+    links
+}
+
+/// Links of the whole content, skipping fenced code blocks.
+pub fn parse(content: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut in_code_block = false;
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if !in_code_block {
+            links.extend(parse_line(line));
+        }
+    }
+
+    links
+}
+
+/// Index keys of the distinct link targets in `content`.
+pub fn keys(content: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    for link in parse(content) {
+        let key = link.key();
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    keys
+}
+
+/// The link under the char `column` of `line`, if any.
+pub fn link_at(line: &str, column: usize) -> Option<Link> {
+    parse_line(line)
+        .into_iter()
+        .find(|link| (link.start..link.end).contains(&column))
+}
+
+/// Reads the target out of the inside of `[[target#heading|label]]`.
+fn target(inner: &str) -> Option<String> {
+    let target = inner.split_once('|').map_or(inner, |(target, _)| target);
+    // links inside Markdown tables escape the label separator as `\|`
+    let target = target.strip_suffix('\\').unwrap_or(target);
+    let target = target.split_once('#').map_or(target, |(name, _)| name);
+    let target = target.trim();
+
+    (!target.is_empty()).then(|| target.to_owned())
+}
@@ -1,3 +1,7 @@
+mod migration;
+
+pub use migration::SCHEMA_VERSION;
+
 use {
     crate::{
         Result,
@@ -37,46 +41,7 @@ pub async fn setup(storage: &mut Storage) -> Result<DirectoryId> {
         .execute(storage)
         .await?;
 
-    table("Directory")
-        .create_table_if_not_exists()
-        .add_column("id UUID PRIMARY KEY DEFAULT GENERATE_UUID()")
-        .add_column("parent_id UUID NULL")
-        .add_column("name TEXT NOT NULL")
-        .add_column("created_at TIMESTAMP NOT NULL DEFAULT NOW()")
-        .add_column("updated_at TIMESTAMP NOT NULL DEFAULT NOW()")
-        .execute(storage)
-        .await?;
-
-    table("Note")
-        .create_table_if_not_exists()
-        .add_column("id UUID PRIMARY KEY")
-        .add_column("name TEXT NOT NULL")
-        .add_column("directory_id UUID NOT NULL")
-        .add_column("created_at TIMESTAMP NOT NULL DEFAULT NOW()")
-        .add_column("updated_at TIMESTAMP NOT NULL DEFAULT NOW()")
-        .add_column("content TEXT NOT NULL DEFAULT ''")
-        .execute(storage)
-        .await?;
-
-    let schema_version_not_exists = table("Meta")
-        .select()
-        .filter(col("key").eq(text("schema_version")))
-        .project("key")
-        .execute(storage)
-        .await?
-        .select()
-        .expect("Meta query must yield a result set")
-        .count()
-        == 0;
-
-    if schema_version_not_exists {
-        table("Meta")
-            .insert()
-            .columns(vec!["key", "value"])
-            .values(vec![vec![text("schema_version"), text("1")]])
-            .execute(storage)
-            .await?;
-    }
+    migration::run(storage).await?;
 
     let root_not_exists = table("Directory")
         .select()
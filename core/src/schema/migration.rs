@@ -0,0 +1,198 @@
+use {
+    crate::{
+        Error, Result,
+        backend::local::{Execute, Storage},
+        link,
+    },
+    gluesql::{
+        FromGlueRow,
+        core::{
+            ast_builder::{col, function::now, table, text, uuid},
+            row_conversion::SelectExt,
+        },
+    },
+};
+
+/// Schema version written by this build of glues.
+///
+/// Bump it together with a new arm in [`upgrade`] whenever the layout of
+/// the stored tables changes.
+pub const SCHEMA_VERSION: u32 = 5;
+
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+#[derive(FromGlueRow)]
+struct MetaRow {
+    value: String,
+}
+
+#[derive(FromGlueRow)]
+struct NoteContentRow {
+    id: String,
+    content: String,
+}
+
+/// Brings the storage up to [`SCHEMA_VERSION`], applying each pending
+/// upgrade step in order and recording progress in `Meta` after every step.
+pub async fn run(storage: &mut Storage) -> Result<()> {
+    let stored = stored_version(storage).await?;
+    let mut version = stored.unwrap_or(0);
+
+    if version > SCHEMA_VERSION {
+        return Err(Error::SchemaVersion(format!(
+            "database uses schema version {version}, but this build only supports up to {SCHEMA_VERSION}"
+        )));
+    }
+
+    while version < SCHEMA_VERSION {
+        version += 1;
+        upgrade(storage, version).await?;
+        save_version(storage, version, version == 1 && stored.is_none()).await?;
+    }
+
+    Ok(())
+}
+
+async fn upgrade(storage: &mut Storage, version: u32) -> Result<()> {
+    match version {
+        1 => {
+            table("Directory")
+                .create_table_if_not_exists()
+                .add_column("id UUID PRIMARY KEY DEFAULT GENERATE_UUID()")
+                .add_column("parent_id UUID NULL")
+                .add_column("name TEXT NOT NULL")
+                .add_column("created_at TIMESTAMP NOT NULL DEFAULT NOW()")
+                .add_column("updated_at TIMESTAMP NOT NULL DEFAULT NOW()")
+                .execute(storage)
+                .await?;
+
+            table("Note")
+                .create_table_if_not_exists()
+                .add_column("id UUID PRIMARY KEY")
+                .add_column("name TEXT NOT NULL")
+                .add_column("directory_id UUID NOT NULL")
+                .add_column("created_at TIMESTAMP NOT NULL DEFAULT NOW()")
+                .add_column("updated_at TIMESTAMP NOT NULL DEFAULT NOW()")
+                .add_column("content TEXT NOT NULL DEFAULT ''")
+                .execute(storage)
+                .await?;
+        }
+        2 => {
+            table("Tag")
+                .create_table_if_not_exists()
+                .add_column("id UUID PRIMARY KEY")
+                .add_column("name TEXT NOT NULL")
+                .add_column("created_at TIMESTAMP NOT NULL DEFAULT NOW()")
+                .execute(storage)
+                .await?;
+
+            table("NoteTag")
+                .create_table_if_not_exists()
+                .add_column("note_id UUID NOT NULL")
+                .add_column("tag_id UUID NOT NULL")
+                .add_column("created_at TIMESTAMP NOT NULL DEFAULT NOW()")
+                .execute(storage)
+                .await?;
+        }
+        3 => {
+            for name in ["Directory", "Note"] {
+                table(name)
+                    .alter_table()
+                    .add_column("deleted_at TIMESTAMP NULL")
+                    .execute(storage)
+                    .await?;
+            }
+        }
+        4 => {
+            table("NoteRevision")
+                .create_table_if_not_exists()
+                .add_column("id UUID PRIMARY KEY")
+                .add_column("note_id UUID NOT NULL")
+                .add_column("content TEXT NOT NULL")
+                .add_column("created_at TIMESTAMP NOT NULL DEFAULT NOW()")
+                .execute(storage)
+                .await?;
+        }
+        5 => {
+            table("NoteLink")
+                .create_table_if_not_exists()
+                .add_column("note_id UUID NOT NULL")
+                .add_column("target TEXT NOT NULL")
+                .execute(storage)
+                .await?;
+
+            // index the links already written in existing notes
+            let notes = table("Note")
+                .select()
+                .project(vec!["id", "content"])
+                .execute(storage)
+                .await?
+                .rows_as::<NoteContentRow>()?;
+            let rows = notes
+                .into_iter()
+                .flat_map(|note| {
+                    link::keys(&note.content)
+                        .into_iter()
+                        .map(move |key| vec![uuid(note.id.clone()), text(key)])
+                })
+                .collect::<Vec<_>>();
+            if !rows.is_empty() {
+                table("NoteLink")
+                    .insert()
+                    .columns(vec!["note_id", "target"])
+                    .values(rows)
+                    .execute(storage)
+                    .await?;
+            }
+        }
+        _ => {
+            return Err(Error::SchemaVersion(format!(
+                "no upgrade step for schema version {version}"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+async fn stored_version(storage: &mut Storage) -> Result<Option<u32>> {
+    let row = table("Meta")
+        .select()
+        .filter(col("key").eq(text(SCHEMA_VERSION_KEY)))
+        .project("value")
+        .execute(storage)
+        .await?
+        .rows_as::<MetaRow>()?
+        .into_iter()
+        .next();
+
+    row.map(|row| {
+        row.value.parse::<u32>().map_err(|_| {
+            Error::SchemaVersion(format!("invalid stored schema version: {}", row.value))
+        })
+    })
+    .transpose()
+}
+
+async fn save_version(storage: &mut Storage, version: u32, insert: bool) -> Result<()> {
+    let value = text(version.to_string());
+
+    if insert {
+        table("Meta")
+            .insert()
+            .columns(vec!["key", "value"])
+            .values(vec![vec![text(SCHEMA_VERSION_KEY), value]])
+            .execute(storage)
+            .await?;
+    } else {
+        table("Meta")
+            .update()
+            .filter(col("key").eq(text(SCHEMA_VERSION_KEY)))
+            .set("value", value)
+            .set("updated_at", now())
+            .execute(storage)
+            .await?;
+    }
+
+    Ok(())
+}
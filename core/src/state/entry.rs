@@ -1,6 +1,6 @@
 use crate::{
     EntryEvent, EntryTransition, Error, Event, Glues, Result,
-    backend::{local::Db, proxy::ProxyClient},
+    backend::{local::Db, markdown::MarkdownBackend, proxy::ProxyClient},
     state::notebook::NotebookState,
     types::{KeymapGroup, KeymapItem},
 };
@@ -17,7 +17,8 @@ impl EntryState {
                 let mut db = Db::memory().await?;
                 let root_id = db.root_id.clone();
                 let note_id = db.add_note(root_id, "Sample Note".to_owned()).await?.id;
-                db.update_note_content(note_id, "Hi :D".to_owned()).await?;
+                db.update_note_content(note_id, "Hi :D".to_owned(), None)
+                    .await?;
 
                 glues.db = Some(Box::new(db));
                 glues.state = NotebookState::new(glues).await?.into();
@@ -30,6 +31,13 @@ impl EntryState {
 
                 Ok(EntryTransition::OpenNotebook)
             }
+            Entry(OpenMarkdown(path)) => {
+                let db = MarkdownBackend::open(&path).await?;
+                glues.db = Some(Box::new(db));
+                glues.state = NotebookState::new(glues).await?.into();
+
+                Ok(EntryTransition::OpenNotebook)
+            }
             Entry(OpenRedb(path)) => {
                 let db = Db::redb(&path).await?;
                 glues.db = Some(Box::new(db));
@@ -9,7 +9,7 @@ use {
         state::GetInner,
         types::{DirectoryId, Id, KeymapGroup},
     },
-    consume::{directory, note, tabs},
+    consume::{change, conflict, directory, journal, link, note, revision, tabs, template, trash},
 };
 
 pub use inner_state::{
@@ -287,6 +287,41 @@ impl NotebookState {
         }
     }
 
+    /// Replaces the copies of `note` in the tree, the tabs and the selection,
+    /// e.g. after a save gave it a new `updated_at`.
+    pub fn refresh_note(&mut self, note: &Note) {
+        self.root.rename_note(note);
+
+        for tab in self.tabs.iter_mut().filter(|tab| tab.note.id == note.id) {
+            tab.note = note.clone();
+        }
+
+        if let SelectedItem::Note(selected) = &mut self.selected
+            && selected.id == note.id
+        {
+            *selected = note.clone();
+        }
+    }
+
+    /// [`Self::refresh_note`] for a rename or move of the note as read at
+    /// `read_at`; tabs holding an older read keep their `updated_at`, so
+    /// saving them still notices what changed in between.
+    pub fn refresh_moved_note(&mut self, read_at: &str, note: &Note) {
+        let stale = self
+            .tabs
+            .iter()
+            .filter(|tab| tab.note.id == note.id && tab.note.updated_at != read_at)
+            .map(|tab| tab.note.updated_at.clone())
+            .next();
+
+        self.refresh_note(note);
+        if let Some(updated_at) = stale {
+            for tab in self.tabs.iter_mut().filter(|tab| tab.note.id == note.id) {
+                tab.note.updated_at.clone_from(&updated_at);
+            }
+        }
+    }
+
     pub fn get_editing(&self) -> Result<&Note> {
         let i = self
             .tab_index
@@ -305,5 +340,9 @@ pub async fn consume(glues: &mut Glues, event: Event) -> Result<NotebookTransiti
         .ok_or(Error::InvalidState("[consume] empty db".to_owned()))?;
     let state: &mut NotebookState = glues.state.get_inner_mut()?;
 
-    inner_state::consume(db, state, event).await
+    match inner_state::consume(db, state, event).await {
+        // e.g. an older proxy server without search; tell the user instead of failing
+        Err(Error::Unsupported(message)) => Ok(NotebookTransition::Alert(message)),
+        transition => transition,
+    }
 }
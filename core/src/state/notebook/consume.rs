@@ -1,4 +1,11 @@
 mod breadcrumb;
+pub mod change;
+pub mod conflict;
 pub mod directory;
+pub mod journal;
+pub mod link;
 pub mod note;
+pub mod revision;
 pub mod tabs;
+pub mod template;
+pub mod trash;
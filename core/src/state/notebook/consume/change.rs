@@ -0,0 +1,198 @@
+use {
+    super::{breadcrumb, directory},
+    crate::{
+        Error, NotebookTransition, Result,
+        backend::{CoreBackend, proxy::Change},
+        data::Note,
+        state::notebook::{DirectoryItem, InnerState, NoteTreeState, NotebookState, SelectedItem},
+        types::{DirectoryId, NoteId},
+    },
+};
+
+/// Catches the notebook up with changes made by other clients: opened
+/// directories are refetched, open tabs pick up renames and moves, and open
+/// notes changed elsewhere are handed to the frontend to reload.
+pub async fn apply<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    changes: Vec<Change>,
+) -> Result<NotebookTransition> {
+    if changes.iter().any(Change::affects_tree) {
+        reload_tree(db, state).await?;
+    }
+
+    let mut notes: Vec<NoteId> = Vec::new();
+    for change in &changes {
+        let note_id = match change {
+            Change::NoteRenamed { note_id, .. }
+            | Change::NoteMoved { note_id, .. }
+            | Change::NoteContentUpdated { note_id }
+            | Change::NoteTagsChanged { note_id } => note_id,
+            _ => continue,
+        };
+        let listed = find_note(&state.root, note_id).is_some();
+        let opened = state.tabs.iter().any(|tab| &tab.note.id == note_id);
+        if !listed && !opened {
+            continue;
+        }
+
+        let note = db.fetch_note(note_id.clone()).await?;
+        state.root.rename_note(&note);
+        if let SelectedItem::Note(selected) = &mut state.selected
+            && selected.id == note.id
+        {
+            *selected = note.clone();
+        }
+
+        // tabs keep `updated_at` as read, so saving local edits still
+        // notices the change; reloading the note catches them up
+        for tab in state.tabs.iter_mut().filter(|tab| tab.note.id == note.id) {
+            tab.note.name.clone_from(&note.name);
+            tab.note.directory_id.clone_from(&note.directory_id);
+            tab.note.tags.clone_from(&note.tags);
+        }
+
+        if opened && !matches!(change, Change::NoteTagsChanged { .. }) && !notes.contains(note_id) {
+            notes.push(note_id.clone());
+        }
+    }
+
+    breadcrumb::update_breadcrumbs(db, state).await?;
+
+    Ok(NotebookTransition::RemoteChanges { notes })
+}
+
+/// Looks for changes made outside of Glues, such as a Git pull or another
+/// process writing the same storage, which no change feed reports. The tree
+/// is refetched and open notes whose `updated_at` moved on since they were
+/// read are handed to the frontend, as [`apply`] does.
+pub async fn detect<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+) -> Result<NotebookTransition> {
+    let root = state.root.clone();
+    reload_tree(db, state).await?;
+    let tree_changed = state.root != root;
+
+    let mut notes: Vec<NoteId> = Vec::new();
+    let opened = state
+        .tabs
+        .iter()
+        .map(|tab| tab.note.clone())
+        .collect::<Vec<_>>();
+    for read in opened {
+        if notes.contains(&read.id) {
+            continue;
+        }
+
+        let note = match db.fetch_note(read.id.clone()).await {
+            Ok(note) => note,
+            // removed elsewhere; the tab stays until it is closed
+            Err(Error::NotFound(_)) => continue,
+            Err(err) => return Err(err),
+        };
+        if note.updated_at == read.updated_at {
+            continue;
+        }
+
+        for tab in state.tabs.iter_mut().filter(|tab| tab.note.id == note.id) {
+            tab.note.name.clone_from(&note.name);
+            tab.note.directory_id.clone_from(&note.directory_id);
+            tab.note.tags.clone_from(&note.tags);
+        }
+        notes.push(note.id);
+    }
+
+    if !tree_changed && notes.is_empty() {
+        return Ok(NotebookTransition::None);
+    }
+    breadcrumb::update_breadcrumbs(db, state).await?;
+
+    Ok(NotebookTransition::RemoteChanges { notes })
+}
+
+/// Answers a rename or move refused because the note changed elsewhere:
+/// the tree is reloaded so that a retry starts from the stored note.
+pub async fn reject_stale<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    message: String,
+) -> Result<NotebookTransition> {
+    state.inner_state = match state.selected {
+        SelectedItem::Directory(_) => InnerState::NoteTree(NoteTreeState::DirectorySelected),
+        _ => InnerState::NoteTree(NoteTreeState::NoteSelected),
+    };
+    reload_tree(db, state).await?;
+
+    Ok(NotebookTransition::Alert(format!(
+        "{message}; the notes were reloaded, please try again"
+    )))
+}
+
+/// Refetches every opened directory, keeping the same directories open, and
+/// moves the selection to the root when the selected entry is gone.
+async fn reload_tree<B: CoreBackend + ?Sized>(db: &mut B, state: &mut NotebookState) -> Result<()> {
+    let mut opened = Vec::new();
+    collect_opened(&state.root, &mut opened);
+
+    state.root.directory = db.fetch_directory(state.root.directory.id.clone()).await?;
+    for directory_id in opened {
+        // parents come first, so a directory that was removed or moved out
+        // of sight is no longer found and stays closed
+        if state.root.find(&directory_id).is_some() {
+            directory::open(db, state, directory_id).await?;
+        }
+    }
+
+    let selected = match &state.selected {
+        SelectedItem::Note(note) => find_note(&state.root, &note.id)
+            .cloned()
+            .map(SelectedItem::Note),
+        SelectedItem::Directory(directory) => state
+            .root
+            .find(&directory.id)
+            .map(|item| SelectedItem::Directory(item.directory.clone())),
+        SelectedItem::None => Some(SelectedItem::None),
+    };
+    match selected {
+        Some(selected) => state.selected = selected,
+        None if matches!(
+            state.inner_state,
+            InnerState::NoteTree(NoteTreeState::NoteSelected | NoteTreeState::DirectorySelected)
+        ) =>
+        {
+            state.selected = SelectedItem::Directory(state.root.directory.clone());
+            state.inner_state = InnerState::NoteTree(NoteTreeState::DirectorySelected);
+        }
+        // keep editing or the open dialog; the entry is still referenced
+        None => {}
+    }
+
+    Ok(())
+}
+
+fn collect_opened(item: &DirectoryItem, opened: &mut Vec<DirectoryId>) {
+    let Some(children) = item.children.as_ref() else {
+        return;
+    };
+
+    opened.push(item.directory.id.clone());
+    for child in &children.directories {
+        collect_opened(child, opened);
+    }
+}
+
+fn find_note<'a>(item: &'a DirectoryItem, note_id: &NoteId) -> Option<&'a Note> {
+    let children = item.children.as_ref()?;
+
+    children
+        .notes
+        .iter()
+        .find(|note| &note.id == note_id)
+        .or_else(|| {
+            children
+                .directories
+                .iter()
+                .find_map(|child| find_note(child, note_id))
+        })
+}
@@ -0,0 +1,73 @@
+use crate::{
+    NotebookTransition, Result,
+    backend::CoreBackend,
+    state::notebook::{DirectoryItem, NotebookState},
+    types::NoteId,
+};
+
+/// Drops the local edits and takes the stored note.
+pub async fn reload<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    note_id: NoteId,
+) -> Result<NotebookTransition> {
+    let note = db.fetch_note(note_id.clone()).await?;
+    let content = db.fetch_note_content(note_id.clone()).await?;
+    state.refresh_note(&note);
+
+    Ok(NotebookTransition::NoteReloaded { note_id, content })
+}
+
+/// Writes the local edits over whatever was stored in the meantime.
+pub async fn overwrite<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    note_id: NoteId,
+    content: String,
+) -> Result<NotebookTransition> {
+    let note = db
+        .update_note_content(note_id.clone(), content.trim_end().to_owned(), None)
+        .await?;
+    db.log(
+        "note::overwrite".to_owned(),
+        format!("id: {note_id}\nat: {}", note.updated_at),
+    )
+    .await?;
+    state.refresh_note(&note);
+
+    Ok(NotebookTransition::UpdateNoteContent(note_id))
+}
+
+/// Keeps the local edits in a new note next to the stored one, which the
+/// editor then switches to.
+pub async fn save_copy<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    note_id: NoteId,
+    content: String,
+) -> Result<NotebookTransition> {
+    let note = db.fetch_note(note_id.clone()).await?;
+    let copy = db
+        .add_note(note.directory_id.clone(), format!("{} (copy)", note.name))
+        .await?;
+    let copy = db
+        .update_note_content(copy.id, content.trim_end().to_owned(), None)
+        .await?;
+
+    if let Some(DirectoryItem {
+        children: Some(children),
+        ..
+    }) = state.root.find_mut(&note.directory_id)
+    {
+        children.notes = db.fetch_notes(note.directory_id.clone()).await?;
+    }
+
+    let content = db.fetch_note_content(note_id.clone()).await?;
+    state.refresh_note(&note);
+
+    Ok(NotebookTransition::NoteCopySaved {
+        copy,
+        note_id,
+        content,
+    })
+}
@@ -4,6 +4,7 @@ use {
         Error, NotebookTransition, Result,
         backend::CoreBackend,
         data::Directory,
+        import::import_markdown,
         state::notebook::{
             DirectoryItem, DirectoryItemChildren, InnerState, NoteTreeState, NotebookState,
             SelectedItem,
@@ -11,6 +12,7 @@ use {
         transition::{MoveModeTransition, NoteTreeTransition},
         types::DirectoryId,
     },
+    std::path::Path,
 };
 
 pub async fn open<B: CoreBackend + ?Sized>(
@@ -239,6 +241,40 @@ pub async fn add<B: CoreBackend + ?Sized>(
     ))
 }
 
+pub async fn import<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    directory: Directory,
+    path: String,
+) -> Result<NotebookTransition> {
+    if !Path::new(&path).is_dir() {
+        return Ok(NotebookTransition::Alert(format!(
+            "Cannot import '{path}': not a folder"
+        )));
+    }
+
+    let summary = import_markdown(db, Path::new(&path), directory.id.clone()).await?;
+    db.log(
+        "directory::import".to_owned(),
+        format!(
+            "  id: {}
+from: {}
+notes: {}",
+            directory.id, path, summary.notes
+        ),
+    )
+    .await?;
+
+    open_all(db, state, directory.id.clone()).await?;
+    open(db, state, directory.id.clone()).await?;
+    state.selected = SelectedItem::Directory(directory.clone());
+    state.inner_state = InnerState::NoteTree(NoteTreeState::DirectorySelected);
+
+    Ok(NotebookTransition::NoteTree(
+        NoteTreeTransition::ImportFolder { directory, summary },
+    ))
+}
+
 pub async fn move_directory<B: CoreBackend + ?Sized>(
     db: &mut B,
     state: &mut NotebookState,
@@ -0,0 +1,34 @@
+use {
+    super::{directory, note},
+    crate::{
+        NotebookTransition, Result,
+        backend::CoreBackend,
+        journal::{self, JournalDay},
+        state::notebook::{InnerState, NotebookState, SelectedItem},
+    },
+};
+
+/// Opens the journal note of `day`, counted from the journal note being
+/// edited or selected, and from today otherwise.
+pub async fn open<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    day: JournalDay,
+) -> Result<NotebookTransition> {
+    let current = match state.inner_state {
+        InnerState::Editor(_) => state.get_editing().ok(),
+        InnerState::NoteTree(_) => match &state.selected {
+            SelectedItem::Note(note) => Some(note),
+            _ => None,
+        },
+    };
+    let date = day.date(current.and_then(journal::date_of));
+    let note = journal::find_or_create(db, date).await?;
+
+    // a note added to an expanded directory only shows up once it is reloaded
+    if state.check_opened(&note.directory_id) {
+        directory::open(db, state, note.directory_id.clone()).await?;
+    }
+
+    note::jump(db, state, note).await
+}
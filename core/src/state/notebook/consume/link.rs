@@ -0,0 +1,78 @@
+use {
+    super::note,
+    crate::{
+        Error, NotebookTransition, Result,
+        backend::CoreBackend,
+        data::Note,
+        state::notebook::{InnerState, NoteTreeState, NotebookState},
+    },
+    uuid::Uuid,
+};
+
+/// Opens the note a `[[target]]` link points at, looked up by ID first and
+/// then by name.
+pub async fn follow<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    target: String,
+) -> Result<NotebookTransition> {
+    match resolve(db, &target).await? {
+        Some(note) => note::jump(db, state, note).await,
+        None => Ok(NotebookTransition::Alert(format!(
+            "No note found for [[{target}]]"
+        ))),
+    }
+}
+
+/// Lists the notes linking to the note being edited, or to the selected
+/// one in the note tree.
+pub async fn backlinks<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+) -> Result<NotebookTransition> {
+    let note = match state.inner_state {
+        InnerState::Editor(_) => state.get_editing()?.clone(),
+        InnerState::NoteTree(_) => {
+            let note = state.get_selected_note()?.clone();
+            state.inner_state = InnerState::NoteTree(NoteTreeState::NoteSelected);
+
+            note
+        }
+    };
+    let notes = db.fetch_backlinks(note.id.clone()).await?;
+
+    Ok(NotebookTransition::ShowBacklinks { note, notes })
+}
+
+async fn resolve<B: CoreBackend + ?Sized>(db: &mut B, target: &str) -> Result<Option<Note>> {
+    if Uuid::parse_str(target).is_ok() {
+        match db.fetch_note(target.to_owned()).await {
+            Ok(note) => {
+                // a note in the trash is not listed in its directory
+                let listed = db
+                    .fetch_notes(note.directory_id.clone())
+                    .await?
+                    .into_iter()
+                    .any(|listed| listed.id == note.id);
+                if listed {
+                    return Ok(Some(note));
+                }
+            }
+            Err(Error::NotFound(_)) => {}
+            Err(error) => return Err(error),
+        }
+    }
+
+    // links imported from Obsidian may still carry a folder path
+    let name = target.rsplit('/').next().unwrap_or(target);
+    let mut notes = db
+        .search_notes(name.to_owned())
+        .await?
+        .into_iter()
+        .map(|result| result.note)
+        .filter(|note| note.name.to_lowercase() == name.to_lowercase())
+        .collect::<Vec<_>>();
+    notes.sort_by_key(|note| note.name != name);
+
+    Ok(notes.into_iter().next())
+}
@@ -1,5 +1,5 @@
 use {
-    super::{breadcrumb, directory},
+    super::{breadcrumb, change, directory},
     crate::{
         Error, NotebookTransition, Result,
         backend::CoreBackend,
@@ -33,33 +33,27 @@ pub async fn rename<B: CoreBackend + ?Sized>(
     note: Note,
     new_name: String,
 ) -> Result<NotebookTransition> {
-    db.rename_note(note.id.clone(), new_name.clone()).await?;
+    let updated = match db
+        .rename_note(
+            note.id.clone(),
+            new_name.clone(),
+            Some(note.updated_at.clone()),
+        )
+        .await
+    {
+        Err(Error::Conflict(message)) => return change::reject_stale(db, state, message).await,
+        result => result?,
+    };
     db.log(
         "note::rename".to_owned(),
         format!("  id: {}\nfrom: {}\n  to: {}", note.id, note.name, new_name),
     )
     .await?;
 
-    let notes = db.fetch_notes(note.directory_id.clone()).await?;
-    let updated = notes
-        .iter()
-        .find(|updated| updated.id == note.id)
-        .cloned()
-        .ok_or(Error::NotFound(
-            "[note::rename] failed to fetch updated note".to_owned(),
-        ))?;
-
     state.root.rename_note(&updated).ok_or(Error::NotFound(
         "[note::rename] failed to find parent directory".to_owned(),
     ))?;
-
-    for tab in state
-        .tabs
-        .iter_mut()
-        .filter(|tab| tab.note.id == updated.id)
-    {
-        tab.note = updated.clone();
-    }
+    state.refresh_moved_note(&note.updated_at, &updated);
 
     state.selected = SelectedItem::Note(updated.clone());
     state.inner_state = InnerState::NoteTree(NoteTreeState::NoteSelected);
@@ -125,6 +119,16 @@ pub async fn add<B: CoreBackend + ?Sized>(
 ) -> Result<NotebookTransition> {
     let note = db.add_note(directory.id.clone(), note_name).await?;
 
+    added(db, state, directory, note).await
+}
+
+/// Shows `note`, just added to `directory`, in the tree and selects it.
+pub(super) async fn added<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    directory: Directory,
+    note: Note,
+) -> Result<NotebookTransition> {
     let item = state
         .root
         .find_mut(&directory.id)
@@ -186,6 +190,26 @@ pub async fn open<B: CoreBackend + ?Sized>(
     }))
 }
 
+pub async fn jump<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    note: Note,
+) -> Result<NotebookTransition> {
+    directory::open_all(db, state, note.directory_id.clone()).await?;
+    state.selected = SelectedItem::Note(note.clone());
+
+    open(db, state, note).await
+}
+
+pub async fn search<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    query: String,
+) -> Result<NotebookTransition> {
+    let results = db.search_notes(query).await?;
+
+    Ok(NotebookTransition::SearchResults(results))
+}
+
 pub fn view(state: &mut NotebookState) -> Result<NotebookTransition> {
     let note = state.get_editing()?.clone();
 
@@ -196,14 +220,36 @@ pub fn view(state: &mut NotebookState) -> Result<NotebookTransition> {
 
 pub async fn update_content<B: CoreBackend + ?Sized>(
     db: &mut B,
+    state: &mut NotebookState,
     note_id: NoteId,
     content: String,
 ) -> Result<NotebookTransition> {
     let current = db.fetch_note_content(note_id.clone()).await?;
     let content = content.trim_end();
-    if current.trim_end() != content {
-        db.update_note_content(note_id.clone(), content.to_owned())
-            .await?;
+    if current.trim_end() == content {
+        // in sync with the stored note, whoever wrote it
+        let note = db.fetch_note(note_id.clone()).await?;
+        state.refresh_note(&note);
+
+        return Ok(NotebookTransition::UpdateNoteContent(note_id));
+    }
+
+    let expected_updated_at = state
+        .tabs
+        .iter()
+        .find(|tab| tab.note.id == note_id)
+        .map(|tab| tab.note.updated_at.clone());
+    match db
+        .update_note_content(note_id.clone(), content.to_owned(), expected_updated_at)
+        .await
+    {
+        Ok(note) => state.refresh_note(&note),
+        Err(Error::Conflict(message)) => {
+            let note = db.fetch_note(note_id).await?;
+
+            return Ok(NotebookTransition::NoteConflict { note, message });
+        }
+        Err(error) => return Err(error),
     }
 
     Ok(NotebookTransition::UpdateNoteContent(note_id))
@@ -214,16 +260,20 @@ pub async fn move_note<B: CoreBackend + ?Sized>(
     state: &mut NotebookState,
     directory_id: DirectoryId,
 ) -> Result<NotebookTransition> {
-    let mut note = state.get_selected_note()?.clone();
-    note.directory_id.clone_from(&directory_id);
-
-    state.tabs.iter_mut().for_each(|tab| {
-        if tab.note.id == note.id {
-            tab.note.directory_id.clone_from(&directory_id);
-        }
-    });
+    let read = state.get_selected_note()?.clone();
+    let note = match db
+        .move_note(
+            read.id.clone(),
+            directory_id.clone(),
+            Some(read.updated_at.clone()),
+        )
+        .await
+    {
+        Err(Error::Conflict(message)) => return change::reject_stale(db, state, message).await,
+        result => result?,
+    };
+    state.refresh_moved_note(&read.updated_at, &note);
 
-    db.move_note(note.id.clone(), directory_id.clone()).await?;
     directory::close(state, state.root.directory.clone())?;
     directory::open_all(db, state, directory_id).await?;
 
@@ -0,0 +1,54 @@
+use crate::{
+    NotebookTransition, Result,
+    backend::CoreBackend,
+    data::Note,
+    state::notebook::NotebookState,
+    types::{NoteId, RevisionId},
+};
+
+pub async fn show<B: CoreBackend + ?Sized>(db: &mut B, note: Note) -> Result<NotebookTransition> {
+    let content = db.fetch_note_content(note.id.clone()).await?;
+    let revisions = db.list_note_revisions(note.id.clone()).await?;
+
+    Ok(NotebookTransition::ShowNoteRevisions {
+        note,
+        content,
+        revisions,
+    })
+}
+
+pub async fn load<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    note_id: NoteId,
+    revision_id: RevisionId,
+) -> Result<NotebookTransition> {
+    let content = db.fetch_note_revision(note_id, revision_id.clone()).await?;
+
+    Ok(NotebookTransition::NoteRevisionLoaded {
+        id: revision_id,
+        content,
+    })
+}
+
+pub async fn restore<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    note_id: NoteId,
+    revision_id: RevisionId,
+) -> Result<NotebookTransition> {
+    let content = db
+        .fetch_note_revision(note_id.clone(), revision_id.clone())
+        .await?;
+    // an explicit choice of the user, so it wins over changes made elsewhere
+    let note = db
+        .update_note_content(note_id.clone(), content.clone(), None)
+        .await?;
+    state.refresh_note(&note);
+    db.log(
+        "note::restore_revision".to_owned(),
+        format!("      id: {note_id}\nrevision: {revision_id}"),
+    )
+    .await?;
+
+    Ok(NotebookTransition::NoteRevisionRestored { note_id, content })
+}
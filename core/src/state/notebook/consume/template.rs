@@ -0,0 +1,53 @@
+use {
+    super::note,
+    crate::{
+        NotebookTransition, Result,
+        backend::CoreBackend,
+        data::{Directory, Note},
+        state::notebook::NotebookState,
+        template::{self, Placeholders, TEMPLATES_DIRECTORY},
+    },
+    chrono::Local,
+};
+
+/// Lists the notes of the top-level templates directory, if there is one.
+pub async fn list<B: CoreBackend + ?Sized>(db: &mut B) -> Result<NotebookTransition> {
+    let directory = db
+        .fetch_directories(db.root_id())
+        .await?
+        .into_iter()
+        .find(|directory| directory.name == TEMPLATES_DIRECTORY);
+    let mut templates = match directory {
+        Some(directory) => db.fetch_notes(directory.id).await?,
+        None => Vec::new(),
+    };
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(NotebookTransition::ShowTemplates(templates))
+}
+
+/// Adds a note to `directory` whose content is `template` with its
+/// placeholders expanded.
+pub async fn add<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    directory: Directory,
+    template: Note,
+    note_name: String,
+) -> Result<NotebookTransition> {
+    let content = db.fetch_note_content(template.id).await?;
+    let date = Local::now().format("%Y-%m-%d").to_string();
+    let content = template::expand(
+        &content,
+        &Placeholders {
+            title: &note_name,
+            directory: &directory.name,
+            date: &date,
+        },
+    );
+
+    let note = db.add_note(directory.id.clone(), note_name).await?;
+    let note = db.update_note_content(note.id, content, None).await?;
+
+    note::added(db, state, directory, note).await
+}
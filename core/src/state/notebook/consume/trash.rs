@@ -0,0 +1,81 @@
+use {
+    super::directory,
+    crate::{
+        NotebookTransition, Result,
+        backend::CoreBackend,
+        state::notebook::{InnerState, NoteTreeState, NotebookState, SelectedItem},
+        types::{DirectoryId, NoteId},
+    },
+};
+
+pub async fn show<B: CoreBackend + ?Sized>(db: &mut B) -> Result<NotebookTransition> {
+    let trash = db.fetch_trash().await?;
+
+    Ok(NotebookTransition::ShowTrash(trash))
+}
+
+pub async fn restore_note<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    note_id: NoteId,
+) -> Result<NotebookTransition> {
+    let note = db.restore_note(note_id).await?;
+    db.log(
+        "note::restore".to_owned(),
+        format!("  id: {}\nname: {}", note.id, note.name),
+    )
+    .await?;
+
+    reload(db, state, note.directory_id.clone()).await?;
+    state.selected = SelectedItem::Note(note.clone());
+    state.inner_state = InnerState::NoteTree(NoteTreeState::NoteSelected);
+
+    let trash = db.fetch_trash().await?;
+
+    Ok(NotebookTransition::RestoreFromTrash { id: note.id, trash })
+}
+
+pub async fn restore_directory<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    directory_id: DirectoryId,
+) -> Result<NotebookTransition> {
+    let directory = db.restore_directory(directory_id).await?;
+    db.log(
+        "directory::restore".to_owned(),
+        format!("  id: {}\nname: {}", directory.id, directory.name),
+    )
+    .await?;
+
+    reload(db, state, directory.parent_id.clone()).await?;
+    state.selected = SelectedItem::Directory(directory.clone());
+    state.inner_state = InnerState::NoteTree(NoteTreeState::DirectorySelected);
+
+    let trash = db.fetch_trash().await?;
+
+    Ok(NotebookTransition::RestoreFromTrash {
+        id: directory.id,
+        trash,
+    })
+}
+
+pub async fn empty<B: CoreBackend + ?Sized>(db: &mut B) -> Result<NotebookTransition> {
+    db.purge().await?;
+    db.log("trash::empty".to_owned(), "purged trash".to_owned())
+        .await?;
+
+    show(db).await
+}
+
+/// Expands the tree down to `directory_id` and refetches its children so the
+/// restored entry shows up even when the directory was already open.
+async fn reload<B: CoreBackend + ?Sized>(
+    db: &mut B,
+    state: &mut NotebookState,
+    directory_id: DirectoryId,
+) -> Result<()> {
+    directory::open_all(db, state, directory_id.clone()).await?;
+    directory::open(db, state, directory_id).await?;
+
+    Ok(())
+}
@@ -3,13 +3,13 @@ use crate::{
     types::{DirectoryId, Id},
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct DirectoryItem {
     pub directory: Directory,
     pub children: Option<DirectoryItemChildren>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct DirectoryItemChildren {
     pub directories: Vec<DirectoryItem>,
     pub notes: Vec<Note>,
@@ -4,7 +4,7 @@ mod note_tree;
 use crate::{
     Event, NotebookEvent, NotebookTransition, Result,
     backend::CoreBackend,
-    state::notebook::{NotebookState, note},
+    state::notebook::{NotebookState, change, conflict, journal, link, note, revision, trash},
     types::KeymapGroup,
 };
 pub use editor::{EditorState, VimNormalState, VimVisualState};
@@ -21,8 +21,62 @@ pub async fn consume<B: CoreBackend + ?Sized>(
     state: &mut NotebookState,
     event: Event,
 ) -> Result<NotebookTransition> {
-    if let Event::Notebook(NotebookEvent::UpdateNoteContent { note_id, content }) = event {
-        return note::update_content(db, note_id, content).await;
+    match event {
+        Event::Notebook(NotebookEvent::UpdateNoteContent { note_id, content }) => {
+            return note::update_content(db, state, note_id, content).await;
+        }
+        Event::Notebook(NotebookEvent::ReloadNote(note_id)) => {
+            return conflict::reload(db, state, note_id).await;
+        }
+        Event::Notebook(NotebookEvent::OverwriteNoteContent { note_id, content }) => {
+            return conflict::overwrite(db, state, note_id, content).await;
+        }
+        Event::Notebook(NotebookEvent::SaveNoteCopy { note_id, content }) => {
+            return conflict::save_copy(db, state, note_id, content).await;
+        }
+        Event::Notebook(NotebookEvent::SearchNotes(query)) => {
+            return note::search(db, query).await;
+        }
+        Event::Notebook(NotebookEvent::JumpToNote(note)) => {
+            return note::jump(db, state, note).await;
+        }
+        Event::Notebook(NotebookEvent::FollowLink(target)) => {
+            return link::follow(db, state, target).await;
+        }
+        Event::Notebook(NotebookEvent::ShowBacklinks) => {
+            return link::backlinks(db, state).await;
+        }
+        Event::Notebook(NotebookEvent::OpenJournal(day)) => {
+            return journal::open(db, state, day).await;
+        }
+        Event::Notebook(NotebookEvent::LoadNoteRevision {
+            note_id,
+            revision_id,
+        }) => {
+            return revision::load(db, note_id, revision_id).await;
+        }
+        Event::Notebook(NotebookEvent::RestoreNoteRevision {
+            note_id,
+            revision_id,
+        }) => {
+            return revision::restore(db, state, note_id, revision_id).await;
+        }
+        Event::Notebook(NotebookEvent::RestoreNote(note_id)) => {
+            return trash::restore_note(db, state, note_id).await;
+        }
+        Event::Notebook(NotebookEvent::RestoreDirectory(directory_id)) => {
+            return trash::restore_directory(db, state, directory_id).await;
+        }
+        Event::Notebook(NotebookEvent::EmptyTrash) => {
+            return trash::empty(db).await;
+        }
+        Event::Notebook(NotebookEvent::ApplyChanges(changes)) => {
+            return change::apply(db, state, changes).await;
+        }
+        Event::Notebook(NotebookEvent::CheckChanges) => {
+            return change::detect(db, state).await;
+        }
+        _ => {}
     }
 
     match state.inner_state {
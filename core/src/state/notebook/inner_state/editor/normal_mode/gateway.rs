@@ -1,6 +1,7 @@
 use super::VimNormalState;
 use crate::{
     Error, Event, KeyEvent, Result,
+    journal::JournalDay,
     state::notebook::{EditorState, InnerState, NotebookState},
     transition::{NormalModeTransition, NotebookTransition},
     types::{KeymapGroup, KeymapItem},
@@ -16,6 +17,31 @@ pub fn consume(state: &mut NotebookState, event: Event) -> Result<NotebookTransi
 
             NormalModeTransition::MoveCursorTop.into()
         }
+        Key(KeyEvent::F) => {
+            state.inner_state = InnerState::Editor(EditorState::Normal(VimNormalState::Idle));
+
+            FollowLink.into()
+        }
+        Key(KeyEvent::B) => {
+            state.inner_state = InnerState::Editor(EditorState::Normal(VimNormalState::Idle));
+
+            NormalModeTransition::ShowBacklinks.into()
+        }
+        Key(KeyEvent::D) => {
+            state.inner_state = InnerState::Editor(EditorState::Normal(VimNormalState::Idle));
+
+            OpenJournal(JournalDay::Today).into()
+        }
+        Key(KeyEvent::P) => {
+            state.inner_state = InnerState::Editor(EditorState::Normal(VimNormalState::Idle));
+
+            OpenJournal(JournalDay::Previous).into()
+        }
+        Key(KeyEvent::N) => {
+            state.inner_state = InnerState::Editor(EditorState::Normal(VimNormalState::Idle));
+
+            OpenJournal(JournalDay::Next).into()
+        }
         Key(KeyEvent::Esc) => {
             state.inner_state = InnerState::Editor(EditorState::Normal(VimNormalState::Idle));
 
@@ -37,6 +63,11 @@ pub fn keymap() -> Vec<KeymapGroup> {
         "General",
         vec![
             KeymapItem::new("g", "Move cursor to top"),
+            KeymapItem::new("f", "Follow the [[link]] under the cursor"),
+            KeymapItem::new("b", "Show notes linking here"),
+            KeymapItem::new("d", "Open today's journal note"),
+            KeymapItem::new("p", "Open the previous day's journal note"),
+            KeymapItem::new("n", "Open the next day's journal note"),
             KeymapItem::new("Esc", "Cancel"),
         ],
     )]
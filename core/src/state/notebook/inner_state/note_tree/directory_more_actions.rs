@@ -1,7 +1,7 @@
 use crate::{
     Error, Event, NotebookEvent, NotebookTransition, Result,
     backend::CoreBackend,
-    state::notebook::{NotebookState, directory, note},
+    state::notebook::{NotebookState, directory, note, template},
     types::{KeymapGroup, KeymapItem},
 };
 
@@ -34,11 +34,22 @@ pub async fn consume<B: CoreBackend + ?Sized>(
 
             note::add(db, state, directory, note_name).await
         }
+        Notebook(ShowTemplates) => template::list(db).await,
+        Notebook(AddNoteFromTemplate { template, name }) => {
+            let directory = state.get_selected_directory()?.clone();
+
+            template::add(db, state, directory, template, name).await
+        }
         Notebook(AddDirectory(directory_name)) => {
             let directory = state.get_selected_directory()?.clone();
 
             directory::add(db, state, directory, directory_name).await
         }
+        Notebook(ImportFolder(path)) => {
+            let directory = state.get_selected_directory()?.clone();
+
+            directory::import(db, state, directory, path).await
+        }
         Cancel => {
             let directory = state.get_selected_directory()?.clone();
 
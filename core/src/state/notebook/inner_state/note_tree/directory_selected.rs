@@ -3,7 +3,7 @@ use {
     crate::{
         Error, Event, KeyEvent, NotebookEvent, NotebookTransition, Result,
         backend::CoreBackend,
-        state::notebook::{InnerState, NotebookState, directory, note, tabs},
+        state::notebook::{InnerState, NotebookState, directory, note, tabs, trash},
         transition::{MoveModeTransition, NoteTreeTransition},
         types::{KeymapGroup, KeymapItem},
     },
@@ -104,6 +104,8 @@ pub async fn consume<B: CoreBackend + ?Sized>(
         Key(KeyEvent::AngleBracketClose) => Ok(NotebookTransition::NoteTree(
             NoteTreeTransition::ExpandWidth(1),
         )),
+        Key(KeyEvent::Slash) => Ok(NotebookTransition::ShowSearchDialog),
+        Key(KeyEvent::T) => trash::show(db).await,
         Key(KeyEvent::Tab) if !state.tabs.is_empty() => tabs::focus_editor(db, state).await,
         event @ Key(_) => Ok(NotebookTransition::Inedible(event)),
         _ => Err(Error::Todo(
@@ -126,6 +128,8 @@ pub fn keymap(state: &NotebookState) -> Vec<KeymapGroup> {
         KeymapItem::new("<", "Shrink width"),
         KeymapItem::new("Space", "Move directory"),
         KeymapItem::new("m", "Show more actions"),
+        KeymapItem::new("/", "Search notes"),
+        KeymapItem::new("t", "Show trash"),
     ];
 
     if !state.tabs.is_empty() {
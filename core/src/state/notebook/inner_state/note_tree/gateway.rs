@@ -1,5 +1,6 @@
 use crate::{
     Error, Event, KeyEvent, NotebookTransition, Result,
+    journal::JournalDay,
     state::notebook::{InnerState, NoteTreeState, NotebookState, SelectedItem},
     transition::NoteTreeTransition,
     types::{KeymapGroup, KeymapItem},
@@ -16,6 +17,9 @@ pub fn consume(state: &mut NotebookState, event: Event) -> Result<NotebookTransi
                 NoteTreeTransition::SelectFirst,
             ))
         }
+        Key(KeyEvent::D) => open_journal(state, JournalDay::Today),
+        Key(KeyEvent::P) => open_journal(state, JournalDay::Previous),
+        Key(KeyEvent::N) => open_journal(state, JournalDay::Next),
         Key(KeyEvent::Esc) => {
             state.inner_state = leave_gateway_mode(&state.selected)?;
 
@@ -30,6 +34,14 @@ pub fn consume(state: &mut NotebookState, event: Event) -> Result<NotebookTransi
     }
 }
 
+fn open_journal(state: &mut NotebookState, day: JournalDay) -> Result<NotebookTransition> {
+    state.inner_state = leave_gateway_mode(&state.selected)?;
+
+    Ok(NotebookTransition::NoteTree(
+        NoteTreeTransition::OpenJournal(day),
+    ))
+}
+
 fn leave_gateway_mode(selected: &SelectedItem) -> Result<InnerState> {
     match selected {
         SelectedItem::Directory(_) => Ok(InnerState::NoteTree(NoteTreeState::DirectorySelected)),
@@ -43,6 +55,9 @@ pub fn keymap() -> Vec<KeymapGroup> {
         "General",
         vec![
             KeymapItem::new("g", "Select first"),
+            KeymapItem::new("d", "Open today's journal note"),
+            KeymapItem::new("p", "Open the previous day's journal note"),
+            KeymapItem::new("n", "Open the next day's journal note"),
             KeymapItem::new("Esc", "Cancel"),
         ],
     )]
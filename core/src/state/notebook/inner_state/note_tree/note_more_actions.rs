@@ -1,7 +1,7 @@
 use crate::{
     Error, Event, NotebookEvent, NotebookTransition, Result,
     backend::CoreBackend,
-    state::notebook::{NotebookState, note},
+    state::notebook::{NotebookState, note, revision},
     types::{KeymapGroup, KeymapItem},
 };
 
@@ -24,6 +24,12 @@ pub async fn consume<B: CoreBackend + ?Sized>(
 
             note::rename(db, state, note, new_name).await
         }
+        Notebook(ShowNoteRevisions) => {
+            let note = state.get_selected_note()?.clone();
+            note::select(state, note.clone());
+
+            revision::show(db, note).await
+        }
         Notebook(RemoveNote) => {
             let note = state.get_selected_note()?.clone();
 
@@ -0,0 +1,46 @@
+//! Note templates.
+//!
+//! Notes in the top-level [`TEMPLATES_DIRECTORY`] are offered as templates
+//! for new notes. A template's content is copied into the new note with its
+//! placeholders expanded:
+//!
+//! - `{{title}}`: the name of the new note
+//! - `{{directory}}`: the name of the directory it is added to
+//! - `{{date}}`: today's date as `YYYY-MM-DD`
+//!
+//! Any other `{{…}}` is left as written.
+
+/// Name of the top-level directory holding the templates.
+pub const TEMPLATES_DIRECTORY: &str = "Templates";
+
+/// Values substituted for the placeholders of a template.
+pub struct Placeholders<'a> {
+    pub title: &'a str,
+    pub directory: &'a str,
+    pub date: &'a str,
+}
+
+/// Expands the placeholders in `content`.
+pub fn expand(content: &str, placeholders: &Placeholders) -> String {
+    let mut expanded = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 4 + len;
+        let value = match rest[start + 2..start + 2 + len].trim() {
+            "title" => placeholders.title,
+            "directory" => placeholders.directory,
+            "date" => placeholders.date,
+            _ => &rest[start..end],
+        };
+
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(value);
+        rest = &rest[end..];
+    }
+    expanded.push_str(rest);
+
+    expanded
+}
@@ -1,9 +1,11 @@
 use {
     crate::{
         Event,
-        data::{Directory, Note},
+        data::{Directory, Note, NoteRevision, NoteSearchResult, Trash},
+        import::ImportSummary,
+        journal::JournalDay,
         state::notebook::DirectoryItem,
-        types::{DirectoryId, NoteId},
+        types::{DirectoryId, NoteId, RevisionId},
     },
     strum_macros::Display,
 };
@@ -46,6 +48,55 @@ pub enum NotebookTransition {
     FocusEditor,
 
     UpdateNoteContent(NoteId),
+    /// The note changed elsewhere since it was opened, so the save was
+    /// refused; `note` is the stored version.
+    NoteConflict {
+        note: Note,
+        message: String,
+    },
+    NoteReloaded {
+        note_id: NoteId,
+        content: String,
+    },
+    NoteCopySaved {
+        copy: Note,
+        note_id: NoteId,
+        content: String,
+    },
+
+    ShowSearchDialog,
+    SearchResults(Vec<NoteSearchResult>),
+
+    ShowTemplates(Vec<Note>),
+    ShowBacklinks {
+        note: Note,
+        notes: Vec<Note>,
+    },
+    ShowNoteRevisions {
+        note: Note,
+        content: String,
+        revisions: Vec<NoteRevision>,
+    },
+    NoteRevisionLoaded {
+        id: RevisionId,
+        content: String,
+    },
+    NoteRevisionRestored {
+        note_id: NoteId,
+        content: String,
+    },
+    /// The tree was refreshed after changes by other clients; `notes` are
+    /// the open notes changed elsewhere, to be reloaded or, when edited
+    /// here, resolved by the user.
+    RemoteChanges {
+        notes: Vec<NoteId>,
+    },
+
+    ShowTrash(Trash),
+    RestoreFromTrash {
+        id: String,
+        trash: Trash,
+    },
 
     Alert(String),
 
@@ -81,6 +132,10 @@ pub enum NoteTreeTransition {
 
     AddNote(Note),
     AddDirectory(Directory),
+    ImportFolder {
+        directory: Directory,
+        summary: ImportSummary,
+    },
 
     ShowNoteActionsDialog(Note),
     ShowDirectoryActionsDialog(Directory),
@@ -95,6 +150,8 @@ pub enum NoteTreeTransition {
     SelectNext(usize),
     SelectPrev(usize),
     SelectFirst,
+    /// Asks the frontend to open a daily journal note.
+    OpenJournal(JournalDay),
     SelectLast,
 
     SelectNextDirectory,
@@ -140,6 +197,11 @@ pub enum NormalModeTransition {
     ChangeMode,
     ChangeInsideMode,
     ScrollMode,
+    /// Asks the frontend for the link under the cursor to follow.
+    FollowLink,
+    ShowBacklinks,
+    /// Asks the frontend to save and open a daily journal note.
+    OpenJournal(JournalDay),
 
     // toggle mode
     NextTab(NoteId),
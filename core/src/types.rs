@@ -1,5 +1,7 @@
 pub type NoteId = String; // UUID
 pub type DirectoryId = String; // UUID
+pub type TagId = String; // UUID
+pub type RevisionId = String; // UUID
 pub type Id = String; // UUID
 
 #[derive(Clone, Debug)]
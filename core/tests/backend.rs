@@ -1,4 +1,7 @@
-use glues_core::backend::{CoreBackend, local::Db};
+use glues_core::{
+    Error,
+    backend::{CoreBackend, local::Db},
+};
 
 #[tokio::test]
 async fn memory_backend_operations() {
@@ -44,7 +47,7 @@ async fn memory_backend_operations() {
     assert_eq!(notes[0].name, "Todo");
 
     // update note content
-    db.update_note_content(note.id.clone(), "hello".to_owned())
+    db.update_note_content(note.id.clone(), "hello".to_owned(), None)
         .await
         .expect("backend should update note content");
     let content = db
@@ -54,7 +57,7 @@ async fn memory_backend_operations() {
     assert_eq!(content, "hello");
 
     // rename note
-    db.rename_note(note.id.clone(), "Hello".to_owned())
+    db.rename_note(note.id.clone(), "Hello".to_owned(), None)
         .await
         .expect("backend should rename note");
     let notes = db
@@ -64,7 +67,7 @@ async fn memory_backend_operations() {
     assert_eq!(notes[0].name, "Hello");
 
     // move note to root
-    db.move_note(note.id.clone(), root_id.clone())
+    db.move_note(note.id.clone(), root_id.clone(), None)
         .await
         .expect("backend should move note");
     let notes_root = db
@@ -98,3 +101,533 @@ async fn memory_backend_operations() {
         .await
         .expect("backend should append log entry");
 }
+
+#[tokio::test]
+async fn memory_backend_search_notes() {
+    let mut db = Db::memory()
+        .await
+        .expect("in-memory backend should initialize");
+    let root_id = db.root_id();
+
+    let dir = db
+        .add_directory(root_id.clone(), "Projects".to_owned())
+        .await
+        .expect("backend should add directory");
+    let roadmap = db
+        .add_note(dir.id.clone(), "Roadmap".to_owned())
+        .await
+        .expect("backend should add note");
+    db.update_note_content(
+        roadmap.id.clone(),
+        "intro\nShip the Search feature\nmore text\nsearch again".to_owned(),
+        None,
+    )
+    .await
+    .expect("backend should update note content");
+    let research = db
+        .add_note(root_id.clone(), "Research log".to_owned())
+        .await
+        .expect("backend should add note");
+    db.add_note(root_id.clone(), "Groceries".to_owned())
+        .await
+        .expect("backend should add note");
+
+    let results = db
+        .search_notes("SEARCH".to_owned())
+        .await
+        .expect("backend should search notes");
+    let names = results
+        .iter()
+        .map(|result| result.note.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["Research log", "Roadmap"]);
+
+    let roadmap_result = results
+        .iter()
+        .find(|result| result.note.id == roadmap.id)
+        .expect("roadmap note should match content");
+    let snippets = roadmap_result
+        .snippets
+        .iter()
+        .map(|snippet| (snippet.line_number, snippet.text.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        snippets,
+        vec![(2, "Ship the Search feature"), (4, "search again")]
+    );
+
+    let research_result = results
+        .iter()
+        .find(|result| result.note.id == research.id)
+        .expect("research note should match name");
+    assert!(research_result.snippets.is_empty());
+
+    let results = db
+        .search_notes("   ".to_owned())
+        .await
+        .expect("backend should accept blank queries");
+    assert!(results.is_empty());
+}
+
+#[tokio::test]
+async fn memory_backend_note_tags() {
+    let mut db = Db::memory()
+        .await
+        .expect("in-memory backend should initialize");
+    let root_id = db.root_id();
+
+    let dir = db
+        .add_directory(root_id.clone(), "Projects".to_owned())
+        .await
+        .expect("backend should add directory");
+    let roadmap = db
+        .add_note(dir.id.clone(), "Roadmap".to_owned())
+        .await
+        .expect("backend should add note");
+    let ideas = db
+        .add_note(root_id.clone(), "Ideas".to_owned())
+        .await
+        .expect("backend should add note");
+
+    db.add_note_tag(roadmap.id.clone(), " work ".to_owned())
+        .await
+        .expect("backend should tag note");
+    db.add_note_tag(roadmap.id.clone(), "urgent".to_owned())
+        .await
+        .expect("backend should tag note");
+    db.add_note_tag(roadmap.id.clone(), "work".to_owned())
+        .await
+        .expect("backend should ignore duplicate tags");
+    db.add_note_tag(ideas.id.clone(), "work".to_owned())
+        .await
+        .expect("backend should reuse existing tag");
+    assert!(
+        db.add_note_tag(ideas.id.clone(), "  ".to_owned())
+            .await
+            .is_err()
+    );
+
+    let tags = db.fetch_tags().await.expect("backend should list tags");
+    let names = tags.iter().map(|tag| tag.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["urgent", "work"]);
+
+    let notes = db
+        .fetch_notes(dir.id.clone())
+        .await
+        .expect("backend should list notes with tags");
+    assert_eq!(notes[0].tags, vec!["urgent", "work"]);
+
+    let tagged = db
+        .fetch_notes_by_tag("work".to_owned())
+        .await
+        .expect("backend should fetch notes by tag");
+    let names = tagged
+        .iter()
+        .map(|note| note.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["Ideas", "Roadmap"]);
+
+    db.remove_note_tag(roadmap.id.clone(), "urgent".to_owned())
+        .await
+        .expect("backend should untag note");
+    let tags = db.fetch_tags().await.expect("backend should list tags");
+    let names = tags.iter().map(|tag| tag.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["work"]);
+
+    db.remove_directory(dir.id.clone())
+        .await
+        .expect("backend should remove directory");
+    db.remove_note(ideas.id.clone())
+        .await
+        .expect("backend should remove note");
+    db.purge().await.expect("backend should purge trash");
+    let tags = db.fetch_tags().await.expect("backend should list tags");
+    assert!(tags.is_empty());
+}
+
+#[tokio::test]
+async fn memory_backend_migrate_from() {
+    let mut source = Db::memory()
+        .await
+        .expect("in-memory backend should initialize");
+    let root_id = source.root_id();
+    let dir = source
+        .add_directory(root_id.clone(), "Work".to_owned())
+        .await
+        .expect("backend should add directory");
+    let nested = source
+        .add_directory(dir.id.clone(), "Archive".to_owned())
+        .await
+        .expect("backend should add nested directory");
+    let note = source
+        .add_note(nested.id.clone(), "Plan".to_owned())
+        .await
+        .expect("backend should add note");
+    source
+        .update_note_content(note.id.clone(), "line one\nline two".to_owned(), None)
+        .await
+        .expect("backend should update note content");
+    source
+        .add_note_tag(note.id.clone(), "project".to_owned())
+        .await
+        .expect("backend should tag note");
+    source
+        .add_note(root_id.clone(), "Inbox".to_owned())
+        .await
+        .expect("backend should add note");
+
+    let mut destination = Db::memory()
+        .await
+        .expect("in-memory backend should initialize");
+    let summary = destination
+        .migrate_from(&mut source)
+        .await
+        .expect("backend should migrate notes");
+    assert_eq!(summary.directories, 2);
+    assert_eq!(summary.notes, 2);
+    assert_eq!(destination.root_id(), root_id);
+
+    let dirs = destination
+        .fetch_directories(dir.id.clone())
+        .await
+        .expect("backend should list migrated directories");
+    assert_eq!(dirs.len(), 1);
+    assert_eq!(dirs[0].id, nested.id);
+    assert_eq!(dirs[0].created_at, nested.created_at);
+
+    let expected = source
+        .fetch_notes(nested.id.clone())
+        .await
+        .expect("backend should list source notes");
+    let notes = destination
+        .fetch_notes(nested.id.clone())
+        .await
+        .expect("backend should list migrated notes");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].id, note.id);
+    assert_eq!(notes[0].created_at, expected[0].created_at);
+    assert_eq!(notes[0].updated_at, expected[0].updated_at);
+    assert_eq!(notes[0].tags, vec!["project"]);
+
+    let content = destination
+        .fetch_note_content(note.id.clone())
+        .await
+        .expect("backend should fetch migrated content");
+    assert_eq!(content, "line one\nline two");
+
+    let mut source = Db::memory()
+        .await
+        .expect("in-memory backend should initialize");
+    assert!(destination.migrate_from(&mut source).await.is_err());
+}
+
+#[tokio::test]
+async fn memory_backend_trash() {
+    let mut db = Db::memory()
+        .await
+        .expect("in-memory backend should initialize");
+    let root_id = db.root_id();
+
+    let work = db
+        .add_directory(root_id.clone(), "Work".to_owned())
+        .await
+        .expect("backend should add directory");
+    let archive = db
+        .add_directory(work.id.clone(), "Archive".to_owned())
+        .await
+        .expect("backend should add nested directory");
+    let plan = db
+        .add_note(archive.id.clone(), "Plan".to_owned())
+        .await
+        .expect("backend should add note");
+    let draft = db
+        .add_note(work.id.clone(), "Draft".to_owned())
+        .await
+        .expect("backend should add note");
+    let inbox = db
+        .add_note(root_id.clone(), "Inbox".to_owned())
+        .await
+        .expect("backend should add note");
+
+    // a note trashed on its own stays in the trash when its directory comes back
+    db.remove_note(draft.id.clone())
+        .await
+        .expect("backend should trash note");
+    db.remove_directory(work.id.clone())
+        .await
+        .expect("backend should trash directory");
+    db.remove_note(inbox.id.clone())
+        .await
+        .expect("backend should trash note");
+
+    assert!(
+        db.fetch_directories(root_id.clone())
+            .await
+            .expect("backend should list directories")
+            .is_empty()
+    );
+    assert!(
+        db.fetch_notes(root_id.clone())
+            .await
+            .expect("backend should list notes")
+            .is_empty()
+    );
+    assert!(
+        db.search_notes("plan".to_owned())
+            .await
+            .expect("backend should search notes")
+            .is_empty()
+    );
+
+    let trash = db.fetch_trash().await.expect("backend should list trash");
+    let directories = trash
+        .directories
+        .iter()
+        .map(|directory| directory.name.as_str())
+        .collect::<Vec<_>>();
+    let mut notes = trash
+        .notes
+        .iter()
+        .map(|note| note.name.as_str())
+        .collect::<Vec<_>>();
+    notes.sort();
+    assert_eq!(directories, vec!["Work"]);
+    assert_eq!(notes, vec!["Draft", "Inbox"]);
+
+    let restored = db
+        .restore_directory(work.id.clone())
+        .await
+        .expect("backend should restore directory");
+    assert_eq!(restored.parent_id, root_id);
+    let notes = db
+        .fetch_notes(archive.id.clone())
+        .await
+        .expect("backend should list restored notes");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].id, plan.id);
+    assert!(
+        db.fetch_notes(work.id.clone())
+            .await
+            .expect("backend should list notes")
+            .is_empty()
+    );
+
+    // restoring a note whose directory is gone moves it to the root
+    db.remove_note(plan.id.clone())
+        .await
+        .expect("backend should trash note");
+    db.remove_directory(archive.id.clone())
+        .await
+        .expect("backend should trash directory");
+    let restored = db
+        .restore_note(plan.id.clone())
+        .await
+        .expect("backend should restore note");
+    assert_eq!(restored.directory_id, root_id);
+    assert!(db.restore_note(plan.id.clone()).await.is_err());
+
+    db.purge().await.expect("backend should purge trash");
+    let trash = db.fetch_trash().await.expect("backend should list trash");
+    assert!(trash.directories.is_empty());
+    assert!(trash.notes.is_empty());
+    assert!(db.fetch_directory(archive.id.clone()).await.is_err());
+    let notes = db
+        .fetch_notes(root_id.clone())
+        .await
+        .expect("backend should list notes");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].id, plan.id);
+}
+
+#[tokio::test]
+async fn memory_backend_note_revisions() {
+    let mut db = Db::memory()
+        .await
+        .expect("in-memory backend should initialize");
+    let note = db
+        .add_note(db.root_id(), "Journal".to_owned())
+        .await
+        .expect("backend should add note");
+
+    for content in ["first", "second", "second", "third"] {
+        db.update_note_content(note.id.clone(), content.to_owned(), None)
+            .await
+            .expect("backend should update note content");
+    }
+
+    let revisions = db
+        .list_note_revisions(note.id.clone())
+        .await
+        .expect("backend should list revisions");
+    let mut contents = Vec::new();
+    for revision in &revisions {
+        assert_eq!(revision.note_id, note.id);
+        contents.push(
+            db.fetch_note_revision(note.id.clone(), revision.id.clone())
+                .await
+                .expect("backend should fetch revision"),
+        );
+    }
+    assert_eq!(contents, vec!["third", "second", "first"]);
+
+    for i in 0..105 {
+        db.update_note_content(note.id.clone(), format!("edit {i}"), None)
+            .await
+            .expect("backend should update note content");
+    }
+    let revisions = db
+        .list_note_revisions(note.id.clone())
+        .await
+        .expect("backend should list revisions");
+    assert_eq!(revisions.len(), 100);
+    let latest = db
+        .fetch_note_revision(note.id.clone(), revisions[0].id.clone())
+        .await
+        .expect("backend should fetch revision");
+    assert_eq!(latest, "edit 104");
+}
+
+#[tokio::test]
+async fn memory_backend_keeps_original_content_as_revision() {
+    let mut source = Db::memory()
+        .await
+        .expect("in-memory backend should initialize");
+    let note = source
+        .add_note(source.root_id(), "Plan".to_owned())
+        .await
+        .expect("backend should add note");
+    source
+        .update_note_content(note.id.clone(), "original".to_owned(), None)
+        .await
+        .expect("backend should update note content");
+
+    // migrated notes start without history
+    let mut db = Db::memory()
+        .await
+        .expect("in-memory backend should initialize");
+    db.migrate_from(&mut source)
+        .await
+        .expect("backend should migrate notes");
+    db.update_note_content(note.id.clone(), "changed".to_owned(), None)
+        .await
+        .expect("backend should update note content");
+
+    let revisions = db
+        .list_note_revisions(note.id.clone())
+        .await
+        .expect("backend should list revisions");
+    assert_eq!(revisions.len(), 2);
+    let original = db
+        .fetch_note_revision(note.id.clone(), revisions[1].id.clone())
+        .await
+        .expect("backend should fetch revision");
+    assert_eq!(original, "original");
+}
+
+#[tokio::test]
+async fn memory_backend_rejects_stale_note_changes() {
+    let mut db = Db::memory()
+        .await
+        .expect("in-memory backend should initialize");
+    let root_id = db.root_id();
+    let work = db
+        .add_directory(root_id.clone(), "Work".to_owned())
+        .await
+        .expect("backend should add directory");
+    let read = db
+        .add_note(root_id.clone(), "Plan".to_owned())
+        .await
+        .expect("backend should add note");
+
+    // another client saves first
+    let saved = db
+        .update_note_content(
+            read.id.clone(),
+            "theirs".to_owned(),
+            Some(read.updated_at.clone()),
+        )
+        .await
+        .expect("backend should update a note read at its latest version");
+    assert_ne!(saved.updated_at, read.updated_at);
+
+    let stale = Some(read.updated_at.clone());
+    assert!(matches!(
+        db.update_note_content(read.id.clone(), "mine".to_owned(), stale.clone())
+            .await,
+        Err(Error::Conflict(_))
+    ));
+    assert!(matches!(
+        db.rename_note(read.id.clone(), "Mine".to_owned(), stale.clone())
+            .await,
+        Err(Error::Conflict(_))
+    ));
+    assert!(matches!(
+        db.move_note(read.id.clone(), work.id.clone(), stale).await,
+        Err(Error::Conflict(_))
+    ));
+    let content = db
+        .fetch_note_content(read.id.clone())
+        .await
+        .expect("backend should fetch note content");
+    assert_eq!(content, "theirs");
+
+    // the returned note carries the version to expect next
+    let renamed = db
+        .rename_note(read.id.clone(), "Mine".to_owned(), Some(saved.updated_at))
+        .await
+        .expect("backend should rename a note read at its latest version");
+    assert_eq!(renamed.name, "Mine");
+    db.update_note_content(read.id.clone(), "mine".to_owned(), None)
+        .await
+        .expect("backend should overwrite without a precondition");
+}
+
+#[tokio::test]
+async fn memory_backend_backlinks() {
+    let mut db = Db::memory()
+        .await
+        .expect("in-memory backend should initialize");
+    let root_id = db.root_id();
+
+    let target = db
+        .add_note(root_id.clone(), "Target".to_owned())
+        .await
+        .expect("backend should add note");
+    let by_name = db
+        .add_note(root_id.clone(), "By name".to_owned())
+        .await
+        .expect("backend should add note");
+    let by_id = db
+        .add_note(root_id.clone(), "By id".to_owned())
+        .await
+        .expect("backend should add note");
+
+    db.update_note_content(by_name.id.clone(), "see [[target|it]]".to_owned(), None)
+        .await
+        .expect("backend should update note content");
+    db.update_note_content(by_id.id.clone(), format!("[[{}#top]]", target.id), None)
+        .await
+        .expect("backend should update note content");
+
+    let names = |notes: Vec<glues_core::data::Note>| {
+        notes.into_iter().map(|note| note.name).collect::<Vec<_>>()
+    };
+    let backlinks = db
+        .fetch_backlinks(target.id.clone())
+        .await
+        .expect("backend should list backlinks");
+    assert_eq!(names(backlinks), vec!["By id", "By name"]);
+
+    // dropping the link or trashing the note removes the backlink
+    db.update_note_content(by_name.id.clone(), "no links".to_owned(), None)
+        .await
+        .expect("backend should update note content");
+    db.remove_note(by_id.id.clone())
+        .await
+        .expect("backend should remove note");
+    let backlinks = db
+        .fetch_backlinks(target.id.clone())
+        .await
+        .expect("backend should list backlinks");
+    assert!(backlinks.is_empty());
+}
@@ -0,0 +1,64 @@
+use {
+    chrono::NaiveDate,
+    glues_core::{
+        CoreBackend,
+        backend::local::Db,
+        journal::{self, JournalDay},
+    },
+};
+
+fn day(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").expect("date should parse")
+}
+
+#[tokio::test]
+async fn journal_note_is_found_or_created() {
+    let mut db = Db::memory()
+        .await
+        .expect("in-memory backend should initialize");
+
+    let note = journal::find_or_create(&mut db, day("2024-05-01"))
+        .await
+        .expect("journal note should be created");
+    assert_eq!(note.name, "2024-05-01");
+    assert_eq!(
+        journal::note_path(day("2024-05-01")),
+        "Journal/2024/05/2024-05-01"
+    );
+
+    let month = db
+        .fetch_directory(note.directory_id.clone())
+        .await
+        .expect("backend should fetch month directory");
+    assert_eq!(month.name, "05");
+
+    let again = journal::find_or_create(&mut db, day("2024-05-01"))
+        .await
+        .expect("journal note should be found");
+    assert_eq!(again.id, note.id);
+
+    // the next day shares the existing folders
+    let next = journal::find_or_create(&mut db, day("2024-05-02"))
+        .await
+        .expect("journal note should be created");
+    assert_eq!(next.directory_id, note.directory_id);
+    let journals = db
+        .fetch_directories(db.root_id())
+        .await
+        .expect("backend should list directories");
+    assert_eq!(journals.len(), 1);
+    assert_eq!(journal::date_of(&next), Some(day("2024-05-02")));
+}
+
+#[test]
+fn journal_day_navigation() {
+    let current = Some(day("2024-03-01"));
+
+    assert_eq!(JournalDay::Previous.date(current), day("2024-02-29"));
+    assert_eq!(JournalDay::Next.date(current), day("2024-03-02"));
+    assert_eq!(JournalDay::Today.date(current), journal::today());
+    assert_eq!(
+        JournalDay::Next.date(None),
+        journal::today().succ_opt().expect("tomorrow should exist")
+    );
+}
@@ -0,0 +1,24 @@
+use glues_core::link::{keys, link_at, parse_line};
+
+#[test]
+fn link_targets_and_positions() {
+    let links = parse_line("see [[Daily|today]] and [[0190-ab#Plan]] ✓ [[ ]]");
+    let targets = links
+        .iter()
+        .map(|link| (link.target.as_str(), link.start, link.end))
+        .collect::<Vec<_>>();
+
+    assert_eq!(targets, vec![("Daily", 4, 19), ("0190-ab", 24, 40)]);
+    assert_eq!(
+        link_at("é [[Ideas]]", 2).map(|link| link.target),
+        Some("Ideas".to_owned())
+    );
+    assert_eq!(link_at("é [[Ideas]]", 1), None);
+}
+
+#[test]
+fn link_keys_skip_code_blocks_and_duplicates() {
+    let content = "[[Ideas]]\n```\n[[Hidden]]\n```\n| [[ideas\\|alias]] |";
+
+    assert_eq!(keys(content), vec!["ideas".to_owned()]);
+}
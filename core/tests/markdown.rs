@@ -0,0 +1,228 @@
+use {
+    glues_core::{
+        CoreBackend, Error,
+        backend::local::Db,
+        export::{ExportOptions, ExportSummary, export_markdown},
+        import::{ImportSummary, import_markdown, import_obsidian},
+    },
+    std::fs,
+};
+
+#[tokio::test]
+async fn export_writes_markdown_tree() {
+    let mut db = Db::memory().await.expect("memory backend should open");
+    let root_id = db.root_id();
+
+    let work = db
+        .add_directory(root_id.clone(), "Work".to_owned())
+        .await
+        .expect("directory should be added");
+    let plans = db
+        .add_directory(work.id.clone(), "Plans".to_owned())
+        .await
+        .expect("nested directory should be added");
+    let todo = db
+        .add_note(work.id.clone(), "To/do".to_owned())
+        .await
+        .expect("note should be added");
+    db.update_note_content(todo.id.clone(), "- [ ] ship".to_owned(), None)
+        .await
+        .expect("content should be updated");
+    db.add_note_tag(todo.id.clone(), "urgent".to_owned())
+        .await
+        .expect("tag should be added");
+    db.add_note(plans.id.clone(), "Q1".to_owned())
+        .await
+        .expect("nested note should be added");
+    db.add_note(root_id.clone(), "Inbox".to_owned())
+        .await
+        .expect("root note should be added");
+    db.add_note(root_id.clone(), "Inbox".to_owned())
+        .await
+        .expect("duplicate note should be added");
+
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let dest = dir.path().join("backup");
+    let summary = export_markdown(&mut db, &dest, ExportOptions::default())
+        .await
+        .expect("export should succeed");
+    assert_eq!(
+        summary,
+        ExportSummary {
+            directories: 2,
+            notes: 4,
+        }
+    );
+
+    assert!(dest.join("Inbox.md").is_file());
+    assert!(dest.join("Inbox (2).md").is_file());
+    assert!(dest.join("Work/Plans/Q1.md").is_file());
+    assert_eq!(
+        fs::read_to_string(dest.join("Work/To_do.md")).expect("note file should exist"),
+        "- [ ] ship"
+    );
+
+    let err = export_markdown(&mut db, &dest, ExportOptions::default())
+        .await
+        .expect_err("non-empty destination should be rejected");
+    assert!(matches!(err, Error::InvalidState(_)));
+
+    let dest = dir.path().join("with-front-matter");
+    export_markdown(&mut db, &dest, ExportOptions { front_matter: true })
+        .await
+        .expect("export with front matter should succeed");
+    let todo = db
+        .fetch_notes(work.id.clone())
+        .await
+        .expect("notes should be listed")
+        .into_iter()
+        .next()
+        .expect("note should exist");
+    assert_eq!(
+        fs::read_to_string(dest.join("Work/To_do.md")).expect("note file should exist"),
+        format!(
+            "---\nid: {}\ncreated_at: {}\nupdated_at: {}\ntags: [\"urgent\"]\n---\n\n- [ ] ship",
+            todo.id, todo.created_at, todo.updated_at
+        )
+    );
+}
+
+#[tokio::test]
+async fn import_reads_exported_tree() {
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let src = dir.path();
+    fs::create_dir_all(src.join("Work/Plans")).expect("folders should be created");
+    fs::create_dir_all(src.join(".git")).expect("hidden folder should be created");
+    fs::write(src.join("Inbox.md"), "# Inbox").expect("file should be written");
+    fs::write(src.join("Work/todo.txt"), "ship it").expect("file should be written");
+    fs::write(src.join("Work/Plans/Q1.markdown"), "").expect("file should be written");
+    fs::write(src.join("Work/logo.png"), [0x89, 0x50]).expect("file should be written");
+    fs::write(src.join(".git/HEAD"), "ref").expect("file should be written");
+
+    let mut db = Db::memory().await.expect("memory backend should open");
+    let root_id = db.root_id();
+    let summary = import_markdown(&mut db, src, root_id.clone())
+        .await
+        .expect("import should succeed");
+    assert_eq!(
+        summary,
+        ImportSummary {
+            directories: 2,
+            notes: 3,
+            skipped: 1,
+            links: 0,
+        }
+    );
+
+    let notes = db
+        .fetch_notes(root_id.clone())
+        .await
+        .expect("root notes should be listed");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].name, "Inbox");
+    assert_eq!(
+        db.fetch_note_content(notes[0].id.clone())
+            .await
+            .expect("content should be fetched"),
+        "# Inbox"
+    );
+
+    let directories = db
+        .fetch_directories(root_id)
+        .await
+        .expect("directories should be listed");
+    assert_eq!(directories.len(), 1);
+    assert_eq!(directories[0].name, "Work");
+    let notes = db
+        .fetch_notes(directories[0].id.clone())
+        .await
+        .expect("notes should be listed");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].name, "todo");
+
+    let export = dir.path().join("export");
+    let exported = export_markdown(&mut db, &export, ExportOptions::default())
+        .await
+        .expect("export should succeed");
+    let mut copy = Db::memory().await.expect("memory backend should open");
+    let copy_root = copy.root_id();
+    let imported = import_markdown(&mut copy, &export, copy_root)
+        .await
+        .expect("exported folder should import");
+    assert_eq!(imported.directories, exported.directories);
+    assert_eq!(imported.notes, exported.notes);
+}
+
+#[tokio::test]
+async fn obsidian_import_rewrites_wiki_links() {
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let vault = dir.path();
+    fs::create_dir_all(vault.join(".obsidian")).expect("config folder should be created");
+    fs::create_dir_all(vault.join("Projects")).expect("folder should be created");
+    fs::write(vault.join(".obsidian/app.json"), "{}").expect("file should be written");
+    fs::write(vault.join("diagram.png"), [0x89, 0x50]).expect("file should be written");
+    fs::write(vault.join("Roadmap.md"), "# Roadmap").expect("file should be written");
+    fs::write(vault.join("Projects/Roadmap.md"), "nested").expect("file should be written");
+    fs::write(
+        vault.join("Projects/Glues.md"),
+        "See [[Roadmap]], [[Projects/Roadmap#Q1|the plan]] and [[Missing]].\n\
+         | a | [[roadmap\\|alias]] |\n\
+         ![[diagram.png]]\n\
+         ```\n[[Roadmap]]\n```\n",
+    )
+    .expect("file should be written");
+
+    let mut db = Db::memory().await.expect("memory backend should open");
+    let root_id = db.root_id();
+    let summary = import_obsidian(&mut db, vault, root_id.clone())
+        .await
+        .expect("vault should import");
+    assert_eq!(
+        summary,
+        ImportSummary {
+            directories: 1,
+            notes: 3,
+            skipped: 1,
+            links: 3,
+        }
+    );
+
+    let roadmap = db
+        .fetch_notes(root_id.clone())
+        .await
+        .expect("root notes should be listed")
+        .remove(0);
+    let projects = db
+        .fetch_directories(root_id)
+        .await
+        .expect("directories should be listed")
+        .remove(0);
+    let notes = db
+        .fetch_notes(projects.id)
+        .await
+        .expect("notes should be listed");
+    let glues = notes
+        .iter()
+        .find(|note| note.name == "Glues")
+        .expect("linking note should be imported");
+    let nested = notes
+        .iter()
+        .find(|note| note.name == "Roadmap")
+        .expect("nested note should be imported");
+
+    let content = db
+        .fetch_note_content(glues.id.clone())
+        .await
+        .expect("content should be fetched");
+    assert_eq!(
+        content,
+        format!(
+            "See [[{root}|Roadmap]], [[{nested}#Q1|the plan]] and [[Missing]].\n\
+             | a | [[{root}\\|alias]] |\n\
+             ![[diagram.png]]\n\
+             ```\n[[Roadmap]]\n```\n",
+            root = roadmap.id,
+            nested = nested.id,
+        )
+    );
+}
@@ -0,0 +1,303 @@
+use {
+    glues_core::{
+        Error,
+        backend::{CoreBackend, markdown::MarkdownBackend},
+    },
+    std::fs,
+};
+
+async fn open(dir: &tempfile::TempDir) -> MarkdownBackend {
+    let path = dir.path().to_str().expect("temp path should be utf-8");
+
+    MarkdownBackend::open(path)
+        .await
+        .expect("markdown backend should open")
+}
+
+#[tokio::test]
+async fn markdown_backend_operations() {
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let mut backend = open(&dir).await;
+    let root_id = backend.root_id();
+
+    let root = backend
+        .fetch_directory(root_id.clone())
+        .await
+        .expect("backend should fetch root directory");
+    assert_eq!(root.name, "Notes");
+
+    let work = backend
+        .add_directory(root_id.clone(), "Work".to_owned())
+        .await
+        .expect("backend should add directory");
+    let note = backend
+        .add_note(work.id.clone(), "Todo".to_owned())
+        .await
+        .expect("backend should add note");
+    backend
+        .update_note_content(note.id.clone(), "- [ ] ship".to_owned(), None)
+        .await
+        .expect("backend should update note content");
+
+    let text = fs::read_to_string(dir.path().join("Work/Todo.md")).expect("note file should exist");
+    assert!(text.starts_with(&format!("---\nid: {}\n", note.id)));
+    assert!(text.ends_with("---\n\n- [ ] ship"));
+    assert_eq!(
+        backend
+            .fetch_note_content(note.id.clone())
+            .await
+            .expect("backend should fetch note content"),
+        "- [ ] ship"
+    );
+
+    let renamed = backend
+        .rename_note(note.id.clone(), "Done".to_owned(), None)
+        .await
+        .expect("backend should rename note");
+    assert_eq!(renamed.name, "Done");
+    assert!(dir.path().join("Work/Done.md").is_file());
+
+    let archive = backend
+        .add_directory(root_id.clone(), "Archive".to_owned())
+        .await
+        .expect("backend should add directory");
+    let moved = backend
+        .move_note(note.id.clone(), archive.id.clone(), None)
+        .await
+        .expect("backend should move note");
+    assert_eq!(moved.directory_id, archive.id);
+    assert!(dir.path().join("Archive/Done.md").is_file());
+
+    backend
+        .move_directory(archive.id.clone(), work.id.clone())
+        .await
+        .expect("backend should move directory");
+    let moved = backend
+        .fetch_directory(archive.id.clone())
+        .await
+        .expect("backend should fetch moved directory");
+    assert_eq!(moved.parent_id, work.id);
+    assert!(dir.path().join("Work/Archive/Done.md").is_file());
+
+    let err = backend
+        .move_directory(work.id.clone(), archive.id.clone())
+        .await
+        .expect_err("directory should not move into itself");
+    assert!(matches!(err, Error::InvalidState(_)));
+}
+
+#[tokio::test]
+async fn markdown_backend_picks_up_external_files() {
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let mut backend = open(&dir).await;
+    let root_id = backend.root_id();
+
+    fs::create_dir(dir.path().join("Ideas")).expect("folder should be created");
+    fs::write(dir.path().join("Ideas/Plain.md"), "written elsewhere")
+        .expect("note file should be written");
+
+    let ideas = backend
+        .fetch_directories(root_id.clone())
+        .await
+        .expect("backend should list directories");
+    assert_eq!(ideas.len(), 1);
+    assert_eq!(ideas[0].name, "Ideas");
+
+    let notes = backend
+        .fetch_notes(ideas[0].id.clone())
+        .await
+        .expect("backend should list notes");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].name, "Plain");
+    assert_eq!(
+        backend
+            .fetch_note_content(notes[0].id.clone())
+            .await
+            .expect("backend should fetch note content"),
+        "written elsewhere"
+    );
+
+    // Reopening keeps the assigned ids.
+    let mut backend = open(&dir).await;
+    let reopened = backend
+        .fetch_notes(ideas[0].id.clone())
+        .await
+        .expect("backend should list notes after reopening");
+    assert_eq!(reopened[0].id, notes[0].id);
+}
+
+#[tokio::test]
+async fn markdown_backend_tags_and_search() {
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let mut backend = open(&dir).await;
+    let root_id = backend.root_id();
+
+    let note = backend
+        .add_note(root_id.clone(), "Groceries".to_owned())
+        .await
+        .expect("backend should add note");
+    backend
+        .update_note_content(note.id.clone(), "milk and bread".to_owned(), None)
+        .await
+        .expect("backend should update note content");
+    backend
+        .add_note_tag(note.id.clone(), " home ".to_owned())
+        .await
+        .expect("backend should add tag");
+    let tagged = backend
+        .fetch_note(note.id.clone())
+        .await
+        .expect("backend should fetch note");
+    assert_eq!(tagged.tags, vec!["home".to_owned()]);
+
+    let tags = backend
+        .fetch_tags()
+        .await
+        .expect("backend should list tags");
+    assert_eq!(tags.len(), 1);
+    let notes = backend
+        .fetch_notes_by_tag("home".to_owned())
+        .await
+        .expect("backend should list notes by tag");
+    assert_eq!(notes.len(), 1);
+
+    let results = backend
+        .search_notes("BREAD".to_owned())
+        .await
+        .expect("backend should search notes");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].note.id, note.id);
+
+    backend
+        .remove_note_tag(note.id.clone(), "home".to_owned())
+        .await
+        .expect("backend should remove tag");
+    let untagged = backend
+        .fetch_note(note.id.clone())
+        .await
+        .expect("backend should fetch note");
+    assert!(untagged.tags.is_empty());
+}
+
+#[tokio::test]
+async fn markdown_backend_trash() {
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let mut backend = open(&dir).await;
+    let root_id = backend.root_id();
+
+    let work = backend
+        .add_directory(root_id.clone(), "Work".to_owned())
+        .await
+        .expect("backend should add directory");
+    let note = backend
+        .add_note(work.id.clone(), "Todo".to_owned())
+        .await
+        .expect("backend should add note");
+
+    backend
+        .remove_note(note.id.clone())
+        .await
+        .expect("backend should remove note");
+    assert!(!dir.path().join("Work/Todo.md").exists());
+    backend
+        .remove_directory(work.id.clone())
+        .await
+        .expect("backend should remove directory");
+    assert!(!dir.path().join("Work").exists());
+
+    let trash = backend
+        .fetch_trash()
+        .await
+        .expect("backend should list trash");
+    assert_eq!(trash.directories.len(), 1);
+    assert_eq!(trash.notes.len(), 1);
+
+    // The note's folder is trashed too, so it comes back to the root.
+    let restored = backend
+        .restore_note(note.id.clone())
+        .await
+        .expect("backend should restore note");
+    assert_eq!(restored.directory_id, root_id);
+    assert!(dir.path().join("Todo.md").is_file());
+
+    backend.purge().await.expect("backend should purge trash");
+    let trash = backend
+        .fetch_trash()
+        .await
+        .expect("backend should list trash");
+    assert!(trash.directories.is_empty());
+    assert!(trash.notes.is_empty());
+    let err = backend
+        .fetch_directory(work.id.clone())
+        .await
+        .expect_err("purged directory should be gone");
+    assert!(matches!(err, Error::NotFound(_)));
+}
+
+#[tokio::test]
+async fn markdown_backend_revisions_and_conflicts() {
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let mut backend = open(&dir).await;
+    let root_id = backend.root_id();
+
+    let note = backend
+        .add_note(root_id.clone(), "Draft".to_owned())
+        .await
+        .expect("backend should add note");
+    let first = backend
+        .update_note_content(note.id.clone(), "one".to_owned(), None)
+        .await
+        .expect("backend should update note content");
+    backend
+        .update_note_content(
+            note.id.clone(),
+            "two".to_owned(),
+            Some(first.updated_at.clone()),
+        )
+        .await
+        .expect("fresh update should be accepted");
+
+    let revisions = backend
+        .list_note_revisions(note.id.clone())
+        .await
+        .expect("backend should list revisions");
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(
+        backend
+            .fetch_note_revision(note.id.clone(), revisions[1].id.clone())
+            .await
+            .expect("backend should fetch revision"),
+        "one"
+    );
+
+    let err = backend
+        .update_note_content(
+            note.id.clone(),
+            "three".to_owned(),
+            Some("2000-01-01 00:00:00".to_owned()),
+        )
+        .await
+        .expect_err("stale update should be rejected");
+    assert!(matches!(err, Error::Conflict(_)));
+}
+
+#[tokio::test]
+async fn markdown_backend_backlinks() {
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let mut backend = open(&dir).await;
+    let root_id = backend.root_id();
+
+    let target = backend
+        .add_note(root_id.clone(), "Target".to_owned())
+        .await
+        .expect("backend should add note");
+    fs::write(dir.path().join("Linking.md"), "written by [[Target]]")
+        .expect("note file should be written");
+
+    let backlinks = backend
+        .fetch_backlinks(target.id.clone())
+        .await
+        .expect("backend should list backlinks");
+    assert_eq!(backlinks.len(), 1);
+    assert_eq!(backlinks[0].name, "Linking");
+}
@@ -0,0 +1,108 @@
+use {
+    glues_core::{CoreBackend, Error, SCHEMA_VERSION, backend::local::Db},
+    gluesql::prelude::{FileStorage, Glue, Payload, Value},
+};
+
+async fn stored_version(path: &str) -> String {
+    let mut glue = Glue::new(FileStorage::new(path).expect("file storage should open"));
+    let payloads = glue
+        .execute("SELECT value FROM Meta WHERE key = 'schema_version'")
+        .await
+        .expect("Meta should be readable");
+
+    match payloads.into_iter().next() {
+        Some(Payload::Select { mut rows, .. }) => match rows.pop().and_then(|mut row| row.pop()) {
+            Some(Value::Str(value)) => value,
+            value => panic!("unexpected schema_version value: {value:?}"),
+        },
+        payload => panic!("unexpected payload: {payload:?}"),
+    }
+}
+
+#[tokio::test]
+async fn fresh_database_is_created_at_current_version() {
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let path = dir.path().to_str().expect("temp path should be utf-8");
+
+    Db::file(path)
+        .await
+        .expect("file backend should initialize");
+    assert_eq!(stored_version(path).await, SCHEMA_VERSION.to_string());
+
+    Db::file(path)
+        .await
+        .expect("file backend should reopen at the current version");
+}
+
+#[tokio::test]
+async fn version_one_database_is_upgraded() {
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let path = dir.path().to_str().expect("temp path should be utf-8");
+
+    {
+        let mut glue = Glue::new(FileStorage::new(path).expect("file storage should open"));
+        for sql in [
+            "CREATE TABLE Meta (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+            )",
+            "CREATE TABLE Directory (
+                id UUID PRIMARY KEY DEFAULT GENERATE_UUID(),
+                parent_id UUID NULL,
+                name TEXT NOT NULL,
+                created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+                updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+            )",
+            "CREATE TABLE Note (
+                id UUID PRIMARY KEY,
+                name TEXT NOT NULL,
+                directory_id UUID NOT NULL,
+                created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+                updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+                content TEXT NOT NULL DEFAULT ''
+            )",
+            "INSERT INTO Meta (key, value) VALUES ('schema_version', '1')",
+            r#"INSERT INTO "Directory" (name) VALUES ('Notes')"#,
+        ] {
+            glue.execute(sql)
+                .await
+                .expect("version 1 schema should be created");
+        }
+    }
+
+    let mut db = Db::file(path)
+        .await
+        .expect("file backend should upgrade version 1 database");
+    assert_eq!(stored_version(path).await, SCHEMA_VERSION.to_string());
+
+    let note = db
+        .add_note(db.root_id(), "Plan".to_owned())
+        .await
+        .expect("backend should add note after upgrade");
+    db.add_note_tag(note.id, "work".to_owned())
+        .await
+        .expect("backend should tag note after upgrade");
+}
+
+#[tokio::test]
+async fn newer_database_is_refused() {
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let path = dir.path().to_str().expect("temp path should be utf-8");
+
+    Db::file(path)
+        .await
+        .expect("file backend should initialize");
+    {
+        let mut glue = Glue::new(FileStorage::new(path).expect("file storage should open"));
+        glue.execute(format!(
+            "UPDATE Meta SET value = '{}' WHERE key = 'schema_version'",
+            SCHEMA_VERSION + 1
+        ))
+        .await
+        .expect("schema_version should be updated");
+    }
+
+    let result = Db::file(path).await;
+    assert!(matches!(result, Err(Error::SchemaVersion(_))));
+}
@@ -3,10 +3,22 @@ use glues_core::{
     backend::{
         CoreBackend,
         local::Db,
-        proxy::{ProxyClient, ProxyServer, request::ProxyRequest},
+        proxy::{
+            Access, AccessScope, Capability, Change, ErrorCode, ProxyClient, ProxyError,
+            ProxyServer,
+            request::ProxyRequest,
+            response::{ProxyResponse, ResultPayload},
+        },
+    },
+};
+use std::{
+    io::ErrorKind,
+    net::TcpListener,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
     },
 };
-use std::{io::ErrorKind, net::TcpListener, sync::Arc};
 use tiny_http::{Response, Server};
 use tokio::sync::Mutex;
 
@@ -45,7 +57,7 @@ async fn proxy_backend_operations() {
                 serde_json::from_str(&body).expect("proxy request JSON should deserialize");
             let response = handle.block_on(async {
                 let mut s = srv.lock().await;
-                s.handle(proxy_req).await
+                s.handle(&AccessScope::full(), proxy_req).await
             });
             let body =
                 serde_json::to_string(&response).expect("proxy response should serialize to JSON");
@@ -94,18 +106,70 @@ async fn proxy_backend_operations() {
     assert_eq!(notes.len(), 1);
     assert_eq!(notes[0].name, "Todo");
 
-    client
-        .update_note_content(note.id.clone(), "hello".to_owned())
+    let updated = client
+        .update_note_content(
+            note.id.clone(),
+            "hello".to_owned(),
+            Some(note.updated_at.clone()),
+        )
         .await
         .expect("proxy client should update note content");
+    assert_ne!(updated.updated_at, note.updated_at);
+    let stale = client
+        .update_note_content(
+            note.id.clone(),
+            "stale".to_owned(),
+            Some(note.updated_at.clone()),
+        )
+        .await;
+    assert!(matches!(stale, Err(Error::Conflict(_))), "{stale:?}");
     let content = client
         .fetch_note_content(note.id.clone())
         .await
         .expect("proxy client should fetch note content");
     assert_eq!(content, "hello");
+    let revisions = client
+        .list_note_revisions(note.id.clone())
+        .await
+        .expect("proxy client should list revisions");
+    assert_eq!(revisions.len(), 1);
+    let revision = client
+        .fetch_note_revision(note.id.clone(), revisions[0].id.clone())
+        .await
+        .expect("proxy client should fetch revision");
+    assert_eq!(revision, "hello");
+
+    let results = client
+        .search_notes("HELL".to_owned())
+        .await
+        .expect("proxy client should search notes");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].note.id, note.id);
+    assert_eq!(results[0].snippets[0].line_number, 1);
+
+    client
+        .add_note_tag(note.id.clone(), "draft".to_owned())
+        .await
+        .expect("proxy client should tag note");
+    let tags = client
+        .fetch_tags()
+        .await
+        .expect("proxy client should list tags");
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].name, "draft");
+    let tagged = client
+        .fetch_notes_by_tag("draft".to_owned())
+        .await
+        .expect("proxy client should fetch notes by tag");
+    assert_eq!(tagged.len(), 1);
+    assert_eq!(tagged[0].tags, vec!["draft"]);
+    client
+        .remove_note_tag(note.id.clone(), "draft".to_owned())
+        .await
+        .expect("proxy client should untag note");
 
     client
-        .rename_note(note.id.clone(), "Hello".to_owned())
+        .rename_note(note.id.clone(), "Hello".to_owned(), None)
         .await
         .expect("proxy client should rename note");
     let notes = client
@@ -115,7 +179,7 @@ async fn proxy_backend_operations() {
     assert_eq!(notes[0].name, "Hello");
 
     client
-        .move_note(note.id.clone(), root_id.clone())
+        .move_note(note.id.clone(), root_id.clone(), None)
         .await
         .expect("proxy client should move note");
     let notes_root = client
@@ -144,6 +208,36 @@ async fn proxy_backend_operations() {
         .expect("proxy client should list directories after removal");
     assert!(dirs.is_empty());
 
+    let trash = client
+        .fetch_trash()
+        .await
+        .expect("proxy client should list trash");
+    assert_eq!(trash.directories.len(), 1);
+    assert_eq!(trash.notes.len(), 1);
+    let restored = client
+        .restore_note(note.id.clone())
+        .await
+        .expect("proxy client should restore note");
+    assert_eq!(restored.directory_id, root_id);
+    let restored = client
+        .restore_directory(dir.id.clone())
+        .await
+        .expect("proxy client should restore directory");
+    assert_eq!(restored.id, dir.id);
+    client
+        .remove_directory(dir.id.clone())
+        .await
+        .expect("proxy client should remove directory again");
+    client
+        .purge()
+        .await
+        .expect("proxy client should purge trash");
+    let trash = client
+        .fetch_trash()
+        .await
+        .expect("proxy client should list trash after purge");
+    assert!(trash.directories.is_empty());
+
     client
         .log("test".to_owned(), "message".to_owned())
         .await
@@ -203,7 +297,7 @@ async fn proxy_backend_requires_token() {
                 serde_json::from_str(&body).expect("proxy request JSON should deserialize");
             let response = handle.block_on(async {
                 let mut s = srv.lock().await;
-                s.handle(proxy_req).await
+                s.handle(&AccessScope::full(), proxy_req).await
             });
             let body =
                 serde_json::to_string(&response).expect("proxy response should serialize to JSON");
@@ -220,7 +314,7 @@ async fn proxy_backend_requires_token() {
         Err(err) => err,
     };
     match err {
-        Error::Proxy(message) => {
+        Error::Unauthorized(message) => {
             assert!(
                 message.contains("authentication token"),
                 "unexpected error message: {message}"
@@ -245,3 +339,472 @@ async fn proxy_backend_requires_token() {
         .join()
         .expect("proxy server thread should finish cleanly");
 }
+
+#[tokio::test]
+async fn proxy_server_enforces_access_scope() {
+    let mut db = Db::memory()
+        .await
+        .expect("in-memory proxy database should initialize");
+    let root_id = db.root_id();
+    let work = db
+        .add_directory(root_id.clone(), "Work".to_owned())
+        .await
+        .expect("backend should add directory");
+    let project = db
+        .add_directory(work.id.clone(), "Project".to_owned())
+        .await
+        .expect("backend should add directory");
+    let private = db
+        .add_directory(root_id.clone(), "Private".to_owned())
+        .await
+        .expect("backend should add directory");
+    let plan = db
+        .add_note(project.id.clone(), "Plan".to_owned())
+        .await
+        .expect("backend should add note");
+    let diary = db
+        .add_note(private.id.clone(), "Diary".to_owned())
+        .await
+        .expect("backend should add note");
+    db.add_note(work.id.clone(), "Budget".to_owned())
+        .await
+        .expect("backend should add note");
+    let mut server = ProxyServer::new(Box::new(db));
+
+    let read_only = AccessScope {
+        access: Access::ReadOnly,
+        directories: vec![project.id.clone()],
+    };
+
+    // ancestors of the scoped directory are listed, but only along the path
+    match server
+        .handle(
+            &read_only,
+            ProxyRequest::FetchDirectories {
+                parent_id: root_id.clone(),
+            },
+        )
+        .await
+    {
+        ProxyResponse::Ok(ResultPayload::Directories(directories)) => {
+            let names = directories
+                .iter()
+                .map(|d| d.name.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(names, ["Work"]);
+        }
+        response => panic!("unexpected response: {response:?}"),
+    }
+    match server
+        .handle(
+            &read_only,
+            ProxyRequest::FetchNotes {
+                directory_id: work.id.clone(),
+            },
+        )
+        .await
+    {
+        ProxyResponse::Ok(ResultPayload::Notes(notes)) => assert!(notes.is_empty()),
+        response => panic!("unexpected response: {response:?}"),
+    }
+    match server
+        .handle(
+            &read_only,
+            ProxyRequest::FetchNotes {
+                directory_id: project.id.clone(),
+            },
+        )
+        .await
+    {
+        ProxyResponse::Ok(ResultPayload::Notes(notes)) => assert_eq!(notes.len(), 1),
+        response => panic!("unexpected response: {response:?}"),
+    }
+
+    let denied = [
+        ProxyRequest::FetchNotes {
+            directory_id: private.id.clone(),
+        },
+        ProxyRequest::FetchNoteContent {
+            note_id: diary.id.clone(),
+        },
+        ProxyRequest::UpdateNoteContent {
+            note_id: plan.id.clone(),
+            content: "changed".to_owned(),
+            expected_updated_at: None,
+        },
+    ];
+    for request in denied {
+        assert!(matches!(
+            server.handle(&read_only, request).await,
+            ProxyResponse::Err(error) if error.code == ErrorCode::Forbidden
+        ));
+    }
+
+    match server
+        .handle(
+            &read_only,
+            ProxyRequest::SearchNotes {
+                query: "a".to_owned(),
+            },
+        )
+        .await
+    {
+        ProxyResponse::Ok(ResultPayload::SearchResults(results)) => {
+            let names = results
+                .iter()
+                .map(|result| result.note.name.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(names, ["Plan"]);
+        }
+        response => panic!("unexpected response: {response:?}"),
+    }
+
+    let read_write = AccessScope {
+        access: Access::ReadWrite,
+        ..read_only
+    };
+    assert!(matches!(
+        server
+            .handle(
+                &read_write,
+                ProxyRequest::UpdateNoteContent {
+                    note_id: plan.id.clone(),
+                    content: "changed".to_owned(),
+                    expected_updated_at: None,
+                },
+            )
+            .await,
+        ProxyResponse::Ok(ResultPayload::Note(_))
+    ));
+    assert!(matches!(
+        server
+            .handle(
+                &read_write,
+                ProxyRequest::MoveNote {
+                    note_id: plan.id.clone(),
+                    directory_id: private.id.clone(),
+                    expected_updated_at: None,
+                },
+            )
+            .await,
+        ProxyResponse::Err(_)
+    ));
+    assert!(matches!(
+        server.handle(&read_write, ProxyRequest::Purge).await,
+        ProxyResponse::Err(_)
+    ));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn proxy_client_batches_tree_loading() {
+    let mut db = Db::memory()
+        .await
+        .expect("in-memory proxy database should initialize");
+    let root_id = db.root_id();
+    let work = db
+        .add_directory(root_id.clone(), "Work".to_owned())
+        .await
+        .expect("backend should add directory");
+    db.add_note(root_id.clone(), "Inbox".to_owned())
+        .await
+        .expect("backend should add note");
+    let server = Arc::new(Mutex::new(ProxyServer::new(Box::new(db))));
+
+    let listener = match TcpListener::bind("127.0.0.1:0") {
+        Ok(listener) => listener,
+        Err(err) if err.kind() == ErrorKind::PermissionDenied => {
+            eprintln!("skipping proxy_client_batches_tree_loading: {err}");
+            return;
+        }
+        Err(err) => panic!("proxy server should bind to ephemeral port: {err}"),
+    };
+    let addr = listener
+        .local_addr()
+        .expect("proxy server should expose a local address");
+    let http = Arc::new(
+        Server::from_listener(listener, None).expect("proxy server should accept HTTP connections"),
+    );
+    let requests = Arc::new(AtomicUsize::new(0));
+    let handle = tokio::runtime::Handle::current();
+    let srv = server.clone();
+    let http_clone = http.clone();
+    let counter = requests.clone();
+    let server_thread = std::thread::spawn(move || {
+        for mut req in http_clone.incoming_requests() {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let mut body = String::new();
+            req.as_reader()
+                .read_to_string(&mut body)
+                .expect("proxy request body should read into string");
+            let proxy_req: ProxyRequest =
+                serde_json::from_str(&body).expect("proxy request JSON should deserialize");
+            let response = handle.block_on(async {
+                let mut s = srv.lock().await;
+                s.handle(&AccessScope::full(), proxy_req).await
+            });
+            let body =
+                serde_json::to_string(&response).expect("proxy response should serialize to JSON");
+            let _ = req.respond(Response::from_string(body));
+        }
+    });
+
+    let mut client = ProxyClient::connect(format!("http://{addr}"), None)
+        .await
+        .expect("proxy client should connect to server");
+    requests.store(0, Ordering::SeqCst);
+
+    // opening a directory asks for its notes and sub-directories in one go
+    let notes = client
+        .fetch_notes(root_id.clone())
+        .await
+        .expect("proxy client should list notes");
+    let directories = client
+        .fetch_directories(root_id.clone())
+        .await
+        .expect("proxy client should list directories");
+    let directory = client
+        .fetch_directory(work.id.clone())
+        .await
+        .expect("proxy client should fetch directory");
+    assert_eq!(notes[0].name, "Inbox");
+    assert_eq!(directories[0].name, "Work");
+    assert_eq!(directory.name, "Work");
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    // changes drop what was fetched ahead
+    client
+        .rename_directory(work.id.clone(), "Projects".to_owned())
+        .await
+        .expect("proxy client should rename directory");
+    let directory = client
+        .fetch_directory(work.id.clone())
+        .await
+        .expect("proxy client should fetch directory");
+    assert_eq!(directory.name, "Projects");
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+    let responses = client
+        .batch(vec![
+            ProxyRequest::RootId,
+            ProxyRequest::Batch(Vec::new()),
+            ProxyRequest::FetchNoteContent {
+                note_id: "00000000-0000-7000-8000-000000000000".to_owned(),
+            },
+        ])
+        .await
+        .expect("proxy client should send a batch");
+    assert!(matches!(
+        responses.as_slice(),
+        [
+            ProxyResponse::Ok(ResultPayload::Id(id)),
+            ProxyResponse::Err(ProxyError { code: ErrorCode::InvalidState, .. }),
+            ProxyResponse::Err(ProxyError { code: ErrorCode::NotFound, .. }),
+        ] if *id == root_id
+    ));
+    assert!(matches!(
+        client
+            .fetch_note_content("00000000-0000-7000-8000-000000000000".to_owned())
+            .await,
+        Err(Error::NotFound(_))
+    ));
+
+    drop(client);
+    http.unblock();
+    server_thread
+        .join()
+        .expect("proxy server thread should shut down cleanly");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn proxy_client_degrades_on_legacy_server() {
+    let mut db = Db::memory()
+        .await
+        .expect("in-memory proxy database should initialize");
+    let root_id = db.root_id();
+    db.add_note(root_id.clone(), "Inbox".to_owned())
+        .await
+        .expect("backend should add note");
+    let mut server = ProxyServer::new(Box::new(db));
+
+    match server
+        .handle(
+            &AccessScope::full(),
+            ProxyRequest::Hello {
+                version: 0,
+                capabilities: Vec::new(),
+            },
+        )
+        .await
+    {
+        ProxyResponse::Err(error) => {
+            assert_eq!(error.code, ErrorCode::Unsupported);
+            assert!(error.message.contains("too old"));
+        }
+        response => panic!("unexpected response: {response:?}"),
+    }
+
+    let server = Arc::new(Mutex::new(server));
+    let listener = match TcpListener::bind("127.0.0.1:0") {
+        Ok(listener) => listener,
+        Err(err) if err.kind() == ErrorKind::PermissionDenied => {
+            eprintln!("skipping proxy_client_degrades_on_legacy_server: {err}");
+            return;
+        }
+        Err(err) => panic!("proxy server should bind to ephemeral port: {err}"),
+    };
+    let addr = listener
+        .local_addr()
+        .expect("proxy server should expose a local address");
+    let http = Arc::new(
+        Server::from_listener(listener, None).expect("proxy server should accept HTTP connections"),
+    );
+    let handle = tokio::runtime::Handle::current();
+    let srv = server.clone();
+    let http_clone = http.clone();
+    let server_thread = std::thread::spawn(move || {
+        for mut req in http_clone.incoming_requests() {
+            let mut body = String::new();
+            req.as_reader()
+                .read_to_string(&mut body)
+                .expect("proxy request body should read into string");
+            // a server from before the handshake knows neither `Hello` nor `Batch`
+            let json: serde_json::Value =
+                serde_json::from_str(&body).expect("proxy request should be JSON");
+            if matches!(json["method"].as_str(), Some("Hello" | "Batch")) {
+                let _ = req.respond(Response::from_string("unknown variant").with_status_code(422));
+                continue;
+            }
+
+            let proxy_req: ProxyRequest =
+                serde_json::from_value(json).expect("proxy request JSON should deserialize");
+            let response = handle.block_on(async {
+                let mut s = srv.lock().await;
+                s.handle(&AccessScope::full(), proxy_req).await
+            });
+            let body =
+                serde_json::to_string(&response).expect("proxy response should serialize to JSON");
+            let _ = req.respond(Response::from_string(body));
+        }
+    });
+
+    let mut client = ProxyClient::connect(format!("http://{addr}"), None)
+        .await
+        .expect("proxy client should connect to a legacy server");
+    assert_eq!(client.server().version, 0);
+    assert!(!client.server().supports(Capability::Batch));
+    assert_eq!(client.root_id(), root_id);
+
+    let notes = client
+        .fetch_notes(root_id.clone())
+        .await
+        .expect("proxy client should list notes without batches");
+    assert_eq!(notes[0].name, "Inbox");
+    assert!(matches!(
+        client.search_notes("Inbox".to_owned()).await,
+        Err(Error::Unsupported(_))
+    ));
+
+    drop(client);
+    http.unblock();
+    server_thread
+        .join()
+        .expect("proxy server thread should shut down cleanly");
+}
+
+#[tokio::test]
+async fn proxy_server_broadcasts_changes() {
+    let mut db = Db::memory()
+        .await
+        .expect("in-memory proxy database should initialize");
+    let root_id = db.root_id();
+    let work = db
+        .add_directory(root_id.clone(), "Work".to_owned())
+        .await
+        .expect("backend should add directory");
+    let private = db
+        .add_directory(root_id.clone(), "Private".to_owned())
+        .await
+        .expect("backend should add directory");
+    let mut server = ProxyServer::new(Box::new(db));
+    let mut changes = server.subscribe();
+
+    let note = match server
+        .handle(
+            &AccessScope::full(),
+            ProxyRequest::AddNote {
+                directory_id: work.id.clone(),
+                name: "Plan".to_owned(),
+            },
+        )
+        .await
+    {
+        ProxyResponse::Ok(ResultPayload::Note(note)) => note,
+        response => panic!("unexpected response: {response:?}"),
+    };
+    server
+        .handle(
+            &AccessScope::full(),
+            ProxyRequest::Batch(vec![
+                ProxyRequest::RenameNote {
+                    note_id: note.id.clone(),
+                    name: "Roadmap".to_owned(),
+                    expected_updated_at: None,
+                },
+                ProxyRequest::FetchNoteContent {
+                    note_id: note.id.clone(),
+                },
+            ]),
+        )
+        .await;
+    // rejected requests change nothing
+    let read_only = AccessScope {
+        access: Access::ReadOnly,
+        directories: Vec::new(),
+    };
+    server
+        .handle(
+            &read_only,
+            ProxyRequest::RenameDirectory {
+                directory_id: work.id.clone(),
+                name: "Gone".to_owned(),
+            },
+        )
+        .await;
+
+    let added = changes.try_recv().expect("note creation should broadcast");
+    assert_eq!(
+        added,
+        Change::NoteAdded {
+            note_id: note.id.clone(),
+            directory_id: work.id.clone(),
+        }
+    );
+    let renamed = changes.try_recv().expect("rename should broadcast");
+    assert_eq!(
+        renamed,
+        Change::NoteRenamed {
+            note_id: note.id.clone(),
+            name: "Roadmap".to_owned(),
+        }
+    );
+    assert!(changes.try_recv().is_err());
+
+    let scoped = AccessScope {
+        access: Access::ReadOnly,
+        directories: vec![private.id.clone()],
+    };
+    assert!(server.can_see(&AccessScope::full(), &renamed).await);
+    assert!(!server.can_see(&scoped, &renamed).await);
+    assert!(!server.can_see(&scoped, &Change::TrashEmptied).await);
+    assert!(
+        server
+            .can_see(
+                &scoped,
+                &Change::DirectoryRenamed {
+                    directory_id: private.id.clone(),
+                    name: "Secret".to_owned(),
+                },
+            )
+            .await
+    );
+}
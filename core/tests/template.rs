@@ -0,0 +1,18 @@
+use glues_core::template::{Placeholders, expand};
+
+#[test]
+fn expand_placeholders() {
+    let placeholders = Placeholders {
+        title: "Weekly sync",
+        directory: "Meetings",
+        date: "2024-05-01",
+    };
+
+    assert_eq!(
+        expand(
+            "# {{title}}\n{{ date }} in {{directory}}\n{{unknown}} {{open",
+            &placeholders
+        ),
+        "# Weekly sync\n2024-05-01 in Meetings\n{{unknown}} {{open"
+    );
+}
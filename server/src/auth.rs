@@ -0,0 +1,286 @@
+use {
+    chrono::{DateTime, Duration, Utc},
+    color_eyre::{
+        Result,
+        eyre::{WrapErr, bail},
+    },
+    glues_core::backend::proxy::AccessScope,
+    rand::RngCore,
+    serde::{Deserialize, Serialize},
+    sha2::{Digest, Sha256},
+    std::{
+        fs,
+        io::ErrorKind,
+        path::{Path, PathBuf},
+        sync::Mutex,
+        time::SystemTime,
+    },
+    tracing::warn,
+};
+
+/// How stale a token's `last_used_at` may get before it is written back.
+const LAST_USED_INTERVAL: Duration = Duration::minutes(1);
+
+/// Credentials accepted by the server.
+#[derive(Default)]
+pub struct Auth {
+    /// Global token from `--auth-token`, granting full access.
+    token: Option<String>,
+    tokens: Option<Mutex<TokenFile>>,
+}
+
+impl Auth {
+    pub fn new(token: Option<String>, tokens: Option<TokenFile>) -> Self {
+        Self {
+            token,
+            tokens: tokens.map(Mutex::new),
+        }
+    }
+
+    /// Whether requests must present a bearer token.
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_some() || self.tokens.is_some()
+    }
+
+    /// Scope granted to `token`, or `None` when the token is unknown.
+    pub fn resolve(&self, token: &str) -> Option<AccessScope> {
+        if self.token.as_deref() == Some(token) {
+            return Some(AccessScope::full());
+        }
+
+        let mut tokens = self.tokens.as_ref()?.lock().ok()?;
+        tokens.resolve(token)
+    }
+}
+
+/// The token file given with `--tokens`, reloaded whenever it changes on
+/// disk so tokens created or revoked from the CLI apply without a restart.
+pub struct TokenFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    registry: TokenRegistry,
+}
+
+impl TokenFile {
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let modified = modified(&path);
+        let registry = TokenRegistry::load(&path)?;
+
+        Ok(Self {
+            path,
+            modified,
+            registry,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.registry.tokens.len()
+    }
+
+    fn resolve(&mut self, token: &str) -> Option<AccessScope> {
+        self.refresh();
+
+        let hash = hash_token(token);
+        let now = Utc::now();
+        let entry = self
+            .registry
+            .tokens
+            .iter_mut()
+            .find(|entry| entry.sha256 == hash)?;
+        let scope = entry.scope.clone();
+        if entry
+            .last_used_at
+            .is_some_and(|last_used_at| now - last_used_at < LAST_USED_INTERVAL)
+        {
+            return Some(scope);
+        }
+
+        entry.last_used_at = Some(now);
+        match self.registry.save(&self.path) {
+            Ok(()) => self.modified = modified(&self.path),
+            Err(err) => warn!("failed to record token usage: {err:#}"),
+        }
+
+        Some(scope)
+    }
+
+    fn refresh(&mut self) {
+        let modified = modified(&self.path);
+        if modified == self.modified {
+            return;
+        }
+
+        match TokenRegistry::load(&self.path) {
+            Ok(registry) => {
+                self.registry = registry;
+                self.modified = modified;
+            }
+            Err(err) => warn!("keeping previous tokens: {err:#}"),
+        }
+    }
+}
+
+/// Scoped tokens stored in the token file.
+///
+/// ```json
+/// { "tokens": [{ "label": "assistant", "sha256": "…", "access": "read-only", "directories": ["…"] }] }
+/// ```
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TokenRegistry {
+    #[serde(default)]
+    pub tokens: Vec<TokenEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TokenEntry {
+    pub label: String,
+    /// Hex-encoded SHA-256 of the token; the token itself is never stored.
+    pub sha256: String,
+    #[serde(flatten)]
+    pub scope: AccessScope,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl TokenRegistry {
+    /// Reads the token file; a missing file holds no tokens.
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err)
+                    .wrap_err_with(|| format!("failed to read token file {}", path.display()));
+            }
+        };
+
+        serde_json::from_str(&json)
+            .wrap_err_with(|| format!("invalid token file {}", path.display()))
+    }
+
+    /// Writes the registry through a temporary file so a running server
+    /// never reads a half-written file.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, json)
+            .and_then(|()| fs::rename(&tmp, path))
+            .wrap_err_with(|| format!("failed to write token file {}", path.display()))
+    }
+
+    /// Adds a token labelled `label` and returns its only plain-text copy.
+    pub fn create(&mut self, label: String, scope: AccessScope) -> Result<String> {
+        if self.tokens.iter().any(|entry| entry.label == label) {
+            bail!("a token labelled '{label}' already exists");
+        }
+
+        let mut bytes = [0; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let token = format!("glues_{}", hex::encode(bytes));
+        self.tokens.push(TokenEntry {
+            label,
+            sha256: hash_token(&token),
+            scope,
+            created_at: Some(Utc::now()),
+            last_used_at: None,
+        });
+
+        Ok(token)
+    }
+
+    pub fn revoke(&mut self, label: &str) -> Result<()> {
+        let len = self.tokens.len();
+        self.tokens.retain(|entry| entry.label != label);
+        if self.tokens.len() == len {
+            bail!("no token labelled '{label}'");
+        }
+
+        Ok(())
+    }
+}
+
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{Auth, TokenFile, TokenRegistry},
+        glues_core::backend::proxy::{Access, AccessScope},
+        std::{fs, path::PathBuf},
+    };
+
+    fn token_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("glues-server-{name}-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn resolves_global_and_scoped_tokens() {
+        let path = token_path("resolve");
+        let scope = AccessScope {
+            access: Access::ReadOnly,
+            directories: vec!["work".to_owned()],
+        };
+        let mut registry = TokenRegistry::default();
+        let token = registry
+            .create("assistant".to_owned(), scope.clone())
+            .unwrap();
+        registry.save(&path).unwrap();
+        let auth = Auth::new(
+            Some("secret".to_owned()),
+            Some(TokenFile::open(path.clone()).unwrap()),
+        );
+
+        assert!(auth.is_enabled());
+        assert_eq!(auth.resolve("secret"), Some(AccessScope::full()));
+        assert_eq!(auth.resolve(&token), Some(scope));
+        assert_eq!(auth.resolve("unknown"), None);
+        assert!(!Auth::default().is_enabled());
+
+        let registry = TokenRegistry::load(&path).unwrap();
+        assert!(!fs::read_to_string(&path).unwrap().contains(&token));
+        assert!(registry.tokens[0].last_used_at.is_some());
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn revoked_tokens_are_rejected_without_restart() {
+        let path = token_path("revoke");
+        let mut registry = TokenRegistry::default();
+        let token = registry
+            .create("ci".to_owned(), AccessScope::full())
+            .unwrap();
+        registry.save(&path).unwrap();
+        let auth = Auth::new(None, Some(TokenFile::open(path.clone()).unwrap()));
+        assert!(auth.resolve(&token).is_some());
+
+        let mut registry = TokenRegistry::load(&path).unwrap();
+        assert!(
+            registry
+                .create("ci".to_owned(), AccessScope::full())
+                .is_err()
+        );
+        registry.revoke("ci").unwrap();
+        assert!(registry.revoke("ci").is_err());
+        registry.save(&path).unwrap();
+        // make the change visible on filesystems with coarse timestamps
+        let file = fs::File::options().append(true).open(&path).unwrap();
+        file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5))
+            .unwrap();
+
+        assert_eq!(auth.resolve(&token), None);
+
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -1,27 +1,45 @@
+mod auth;
+mod mcp;
+mod openapi;
+mod rest;
+mod token;
+
 use {
+    auth::{Auth, TokenFile},
     axum::{
         Json, Router,
         body::Body,
-        extract::State,
+        extract::{Extension, State},
         http::{Method, Request, StatusCode, header::AUTHORIZATION},
         middleware::{Next, from_fn},
-        response::Response,
+        response::{
+            Response,
+            sse::{Event, KeepAlive, Sse},
+        },
         routing::{get, post},
     },
     clap::{Args, Parser, Subcommand},
     color_eyre::Result,
+    futures::stream::{self, Stream},
     glues_core::backend::{
         CoreBackend,
         local::Db,
-        proxy::{ProxyServer, request::ProxyRequest, response::ProxyResponse},
+        markdown::MarkdownBackend,
+        proxy::{AccessScope, ProxyServer, request::ProxyRequest, response::ProxyResponse},
+    },
+    std::{convert::Infallible, net::SocketAddr, path::PathBuf, sync::Arc},
+    tokio::{
+        net::TcpListener,
+        signal,
+        sync::{Mutex as AsyncMutex, broadcast::error::RecvError},
     },
-    std::{net::SocketAddr, sync::Arc},
-    tokio::{net::TcpListener, signal, sync::Mutex as AsyncMutex},
     tower_http::cors::{Any, CorsLayer},
     tracing::{error, info, warn},
     tracing_subscriber::EnvFilter,
 };
 
+pub use token::TokenCommand;
+
 #[derive(Clone, Args)]
 pub struct ServerArgs {
     #[arg(long, default_value = "127.0.0.1:4000")]
@@ -30,8 +48,16 @@ pub struct ServerArgs {
     #[arg(long, env = "GLUES_SERVER_TOKEN")]
     pub auth_token: Option<String>,
 
+    /// JSON file of scoped tokens, managed with `token create|list|revoke`
+    #[arg(long, env = "GLUES_SERVER_TOKENS")]
+    pub tokens: Option<PathBuf>,
+
+    /// Serve MCP over stdin/stdout instead of listening for HTTP requests
+    #[arg(long)]
+    pub mcp_stdio: bool,
+
     #[command(subcommand)]
-    pub storage: StorageCommand,
+    pub command: ServerCommand,
 }
 
 #[derive(Parser)]
@@ -41,12 +67,25 @@ struct Cli {
     args: ServerArgs,
 }
 
+#[derive(Subcommand, Clone)]
+pub enum ServerCommand {
+    /// Manage the scoped API tokens in the --tokens file
+    Token {
+        #[command(subcommand)]
+        command: TokenCommand,
+    },
+    #[command(flatten)]
+    Storage(StorageCommand),
+}
+
 #[derive(Subcommand, Clone)]
 pub enum StorageCommand {
     /// In-memory storage (data resets on restart)
     Memory,
     /// File storage backend rooted at the given path
     File { path: String },
+    /// Markdown files in folders, readable by other editors
+    Markdown { path: String },
     /// redb single-file storage backend
     Redb { path: String },
     /// Git storage backend
@@ -65,45 +104,77 @@ pub fn parse_args() -> ServerArgs {
 
 pub async fn run(args: ServerArgs) -> Result<()> {
     color_eyre::install()?;
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env().add_directive("info".parse()?))
-        .with_target(false)
-        .init();
 
     let ServerArgs {
         listen,
         auth_token,
-        storage,
+        tokens,
+        mcp_stdio,
+        command,
     } = args;
+    let storage = match command {
+        ServerCommand::Token { command } => return token::run(tokens, command),
+        ServerCommand::Storage(storage) => storage,
+    };
+
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env().add_directive("info".parse()?))
+        .with_target(false);
+    if mcp_stdio {
+        // stdout carries the MCP messages, so logs must go elsewhere
+        subscriber.with_writer(std::io::stderr).init();
+    } else {
+        subscriber.init();
+    }
 
     let backend = build_backend(storage).await?;
     let server = Arc::new(AsyncMutex::new(ProxyServer::new(backend)));
 
+    if mcp_stdio {
+        return mcp::serve_stdio(server).await;
+    }
+
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
         .allow_headers(Any);
 
-    let mut app = Router::new()
-        .route("/", post(handle_proxy))
-        .route("/health", get(health))
-        .with_state(server.clone())
-        .layer(cors);
-
-    if let Some(token) = auth_token.as_ref() {
+    let tokens = match tokens {
+        Some(path) => {
+            let tokens = TokenFile::open(path.clone())?;
+            info!(
+                "loaded {} scoped tokens from {}",
+                tokens.len(),
+                path.display()
+            );
+            Some(tokens)
+        }
+        None => None,
+    };
+    let auth = Arc::new(Auth::new(auth_token, tokens));
+    if auth.is_enabled() {
         info!("authentication token required for proxy requests");
-        let token = Arc::new(token.clone());
-        let auth_layer = from_fn(move |req, next| {
-            let token = Arc::clone(&token);
-            async move { enforce_bearer(token, req, next).await }
-        });
-        app = app.layer(auth_layer);
     } else if !listen.ip().is_loopback() {
         warn!(
             "proxy server is listening on {listen} without authentication; set GLUES_SERVER_TOKEN or --auth-token to protect access"
         );
     }
 
+    let auth_layer = from_fn(move |req, next| {
+        let auth = Arc::clone(&auth);
+        async move { enforce_bearer(auth, req, next).await }
+    });
+    let app = Router::new()
+        .route("/", post(handle_proxy))
+        .route("/mcp", post(mcp::handle_http))
+        .route("/events", get(events))
+        .route("/health", get(health))
+        .route("/openapi.json", get(openapi::handle))
+        .merge(rest::routes())
+        .with_state(server.clone())
+        .layer(cors)
+        .layer(auth_layer);
+
     let listener = TcpListener::bind(listen).await?;
     info!("listening on {}", listen);
 
@@ -122,6 +193,7 @@ async fn build_backend(storage: StorageCommand) -> Result<Box<dyn CoreBackend +
     let backend: Box<dyn CoreBackend + Send> = match storage {
         StorageCommand::Memory => Box::new(Db::memory().await?),
         StorageCommand::File { path } => Box::new(Db::file(&path).await?),
+        StorageCommand::Markdown { path } => Box::new(MarkdownBackend::open(&path).await?),
         StorageCommand::Redb { path } => Box::new(Db::redb(&path).await?),
         StorageCommand::Git {
             path,
@@ -138,13 +210,51 @@ async fn build_backend(storage: StorageCommand) -> Result<Box<dyn CoreBackend +
 
 async fn handle_proxy(
     State(server): State<Arc<AsyncMutex<ProxyServer>>>,
+    Extension(scope): Extension<AccessScope>,
     Json(request): Json<ProxyRequest>,
 ) -> (StatusCode, Json<ProxyResponse>) {
     let mut server = server.lock_owned().await;
-    let response = server.handle(request).await;
+    let response = server.handle(&scope, request).await;
     (StatusCode::OK, Json(response))
 }
 
+/// Streams notebook changes as server-sent events, leaving out those the
+/// token cannot see.
+async fn events(
+    State(server): State<Arc<AsyncMutex<ProxyServer>>>,
+    Extension(scope): Extension<AccessScope>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = server.lock().await.subscribe();
+
+    let stream = stream::unfold(receiver, move |mut receiver| {
+        let server = Arc::clone(&server);
+        let scope = scope.clone();
+
+        async move {
+            loop {
+                let change = match receiver.recv().await {
+                    Ok(change) => change,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("change feed subscriber skipped {skipped} changes");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return None,
+                };
+                if !server.lock().await.can_see(&scope, &change).await {
+                    continue;
+                }
+
+                let event = Event::default()
+                    .json_data(&change)
+                    .expect("change should serialize");
+                return Some((Ok(event), receiver));
+            }
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
 async fn health() -> StatusCode {
     StatusCode::OK
 }
@@ -158,14 +268,20 @@ async fn shutdown_signal() {
     info!("shutting down");
 }
 
+/// Resolves the bearer token into the [`AccessScope`] handlers run with;
+/// without any configured token every request gets full access.
 async fn enforce_bearer(
-    token: Arc<String>,
-    req: Request<Body>,
+    auth: Arc<Auth>,
+    mut req: Request<Body>,
     next: Next,
 ) -> Result<Response, StatusCode> {
     if req.method() == Method::OPTIONS {
         return Ok(next.run(req).await);
     }
+    if !auth.is_enabled() {
+        req.extensions_mut().insert(AccessScope::full());
+        return Ok(next.run(req).await);
+    }
 
     let Some(header) = req.headers().get(AUTHORIZATION) else {
         return Err(StatusCode::UNAUTHORIZED);
@@ -176,10 +292,11 @@ async fn enforce_bearer(
     let Some(provided) = value.strip_prefix("Bearer ").map(str::trim) else {
         return Err(StatusCode::UNAUTHORIZED);
     };
-    if provided != token.as_str() {
+    let Some(scope) = auth.resolve(provided) else {
         return Err(StatusCode::UNAUTHORIZED);
-    }
+    };
 
+    req.extensions_mut().insert(scope);
     Ok(next.run(req).await)
 }
 
@@ -200,13 +317,13 @@ mod tests {
             .allow_methods(Any)
             .allow_headers(Any);
 
-        let token = Arc::new("secret".to_owned());
+        let auth = Arc::new(Auth::new(Some("secret".to_owned()), None));
         let app = Router::new()
             .route("/", get(ok))
             .layer(cors)
             .layer(from_fn(move |req, next| {
-                let token = Arc::clone(&token);
-                async move { enforce_bearer(token, req, next).await }
+                let auth = Arc::clone(&auth);
+                async move { enforce_bearer(auth, req, next).await }
             }));
 
         let response = app
@@ -230,13 +347,13 @@ mod tests {
             .allow_methods(Any)
             .allow_headers(Any);
 
-        let token = Arc::new("secret".to_owned());
+        let auth = Arc::new(Auth::new(Some("secret".to_owned()), None));
         let app = Router::new()
             .route("/", get(ok))
             .layer(cors)
             .layer(from_fn(move |req, next| {
-                let token = Arc::clone(&token);
-                async move { enforce_bearer(token, req, next).await }
+                let auth = Arc::clone(&auth);
+                async move { enforce_bearer(auth, req, next).await }
             }));
 
         let response = app
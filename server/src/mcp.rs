@@ -0,0 +1,310 @@
+//! Model Context Protocol endpoint that lets local LLM clients read and edit
+//! notes. Tool calls are translated into [`ProxyRequest`]s and run through
+//! [`ProxyServer::handle`], so MCP sees exactly what proxy clients see.
+//!
+//! [`ProxyRequest`]: glues_core::backend::proxy::request::ProxyRequest
+
+mod tools;
+
+use {
+    axum::{
+        Json,
+        extract::{Extension, State},
+        http::StatusCode,
+        response::IntoResponse,
+    },
+    color_eyre::Result,
+    glues_core::backend::proxy::{AccessScope, ProxyServer},
+    serde_json::{Map, Value, json},
+    std::sync::Arc,
+    tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        sync::Mutex as AsyncMutex,
+    },
+    tracing::info,
+};
+
+/// Protocol revisions this server understands, newest first.
+const PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serves newline-delimited JSON-RPC messages on stdin/stdout until stdin is
+/// closed.
+pub async fn serve_stdio(server: Arc<AsyncMutex<ProxyServer>>) -> Result<()> {
+    info!("serving MCP over stdio");
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str(&line) {
+            Ok(message) => {
+                let mut server = server.lock().await;
+                handle_message(&mut server, &AccessScope::full(), message).await
+            }
+            Err(err) => Some(error(Value::Null, PARSE_ERROR, err.to_string())),
+        };
+        if let Some(response) = response {
+            let mut bytes = serde_json::to_vec(&response)?;
+            bytes.push(b'\n');
+            stdout.write_all(&bytes).await?;
+            stdout.flush().await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// `POST /mcp` handler for the HTTP transport; notifications get `202 Accepted`.
+pub async fn handle_http(
+    State(server): State<Arc<AsyncMutex<ProxyServer>>>,
+    Extension(scope): Extension<AccessScope>,
+    Json(message): Json<Value>,
+) -> impl IntoResponse {
+    let mut server = server.lock_owned().await;
+    match handle_message(&mut server, &scope, message).await {
+        Some(response) => (StatusCode::OK, Json(response)).into_response(),
+        None => StatusCode::ACCEPTED.into_response(),
+    }
+}
+
+/// Handles one JSON-RPC message, returning `None` for notifications.
+pub async fn handle_message(
+    server: &mut ProxyServer,
+    scope: &AccessScope,
+    message: Value,
+) -> Option<Value> {
+    let Value::Object(mut message) = message else {
+        return Some(error(
+            Value::Null,
+            INVALID_REQUEST,
+            "expected a JSON-RPC object".to_owned(),
+        ));
+    };
+    let id = message.remove("id")?;
+    let Some(Value::String(method)) = message.remove("method") else {
+        return Some(error(id, INVALID_REQUEST, "missing method".to_owned()));
+    };
+    let params = match message.remove("params") {
+        Some(Value::Object(params)) => params,
+        _ => Map::new(),
+    };
+
+    let result = match method.as_str() {
+        "initialize" => Ok(initialize(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tools::list() })),
+        "tools/call" => call_tool(server, scope, params).await,
+        _ => Err((METHOD_NOT_FOUND, format!("unknown method `{method}`"))),
+    };
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error(id, code, message),
+    })
+}
+
+fn initialize(params: &Map<String, Value>) -> Value {
+    let protocol_version = params
+        .get("protocolVersion")
+        .and_then(Value::as_str)
+        .filter(|version| PROTOCOL_VERSIONS.contains(version))
+        .unwrap_or(PROTOCOL_VERSIONS[0]);
+
+    json!({
+        "protocolVersion": protocol_version,
+        "capabilities": { "tools": {} },
+        "serverInfo": {
+            "name": "glues",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+}
+
+async fn call_tool(
+    server: &mut ProxyServer,
+    scope: &AccessScope,
+    mut params: Map<String, Value>,
+) -> Result<Value, (i64, String)> {
+    let Some(Value::String(name)) = params.remove("name") else {
+        return Err((INVALID_PARAMS, "missing tool name".to_owned()));
+    };
+    let arguments = match params.remove("arguments") {
+        Some(Value::Object(arguments)) => arguments,
+        None | Some(Value::Null) => Map::new(),
+        Some(_) => return Err((INVALID_PARAMS, "arguments must be an object".to_owned())),
+    };
+
+    let (text, is_error) = match tools::call(server, scope, &name, &arguments).await {
+        Ok(text) => (text, false),
+        Err(tools::CallError::Failed(message)) => (message, true),
+        Err(tools::CallError::UnknownTool(name)) => {
+            return Err((INVALID_PARAMS, format!("unknown tool `{name}`")));
+        }
+    };
+
+    Ok(json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    }))
+}
+
+fn error(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, glues_core::backend::local::Db};
+
+    async fn server() -> ProxyServer {
+        ProxyServer::new(Box::new(Db::memory().await.expect("memory backend")))
+    }
+
+    async fn call(server: &mut ProxyServer, name: &str, arguments: Value) -> (String, bool) {
+        let message = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": name, "arguments": arguments },
+        });
+        let response = handle_message(server, &AccessScope::full(), message)
+            .await
+            .expect("request should be answered");
+        let result = &response["result"];
+
+        (
+            result["content"][0]["text"]
+                .as_str()
+                .expect("tool output should be text")
+                .to_owned(),
+            result["isError"].as_bool().expect("isError should be set"),
+        )
+    }
+
+    #[tokio::test]
+    async fn initialize_and_list_tools() {
+        let mut server = server().await;
+
+        let response = handle_message(
+            &mut server,
+            &AccessScope::full(),
+            json!({
+                "jsonrpc": "2.0",
+                "id": "init",
+                "method": "initialize",
+                "params": { "protocolVersion": "2024-11-05" },
+            }),
+        )
+        .await
+        .expect("initialize should be answered");
+        assert_eq!(response["id"], "init");
+        assert_eq!(response["result"]["protocolVersion"], "2024-11-05");
+        assert!(response["result"]["capabilities"]["tools"].is_object());
+
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(
+            handle_message(&mut server, &AccessScope::full(), notification)
+                .await
+                .is_none()
+        );
+
+        let response = handle_message(
+            &mut server,
+            &AccessScope::full(),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+        )
+        .await
+        .expect("tools/list should be answered");
+        let names = response["result"]["tools"]
+            .as_array()
+            .expect("tools should be an array")
+            .iter()
+            .map(|tool| tool["name"].as_str().expect("tool name"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "list_directories",
+                "read_note",
+                "search",
+                "create_note",
+                "update_note"
+            ]
+        );
+
+        let response = handle_message(
+            &mut server,
+            &AccessScope::full(),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "resources/list" }),
+        )
+        .await
+        .expect("unknown method should be answered");
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn tools_read_and_write_notes() {
+        let mut server = server().await;
+
+        let (text, is_error) = call(
+            &mut server,
+            "create_note",
+            json!({ "name": "Standup", "content": "- fixed login" }),
+        )
+        .await;
+        assert!(!is_error, "{text}");
+        let note: Value = serde_json::from_str(&text).expect("note should be JSON");
+        let note_id = note["id"].as_str().expect("note id").to_owned();
+
+        let (text, _) = call(
+            &mut server,
+            "update_note",
+            json!({ "note_id": note_id, "content": "- shipped search", "append": true }),
+        )
+        .await;
+        let updated: Value = serde_json::from_str(&text).expect("note should be JSON");
+        assert_eq!(updated["id"], note_id.as_str());
+
+        // a write based on the note as created lost to the append above
+        let (text, is_error) = call(
+            &mut server,
+            "update_note",
+            json!({
+                "note_id": note_id,
+                "content": "- stale",
+                "expected_updated_at": note["updated_at"],
+            }),
+        )
+        .await;
+        assert!(is_error);
+        assert!(text.starts_with("conflict: "), "{text}");
+
+        let (text, _) = call(&mut server, "read_note", json!({ "note_id": note_id })).await;
+        assert_eq!(text, "- fixed login\n- shipped search");
+
+        let (text, _) = call(&mut server, "search", json!({ "query": "shipped" })).await;
+        let results: Value = serde_json::from_str(&text).expect("results should be JSON");
+        assert_eq!(results[0]["note"]["id"], note_id.as_str());
+
+        let (text, _) = call(&mut server, "list_directories", json!({})).await;
+        let listing: Value = serde_json::from_str(&text).expect("listing should be JSON");
+        assert_eq!(listing["directory"]["name"], "Notes");
+        assert_eq!(listing["notes"][0]["name"], "Standup");
+
+        let (text, is_error) = call(&mut server, "read_note", json!({})).await;
+        assert!(is_error);
+        assert_eq!(text, "missing required argument `note_id`");
+    }
+}
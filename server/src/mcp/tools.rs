@@ -0,0 +1,299 @@
+use {
+    glues_core::{
+        backend::proxy::{
+            AccessScope, ProxyServer,
+            request::ProxyRequest,
+            response::{ProxyResponse, ResultPayload},
+        },
+        data::Note,
+    },
+    serde::Serialize,
+    serde_json::{Map, Value, json},
+};
+
+/// Tool descriptors returned from `tools/list`.
+pub fn list() -> Value {
+    json!([
+        {
+            "name": "list_directories",
+            "description": "List the sub-directories and notes of a directory. Omit directory_id to list the notebook root.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "directory_id": { "type": "string", "description": "Directory to list" }
+                }
+            }
+        },
+        {
+            "name": "read_note",
+            "description": "Read the Markdown content of a note.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "note_id": { "type": "string" }
+                },
+                "required": ["note_id"]
+            }
+        },
+        {
+            "name": "search",
+            "description": "Search note names and contents, returning matching notes with line snippets.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" }
+                },
+                "required": ["query"]
+            }
+        },
+        {
+            "name": "create_note",
+            "description": "Create a note in a directory, optionally with initial content. Omit directory_id to create it in the notebook root.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "directory_id": { "type": "string" },
+                    "name": { "type": "string" },
+                    "content": { "type": "string" }
+                },
+                "required": ["name"]
+            }
+        },
+        {
+            "name": "update_note",
+            "description": "Replace the content of a note, or append to it when append is true. Returns the updated note.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "note_id": { "type": "string" },
+                    "content": { "type": "string" },
+                    "append": { "type": "boolean", "default": false },
+                    "expected_updated_at": {
+                        "type": "string",
+                        "description": "updated_at of the note as last read; the update fails if it changed since"
+                    }
+                },
+                "required": ["note_id", "content"]
+            }
+        }
+    ])
+}
+
+/// Outcome of a `tools/call` request.
+pub enum CallError {
+    /// The tool name is not known; reported as a JSON-RPC error.
+    UnknownTool(String),
+    /// The tool ran but failed; reported as a tool result with `isError`.
+    Failed(String),
+}
+
+/// The proxy server together with the scope of the MCP caller's token.
+struct Session<'a> {
+    server: &'a mut ProxyServer,
+    scope: &'a AccessScope,
+}
+
+/// Runs a tool against the proxy server and returns its text output.
+pub async fn call(
+    server: &mut ProxyServer,
+    scope: &AccessScope,
+    name: &str,
+    arguments: &Map<String, Value>,
+) -> Result<String, CallError> {
+    let server = &mut Session { server, scope };
+    match name {
+        "list_directories" => list_directories(server, arguments).await,
+        "read_note" => read_note(server, arguments).await,
+        "search" => search(server, arguments).await,
+        "create_note" => create_note(server, arguments).await,
+        "update_note" => update_note(server, arguments).await,
+        _ => Err(CallError::UnknownTool(name.to_owned())),
+    }
+}
+
+async fn list_directories(
+    server: &mut Session<'_>,
+    arguments: &Map<String, Value>,
+) -> Result<String, CallError> {
+    let directory_id = match optional_str(arguments, "directory_id")? {
+        Some(directory_id) => directory_id,
+        None => root_id(server).await?,
+    };
+
+    let directory = match request(
+        server,
+        ProxyRequest::FetchDirectory {
+            directory_id: directory_id.clone(),
+        },
+    )
+    .await?
+    {
+        ResultPayload::Directory(directory) => directory,
+        payload => return Err(unexpected(payload)),
+    };
+    let directories = match request(
+        server,
+        ProxyRequest::FetchDirectories {
+            parent_id: directory_id.clone(),
+        },
+    )
+    .await?
+    {
+        ResultPayload::Directories(directories) => directories,
+        payload => return Err(unexpected(payload)),
+    };
+    let notes = fetch_notes(server, directory_id).await?;
+
+    to_text(&json!({
+        "directory": directory,
+        "directories": directories,
+        "notes": notes,
+    }))
+}
+
+async fn read_note(
+    server: &mut Session<'_>,
+    arguments: &Map<String, Value>,
+) -> Result<String, CallError> {
+    let note_id = required_str(arguments, "note_id")?;
+
+    fetch_content(server, note_id).await
+}
+
+async fn search(
+    server: &mut Session<'_>,
+    arguments: &Map<String, Value>,
+) -> Result<String, CallError> {
+    let query = required_str(arguments, "query")?;
+
+    match request(server, ProxyRequest::SearchNotes { query }).await? {
+        ResultPayload::SearchResults(results) => to_text(&results),
+        payload => Err(unexpected(payload)),
+    }
+}
+
+async fn create_note(
+    server: &mut Session<'_>,
+    arguments: &Map<String, Value>,
+) -> Result<String, CallError> {
+    let name = required_str(arguments, "name")?;
+    let content = optional_str(arguments, "content")?;
+    let directory_id = match optional_str(arguments, "directory_id")? {
+        Some(directory_id) => directory_id,
+        None => root_id(server).await?,
+    };
+
+    let note = match request(server, ProxyRequest::AddNote { directory_id, name }).await? {
+        ResultPayload::Note(note) => note,
+        payload => return Err(unexpected(payload)),
+    };
+    let note = match content.filter(|content| !content.is_empty()) {
+        Some(content) => update_content(server, note.id, content, None).await?,
+        None => note,
+    };
+
+    to_text(&note)
+}
+
+async fn update_note(
+    server: &mut Session<'_>,
+    arguments: &Map<String, Value>,
+) -> Result<String, CallError> {
+    let note_id = required_str(arguments, "note_id")?;
+    let content = required_str(arguments, "content")?;
+    let expected_updated_at = optional_str(arguments, "expected_updated_at")?;
+    let append = match arguments.get("append") {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(append)) => *append,
+        Some(_) => return Err(CallError::Failed("`append` must be a boolean".to_owned())),
+    };
+
+    let content = if append {
+        let mut current = fetch_content(server, note_id.clone()).await?;
+        if !current.is_empty() && !current.ends_with('\n') {
+            current.push('\n');
+        }
+        current + &content
+    } else {
+        content
+    };
+    let note = update_content(server, note_id, content, expected_updated_at).await?;
+
+    to_text(&note)
+}
+
+async fn request(
+    server: &mut Session<'_>,
+    request: ProxyRequest,
+) -> Result<ResultPayload, CallError> {
+    match server.server.handle(server.scope, request).await {
+        ProxyResponse::Ok(payload) => Ok(payload),
+        ProxyResponse::Err(error) => Err(CallError::Failed(error.to_string())),
+    }
+}
+
+async fn root_id(server: &mut Session<'_>) -> Result<String, CallError> {
+    match request(server, ProxyRequest::RootId).await? {
+        ResultPayload::Id(id) => Ok(id),
+        payload => Err(unexpected(payload)),
+    }
+}
+
+async fn fetch_notes(
+    server: &mut Session<'_>,
+    directory_id: String,
+) -> Result<Vec<Note>, CallError> {
+    match request(server, ProxyRequest::FetchNotes { directory_id }).await? {
+        ResultPayload::Notes(notes) => Ok(notes),
+        payload => Err(unexpected(payload)),
+    }
+}
+
+async fn fetch_content(server: &mut Session<'_>, note_id: String) -> Result<String, CallError> {
+    match request(server, ProxyRequest::FetchNoteContent { note_id }).await? {
+        ResultPayload::Text(content) => Ok(content),
+        payload => Err(unexpected(payload)),
+    }
+}
+
+async fn update_content(
+    server: &mut Session<'_>,
+    note_id: String,
+    content: String,
+    expected_updated_at: Option<String>,
+) -> Result<Note, CallError> {
+    match request(
+        server,
+        ProxyRequest::UpdateNoteContent {
+            note_id,
+            content,
+            expected_updated_at,
+        },
+    )
+    .await?
+    {
+        ResultPayload::Note(note) => Ok(note),
+        payload => Err(unexpected(payload)),
+    }
+}
+
+fn required_str(arguments: &Map<String, Value>, key: &str) -> Result<String, CallError> {
+    optional_str(arguments, key)?
+        .ok_or_else(|| CallError::Failed(format!("missing required argument `{key}`")))
+}
+
+fn optional_str(arguments: &Map<String, Value>, key: &str) -> Result<Option<String>, CallError> {
+    match arguments.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(_) => Err(CallError::Failed(format!("`{key}` must be a string"))),
+    }
+}
+
+fn to_text<T: Serialize + ?Sized>(value: &T) -> Result<String, CallError> {
+    serde_json::to_string_pretty(value).map_err(|err| CallError::Failed(err.to_string()))
+}
+
+fn unexpected(payload: ResultPayload) -> CallError {
+    CallError::Failed(format!("unexpected response: {payload:?}"))
+}
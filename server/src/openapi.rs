@@ -0,0 +1,407 @@
+//! OpenAPI description of the server, served at `GET /openapi.json`.
+//!
+//! Schemas are generated from the serde types themselves, so the tagging of
+//! [`ProxyRequest`] and [`ProxyResponse`] is spelled out for client
+//! generators; the paths are listed here and must follow the routes in
+//! `lib.rs` and [`crate::rest`].
+
+use {
+    crate::rest::{DirectoryPatch, NameBody, NewNote, NotePatch},
+    axum::Json,
+    glues_core::{
+        backend::proxy::{Change, ProxyError, request::ProxyRequest, response::ProxyResponse},
+        data::{Directory, Note, NoteRevision, NoteSearchResult, Tag, Trash},
+    },
+    schemars::{
+        JsonSchema,
+        r#gen::{SchemaGenerator, SchemaSettings},
+    },
+    serde_json::{Map, Value, json},
+};
+
+pub async fn handle() -> Json<Value> {
+    Json(document())
+}
+
+pub fn document() -> Value {
+    let mut schemas = Schemas(SchemaSettings::openapi3().into_generator());
+    let mut paths = Paths::default();
+
+    let directory = json_body(schemas.of::<Directory>());
+    let note = json_body(schemas.of::<Note>());
+    let notes = json_body(schemas.of::<Vec<Note>>());
+
+    let directory_id = path_param("id", "Directory ID, or `root` for the notebook root");
+    let note_id = path_param("id", "Note ID");
+    let tag_name = path_param("name", "Tag name");
+    let if_match = json!({
+        "name": "If-Match",
+        "in": "header",
+        "description": "The note's `updated_at` as last read; the change is refused with 409 Conflict when the note changed since",
+        "schema": { "type": "string" },
+    });
+
+    paths.add(
+        "/",
+        "post",
+        Operation::new(
+            "proxy",
+            "Runs a proxy request; failures are answered in the body",
+        )
+        .body(json_body(schemas.of::<ProxyRequest>()))
+        .responds("200", json_body(schemas.of::<ProxyResponse>())),
+    );
+    paths.add(
+        "/events",
+        "get",
+        Operation::new("events", "Streams each change as a server-sent event").responds(
+            "200",
+            json!({ "content": { "text/event-stream": { "schema": schemas.of::<Change>() } } }),
+        ),
+    );
+    paths.add(
+        "/mcp",
+        "post",
+        Operation::new("mcp", "Answers a Model Context Protocol JSON-RPC message")
+            .body(json_body(json!({ "type": "object" })))
+            .responds("200", json_body(json!({ "type": "object" }))),
+    );
+    paths.add(
+        "/health",
+        "get",
+        Operation::new("health", "Reports that the server is up").responds("200", json!({})),
+    );
+    paths.add(
+        "/openapi.json",
+        "get",
+        Operation::new("openapi", "Describes the server's API")
+            .responds("200", json_body(json!({ "type": "object" }))),
+    );
+
+    paths.add(
+        "/directories/{id}",
+        "get",
+        Operation::new("getDirectory", "Fetches a directory")
+            .param(&directory_id)
+            .responds("200", directory.clone()),
+    );
+    paths.add(
+        "/directories/{id}",
+        "patch",
+        Operation::new("updateDirectory", "Renames and moves a directory")
+            .param(&directory_id)
+            .body(json_body(schemas.of::<DirectoryPatch>()))
+            .responds("200", directory.clone()),
+    );
+    paths.add(
+        "/directories/{id}",
+        "delete",
+        Operation::new("removeDirectory", "Moves a directory to the trash")
+            .param(&directory_id)
+            .responds("204", json!({})),
+    );
+    paths.add(
+        "/directories/{id}/directories",
+        "get",
+        Operation::new("listDirectories", "Lists the child directories")
+            .param(&directory_id)
+            .responds("200", json_body(schemas.of::<Vec<Directory>>())),
+    );
+    paths.add(
+        "/directories/{id}/directories",
+        "post",
+        Operation::new("addDirectory", "Creates a child directory")
+            .param(&directory_id)
+            .body(json_body(schemas.of::<NameBody>()))
+            .responds("201", directory.clone()),
+    );
+    paths.add(
+        "/directories/{id}/notes",
+        "get",
+        Operation::new("listNotes", "Lists the notes in a directory")
+            .param(&directory_id)
+            .responds("200", notes.clone()),
+    );
+    paths.add(
+        "/directories/{id}/notes",
+        "post",
+        Operation::new("addNote", "Creates a note, optionally with content")
+            .param(&directory_id)
+            .body(json_body(schemas.of::<NewNote>()))
+            .responds("201", note.clone()),
+    );
+
+    paths.add(
+        "/notes/{id}",
+        "get",
+        Operation::new(
+            "getNote",
+            "Fetches a note, with its `updated_at` as the ETag",
+        )
+        .param(&note_id)
+        .responds("200", note.clone()),
+    );
+    paths.add(
+        "/notes/{id}",
+        "patch",
+        Operation::new("updateNote", "Renames and moves a note")
+            .param(&note_id)
+            .param(&if_match)
+            .body(json_body(schemas.of::<NotePatch>()))
+            .responds("200", note.clone()),
+    );
+    paths.add(
+        "/notes/{id}",
+        "delete",
+        Operation::new("removeNote", "Moves a note to the trash")
+            .param(&note_id)
+            .responds("204", json!({})),
+    );
+    paths.add(
+        "/notes/{id}/content",
+        "get",
+        Operation::new("getNoteContent", "Reads a note's Markdown")
+            .param(&note_id)
+            .responds("200", text_body()),
+    );
+    paths.add(
+        "/notes/{id}/content",
+        "put",
+        Operation::new("putNoteContent", "Replaces a note's Markdown")
+            .param(&note_id)
+            .param(&if_match)
+            .body(text_body())
+            .responds("200", note.clone()),
+    );
+    paths.add(
+        "/notes/{id}/backlinks",
+        "get",
+        Operation::new("listBacklinks", "Lists the notes linking to a note")
+            .param(&note_id)
+            .responds("200", notes.clone()),
+    );
+    paths.add(
+        "/notes/{id}/revisions",
+        "get",
+        Operation::new("listNoteRevisions", "Lists the saved revisions of a note")
+            .param(&note_id)
+            .responds("200", json_body(schemas.of::<Vec<NoteRevision>>())),
+    );
+    paths.add(
+        "/notes/{id}/revisions/{revision_id}",
+        "get",
+        Operation::new("getNoteRevision", "Reads the Markdown of a revision")
+            .param(&note_id)
+            .param(&path_param("revision_id", "Revision ID"))
+            .responds("200", text_body()),
+    );
+    paths.add(
+        "/notes/{id}/tags/{name}",
+        "put",
+        Operation::new("addNoteTag", "Tags a note")
+            .param(&note_id)
+            .param(&tag_name)
+            .responds("204", json!({})),
+    );
+    paths.add(
+        "/notes/{id}/tags/{name}",
+        "delete",
+        Operation::new("removeNoteTag", "Untags a note")
+            .param(&note_id)
+            .param(&tag_name)
+            .responds("204", json!({})),
+    );
+
+    paths.add(
+        "/tags",
+        "get",
+        Operation::new("listTags", "Lists all tags")
+            .responds("200", json_body(schemas.of::<Vec<Tag>>())),
+    );
+    paths.add(
+        "/tags/{name}/notes",
+        "get",
+        Operation::new("listNotesByTag", "Lists the notes with a tag")
+            .param(&tag_name)
+            .responds("200", notes),
+    );
+    paths.add(
+        "/search",
+        "get",
+        Operation::new("search", "Searches note names and content")
+            .param(&json!({
+                "name": "q",
+                "in": "query",
+                "required": true,
+                "schema": { "type": "string" },
+            }))
+            .responds("200", json_body(schemas.of::<Vec<NoteSearchResult>>())),
+    );
+    paths.add(
+        "/trash",
+        "get",
+        Operation::new("getTrash", "Lists the trash")
+            .responds("200", json_body(schemas.of::<Trash>())),
+    );
+    paths.add(
+        "/trash",
+        "delete",
+        Operation::new("emptyTrash", "Deletes everything in the trash for good")
+            .responds("204", json!({})),
+    );
+    paths.add(
+        "/trash/notes/{id}/restore",
+        "post",
+        Operation::new("restoreNote", "Restores a note from the trash")
+            .param(&note_id)
+            .responds("200", note),
+    );
+    paths.add(
+        "/trash/directories/{id}/restore",
+        "post",
+        Operation::new("restoreDirectory", "Restores a directory from the trash")
+            .param(&directory_id)
+            .responds("200", directory),
+    );
+
+    let error = json_body(schemas.of::<ProxyError>());
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Glues server",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Proxy, REST and MCP endpoints of glues-server. A bearer token is only required when the server was started with one.",
+        },
+        "security": [{ "bearer": [] }, {}],
+        "paths": paths.0,
+        "components": {
+            "schemas": schemas.into_definitions(),
+            "responses": {
+                "Error": { "description": "The request failed", "content": error["content"] },
+            },
+            "securitySchemes": {
+                "bearer": { "type": "http", "scheme": "bearer" },
+            },
+        },
+    })
+}
+
+struct Schemas(SchemaGenerator);
+
+impl Schemas {
+    /// A reference to the schema of `T`, which is added to the components.
+    fn of<T: JsonSchema>(&mut self) -> Value {
+        serde_json::to_value(self.0.subschema_for::<T>()).expect("schema should serialize")
+    }
+
+    fn into_definitions(self) -> Value {
+        serde_json::to_value(self.0.definitions()).expect("schemas should serialize")
+    }
+}
+
+#[derive(Default)]
+struct Paths(Map<String, Value>);
+
+impl Paths {
+    fn add(&mut self, path: &str, method: &str, operation: Operation) {
+        let item = self
+            .0
+            .entry(path)
+            .or_insert_with(|| Value::Object(Map::new()));
+        item[method] = operation.0;
+    }
+}
+
+struct Operation(Value);
+
+impl Operation {
+    /// An operation that answers failures with the shared `Error` response.
+    fn new(id: &str, summary: &str) -> Self {
+        Self(json!({
+            "operationId": id,
+            "summary": summary,
+            "responses": {
+                "default": { "$ref": "#/components/responses/Error" },
+            },
+        }))
+    }
+
+    fn param(mut self, parameter: &Value) -> Self {
+        match self.0["parameters"].as_array_mut() {
+            Some(parameters) => parameters.push(parameter.clone()),
+            None => self.0["parameters"] = json!([parameter]),
+        }
+        self
+    }
+
+    fn body(mut self, mut body: Value) -> Self {
+        body["required"] = json!(true);
+        self.0["requestBody"] = body;
+        self
+    }
+
+    fn responds(mut self, status: &str, mut response: Value) -> Self {
+        response["description"] = self.0["summary"].clone();
+        self.0["responses"][status] = response;
+        self
+    }
+}
+
+fn path_param(name: &str, description: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "path",
+        "required": true,
+        "description": description,
+        "schema": { "type": "string" },
+    })
+}
+
+fn json_body(schema: Value) -> Value {
+    json!({ "content": { "application/json": { "schema": schema } } })
+}
+
+fn text_body() -> Value {
+    json!({ "content": { "text/plain": { "schema": { "type": "string" } } } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refs(value: &Value, found: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(target)) = map.get("$ref") {
+                    found.push(target.clone());
+                }
+                map.values().for_each(|value| refs(value, found));
+            }
+            Value::Array(values) => values.iter().for_each(|value| refs(value, found)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn document_describes_the_protocol_types() {
+        let document = document();
+
+        let schemas = &document["components"]["schemas"];
+        let request = serde_json::to_string(&schemas["ProxyRequest"]).unwrap();
+        assert!(request.contains("\"method\"") && request.contains("UpdateNoteContent"));
+        assert!(schemas["ResultPayload"].is_object());
+        assert!(schemas["ErrorCode"].is_object());
+        assert!(document["paths"]["/notes/{id}/content"]["put"].is_object());
+
+        let mut found = Vec::new();
+        refs(&document, &mut found);
+        for target in found {
+            let pointer = target.trim_start_matches('#');
+            assert!(
+                document.pointer(pointer).is_some(),
+                "{target} should resolve"
+            );
+        }
+    }
+}
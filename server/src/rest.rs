@@ -0,0 +1,659 @@
+//! Resource-style HTTP API for shell scripts and other tooling, e.g.
+//! `curl localhost:4000/directories/root/notes`. Routes are translated into
+//! [`ProxyRequest`]s and run through [`ProxyServer::handle`], so token scopes
+//! and the change feed apply just as they do for proxy clients.
+//!
+//! [`ProxyRequest`]: glues_core::backend::proxy::request::ProxyRequest
+
+use {
+    axum::{
+        Json, Router, async_trait,
+        extract::{FromRequestParts, Path, Query},
+        http::{
+            HeaderMap, StatusCode,
+            header::{ETAG, IF_MATCH},
+            request::Parts,
+        },
+        response::{IntoResponse, Response},
+        routing::{get, post, put},
+    },
+    glues_core::{
+        backend::proxy::{
+            AccessScope, ErrorCode, ProxyError, ProxyServer,
+            request::ProxyRequest,
+            response::{ProxyResponse, ResultPayload},
+        },
+        data::{Directory, Note, NoteRevision, NoteSearchResult, Tag, Trash},
+        types::{DirectoryId, NoteId},
+    },
+    schemars::JsonSchema,
+    serde::Deserialize,
+    std::sync::Arc,
+    tokio::sync::Mutex as AsyncMutex,
+};
+
+type SharedServer = Arc<AsyncMutex<ProxyServer>>;
+
+/// Stands for the notebook root wherever a directory ID is expected.
+const ROOT_ALIAS: &str = "root";
+
+pub fn routes() -> Router<SharedServer> {
+    Router::new()
+        .route(
+            "/directories/:id",
+            get(get_directory)
+                .patch(update_directory)
+                .delete(remove_directory),
+        )
+        .route(
+            "/directories/:id/directories",
+            get(list_directories).post(add_directory),
+        )
+        .route("/directories/:id/notes", get(list_notes).post(add_note))
+        .route(
+            "/notes/:id",
+            get(get_note).patch(update_note).delete(remove_note),
+        )
+        .route("/notes/:id/content", get(get_content).put(put_content))
+        .route("/notes/:id/backlinks", get(list_backlinks))
+        .route("/notes/:id/revisions", get(list_revisions))
+        .route("/notes/:id/revisions/:revision_id", get(get_revision))
+        .route("/notes/:id/tags/:name", put(add_tag).delete(remove_tag))
+        .route("/tags", get(list_tags))
+        .route("/tags/:name/notes", get(notes_by_tag))
+        .route("/search", get(search))
+        .route("/trash", get(get_trash).delete(empty_trash))
+        .route("/trash/notes/:id/restore", post(restore_note))
+        .route("/trash/directories/:id/restore", post(restore_directory))
+}
+
+/// The proxy server together with the scope of the caller's token.
+pub struct Api {
+    server: SharedServer,
+    scope: AccessScope,
+}
+
+#[async_trait]
+impl FromRequestParts<SharedServer> for Api {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        server: &SharedServer,
+    ) -> Result<Self, Self::Rejection> {
+        let scope = parts
+            .extensions
+            .get::<AccessScope>()
+            .cloned()
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+
+        Ok(Self {
+            server: Arc::clone(server),
+            scope,
+        })
+    }
+}
+
+impl Api {
+    async fn call(&self, request: ProxyRequest) -> Result<ResultPayload, ApiError> {
+        match self.server.lock().await.handle(&self.scope, request).await {
+            ProxyResponse::Ok(payload) => Ok(payload),
+            ProxyResponse::Err(error) => Err(ApiError(error)),
+        }
+    }
+
+    async fn directory_id(&self, id: String) -> Result<DirectoryId, ApiError> {
+        if id != ROOT_ALIAS {
+            return Ok(id);
+        }
+
+        match self.call(ProxyRequest::RootId).await? {
+            ResultPayload::Id(id) => Ok(id),
+            payload => Err(unexpected(payload)),
+        }
+    }
+
+    async fn directory(&self, directory_id: DirectoryId) -> Result<Directory, ApiError> {
+        match self
+            .call(ProxyRequest::FetchDirectory { directory_id })
+            .await?
+        {
+            ResultPayload::Directory(directory) => Ok(directory),
+            payload => Err(unexpected(payload)),
+        }
+    }
+
+    async fn note(&self, request: ProxyRequest) -> Result<Note, ApiError> {
+        match self.call(request).await? {
+            ResultPayload::Note(note) => Ok(note),
+            payload => Err(unexpected(payload)),
+        }
+    }
+
+    async fn notes(&self, request: ProxyRequest) -> Result<Vec<Note>, ApiError> {
+        match self.call(request).await? {
+            ResultPayload::Notes(notes) => Ok(notes),
+            payload => Err(unexpected(payload)),
+        }
+    }
+
+    async fn text(&self, request: ProxyRequest) -> Result<String, ApiError> {
+        match self.call(request).await? {
+            ResultPayload::Text(text) => Ok(text),
+            payload => Err(unexpected(payload)),
+        }
+    }
+
+    async fn unit(&self, request: ProxyRequest) -> Result<StatusCode, ApiError> {
+        match self.call(request).await? {
+            ResultPayload::Unit => Ok(StatusCode::NO_CONTENT),
+            payload => Err(unexpected(payload)),
+        }
+    }
+}
+
+/// A [`ProxyError`] answered with the matching HTTP status.
+pub struct ApiError(ProxyError);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self.0.code {
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::InvalidState => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ErrorCode::Conflict => StatusCode::CONFLICT,
+            ErrorCode::Unsupported | ErrorCode::Todo => StatusCode::NOT_IMPLEMENTED,
+            ErrorCode::InvalidResponse
+            | ErrorCode::SchemaVersion
+            | ErrorCode::Backend
+            | ErrorCode::Unknown => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        (status, Json(self.0)).into_response()
+    }
+}
+
+fn unexpected(payload: ResultPayload) -> ApiError {
+    ApiError(ProxyError::new(
+        ErrorCode::InvalidResponse,
+        format!("unexpected response from proxy server: {payload:?}"),
+    ))
+}
+
+fn bad_request(message: &str) -> ApiError {
+    ApiError(ProxyError::new(ErrorCode::InvalidState, message))
+}
+
+/// The `updated_at` a change is based on, sent as `If-Match`.
+fn if_match(headers: &HeaderMap) -> Result<Option<String>, ApiError> {
+    let Some(value) = headers.get(IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value
+        .to_str()
+        .map_err(|_| bad_request("If-Match must be the note's updated_at"))?;
+
+    Ok(Some(value.trim().trim_matches('"').to_owned()))
+}
+
+fn with_etag(note: Note) -> Response {
+    let etag = format!("\"{}\"", note.updated_at);
+
+    ([(ETAG, etag)], Json(note)).into_response()
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct NameBody {
+    name: String,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct NewNote {
+    name: String,
+    #[serde(default)]
+    content: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct DirectoryPatch {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    parent_id: Option<DirectoryId>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct NotePatch {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    directory_id: Option<DirectoryId>,
+}
+
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    q: String,
+}
+
+async fn get_directory(api: Api, Path(id): Path<String>) -> Result<Json<Directory>, ApiError> {
+    let directory_id = api.directory_id(id).await?;
+
+    api.directory(directory_id).await.map(Json)
+}
+
+async fn update_directory(
+    api: Api,
+    Path(id): Path<String>,
+    Json(patch): Json<DirectoryPatch>,
+) -> Result<Json<Directory>, ApiError> {
+    let directory_id = api.directory_id(id).await?;
+
+    if let Some(name) = patch.name {
+        api.unit(ProxyRequest::RenameDirectory {
+            directory_id: directory_id.clone(),
+            name,
+        })
+        .await?;
+    }
+    if let Some(parent_id) = patch.parent_id {
+        let parent_id = api.directory_id(parent_id).await?;
+        api.unit(ProxyRequest::MoveDirectory {
+            directory_id: directory_id.clone(),
+            parent_id,
+        })
+        .await?;
+    }
+
+    api.directory(directory_id).await.map(Json)
+}
+
+async fn remove_directory(api: Api, Path(id): Path<String>) -> Result<StatusCode, ApiError> {
+    let directory_id = api.directory_id(id).await?;
+
+    api.unit(ProxyRequest::RemoveDirectory { directory_id })
+        .await
+}
+
+async fn list_directories(
+    api: Api,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<Directory>>, ApiError> {
+    let parent_id = api.directory_id(id).await?;
+
+    match api
+        .call(ProxyRequest::FetchDirectories { parent_id })
+        .await?
+    {
+        ResultPayload::Directories(directories) => Ok(Json(directories)),
+        payload => Err(unexpected(payload)),
+    }
+}
+
+async fn add_directory(
+    api: Api,
+    Path(id): Path<String>,
+    Json(body): Json<NameBody>,
+) -> Result<(StatusCode, Json<Directory>), ApiError> {
+    let parent_id = api.directory_id(id).await?;
+
+    match api
+        .call(ProxyRequest::AddDirectory {
+            parent_id,
+            name: body.name,
+        })
+        .await?
+    {
+        ResultPayload::Directory(directory) => Ok((StatusCode::CREATED, Json(directory))),
+        payload => Err(unexpected(payload)),
+    }
+}
+
+async fn list_notes(api: Api, Path(id): Path<String>) -> Result<Json<Vec<Note>>, ApiError> {
+    let directory_id = api.directory_id(id).await?;
+
+    api.notes(ProxyRequest::FetchNotes { directory_id })
+        .await
+        .map(Json)
+}
+
+async fn add_note(
+    api: Api,
+    Path(id): Path<String>,
+    Json(body): Json<NewNote>,
+) -> Result<(StatusCode, Response), ApiError> {
+    let directory_id = api.directory_id(id).await?;
+
+    let mut note = api
+        .note(ProxyRequest::AddNote {
+            directory_id,
+            name: body.name,
+        })
+        .await?;
+    if let Some(content) = body.content.filter(|content| !content.is_empty()) {
+        note = api
+            .note(ProxyRequest::UpdateNoteContent {
+                note_id: note.id,
+                content,
+                expected_updated_at: None,
+            })
+            .await?;
+    }
+
+    Ok((StatusCode::CREATED, with_etag(note)))
+}
+
+async fn get_note(api: Api, Path(note_id): Path<NoteId>) -> Result<Response, ApiError> {
+    api.note(ProxyRequest::FetchNote { note_id })
+        .await
+        .map(with_etag)
+}
+
+async fn update_note(
+    api: Api,
+    Path(note_id): Path<NoteId>,
+    headers: HeaderMap,
+    Json(patch): Json<NotePatch>,
+) -> Result<Response, ApiError> {
+    let mut expected_updated_at = if_match(&headers)?;
+    let mut note = None;
+
+    if let Some(name) = patch.name {
+        let renamed = api
+            .note(ProxyRequest::RenameNote {
+                note_id: note_id.clone(),
+                name,
+                expected_updated_at,
+            })
+            .await?;
+        expected_updated_at = Some(renamed.updated_at.clone());
+        note = Some(renamed);
+    }
+    if let Some(directory_id) = patch.directory_id {
+        let directory_id = api.directory_id(directory_id).await?;
+        let moved = api
+            .note(ProxyRequest::MoveNote {
+                note_id: note_id.clone(),
+                directory_id,
+                expected_updated_at,
+            })
+            .await?;
+        note = Some(moved);
+    }
+
+    let note = match note {
+        Some(note) => note,
+        None => api.note(ProxyRequest::FetchNote { note_id }).await?,
+    };
+
+    Ok(with_etag(note))
+}
+
+async fn remove_note(api: Api, Path(note_id): Path<NoteId>) -> Result<StatusCode, ApiError> {
+    api.unit(ProxyRequest::RemoveNote { note_id }).await
+}
+
+async fn get_content(api: Api, Path(note_id): Path<NoteId>) -> Result<String, ApiError> {
+    api.text(ProxyRequest::FetchNoteContent { note_id }).await
+}
+
+async fn put_content(
+    api: Api,
+    Path(note_id): Path<NoteId>,
+    headers: HeaderMap,
+    content: String,
+) -> Result<Response, ApiError> {
+    let note = api
+        .note(ProxyRequest::UpdateNoteContent {
+            note_id,
+            content,
+            expected_updated_at: if_match(&headers)?,
+        })
+        .await?;
+
+    Ok(with_etag(note))
+}
+
+async fn list_backlinks(
+    api: Api,
+    Path(note_id): Path<NoteId>,
+) -> Result<Json<Vec<Note>>, ApiError> {
+    api.notes(ProxyRequest::FetchBacklinks { note_id })
+        .await
+        .map(Json)
+}
+
+async fn list_revisions(
+    api: Api,
+    Path(note_id): Path<NoteId>,
+) -> Result<Json<Vec<NoteRevision>>, ApiError> {
+    match api
+        .call(ProxyRequest::ListNoteRevisions { note_id })
+        .await?
+    {
+        ResultPayload::Revisions(revisions) => Ok(Json(revisions)),
+        payload => Err(unexpected(payload)),
+    }
+}
+
+async fn get_revision(
+    api: Api,
+    Path((note_id, revision_id)): Path<(NoteId, String)>,
+) -> Result<String, ApiError> {
+    api.text(ProxyRequest::FetchNoteRevision {
+        note_id,
+        revision_id,
+    })
+    .await
+}
+
+async fn add_tag(
+    api: Api,
+    Path((note_id, name)): Path<(NoteId, String)>,
+) -> Result<StatusCode, ApiError> {
+    api.unit(ProxyRequest::AddNoteTag { note_id, name }).await
+}
+
+async fn remove_tag(
+    api: Api,
+    Path((note_id, name)): Path<(NoteId, String)>,
+) -> Result<StatusCode, ApiError> {
+    api.unit(ProxyRequest::RemoveNoteTag { note_id, name })
+        .await
+}
+
+async fn list_tags(api: Api) -> Result<Json<Vec<Tag>>, ApiError> {
+    match api.call(ProxyRequest::FetchTags).await? {
+        ResultPayload::Tags(tags) => Ok(Json(tags)),
+        payload => Err(unexpected(payload)),
+    }
+}
+
+async fn notes_by_tag(api: Api, Path(name): Path<String>) -> Result<Json<Vec<Note>>, ApiError> {
+    api.notes(ProxyRequest::FetchNotesByTag { name })
+        .await
+        .map(Json)
+}
+
+async fn search(
+    api: Api,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<Vec<NoteSearchResult>>, ApiError> {
+    match api
+        .call(ProxyRequest::SearchNotes { query: query.q })
+        .await?
+    {
+        ResultPayload::SearchResults(results) => Ok(Json(results)),
+        payload => Err(unexpected(payload)),
+    }
+}
+
+async fn get_trash(api: Api) -> Result<Json<Trash>, ApiError> {
+    match api.call(ProxyRequest::FetchTrash).await? {
+        ResultPayload::Trash(trash) => Ok(Json(trash)),
+        payload => Err(unexpected(payload)),
+    }
+}
+
+async fn empty_trash(api: Api) -> Result<StatusCode, ApiError> {
+    api.unit(ProxyRequest::Purge).await
+}
+
+async fn restore_note(api: Api, Path(note_id): Path<NoteId>) -> Result<Response, ApiError> {
+    api.note(ProxyRequest::RestoreNote { note_id })
+        .await
+        .map(with_etag)
+}
+
+async fn restore_directory(
+    api: Api,
+    Path(directory_id): Path<DirectoryId>,
+) -> Result<Json<Directory>, ApiError> {
+    match api
+        .call(ProxyRequest::RestoreDirectory { directory_id })
+        .await?
+    {
+        ResultPayload::Directory(directory) => Ok(Json(directory)),
+        payload => Err(unexpected(payload)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        axum::{
+            Extension,
+            body::{Body, to_bytes},
+            http::{Method, Request},
+        },
+        glues_core::backend::{local::Db, proxy::Access},
+        serde_json::{Value, json},
+        tower::ServiceExt,
+    };
+
+    async fn server() -> SharedServer {
+        let backend = Db::memory().await.expect("memory backend");
+
+        Arc::new(AsyncMutex::new(ProxyServer::new(Box::new(backend))))
+    }
+
+    async fn send(
+        server: &SharedServer,
+        scope: AccessScope,
+        request: Request<Body>,
+    ) -> (StatusCode, HeaderMap, String) {
+        let response = routes()
+            .with_state(Arc::clone(server))
+            .layer(Extension(scope))
+            .oneshot(request)
+            .await
+            .expect("request should be answered");
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body should be readable");
+
+        (status, headers, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    fn json_request(method: Method, uri: &str, body: Value) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    fn get_request(uri: &str) -> Request<Body> {
+        Request::builder().uri(uri).body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn notes_are_created_and_edited_by_path() {
+        let server = server().await;
+        let full = AccessScope::full;
+
+        let (status, _, body) = send(
+            &server,
+            full(),
+            json_request(
+                Method::POST,
+                "/directories/root/notes",
+                json!({ "name": "Todo", "content": "milk" }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let note: Value = serde_json::from_str(&body).unwrap();
+        let note_id = note["id"].as_str().unwrap().to_owned();
+
+        let (status, headers, _) =
+            send(&server, full(), get_request(&format!("/notes/{note_id}"))).await;
+        assert_eq!(status, StatusCode::OK);
+        let etag = headers[ETAG].to_str().unwrap().to_owned();
+
+        let put = |etag: &str, content: &str| {
+            Request::builder()
+                .method(Method::PUT)
+                .uri(format!("/notes/{note_id}/content"))
+                .header(IF_MATCH, etag)
+                .body(Body::from(content.to_owned()))
+                .unwrap()
+        };
+        let (status, _, _) = send(&server, full(), put(&etag, "milk\neggs")).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _, body) = send(&server, full(), put(&etag, "bread")).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let error: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(error["code"], "conflict");
+
+        let (status, _, body) = send(
+            &server,
+            full(),
+            get_request(&format!("/notes/{note_id}/content")),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "milk\neggs");
+
+        let (status, _, body) = send(&server, full(), get_request("/directories/root/notes")).await;
+        assert_eq!(status, StatusCode::OK);
+        let notes: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(notes[0]["name"], "Todo");
+    }
+
+    #[tokio::test]
+    async fn errors_map_to_http_statuses() {
+        let server = server().await;
+        let read_only = AccessScope {
+            access: Access::ReadOnly,
+            directories: Vec::new(),
+        };
+
+        let (status, _, _) = send(
+            &server,
+            read_only,
+            json_request(
+                Method::POST,
+                "/directories/root/directories",
+                json!({ "name": "Work" }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, _, body) = send(
+            &server,
+            AccessScope::full(),
+            get_request("/notes/missing/content"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let error: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(error["code"], "not_found");
+    }
+}
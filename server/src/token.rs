@@ -0,0 +1,91 @@
+use {
+    crate::auth::TokenRegistry,
+    clap::Subcommand,
+    color_eyre::{Result, eyre::eyre},
+    glues_core::backend::proxy::{Access, AccessScope},
+    std::path::PathBuf,
+};
+
+#[derive(Subcommand, Clone)]
+pub enum TokenCommand {
+    /// Mint a token and print it once; only its hash is stored
+    Create {
+        /// Unique name shown by `token list` and used by `token revoke`
+        #[arg(long)]
+        label: String,
+        /// Allow reading but not changing notes
+        #[arg(long)]
+        read_only: bool,
+        /// Limit the token to this directory's subtree (repeatable)
+        #[arg(long = "directory", value_name = "DIRECTORY_ID")]
+        directories: Vec<String>,
+    },
+    /// Show the tokens in the token file
+    List,
+    /// Remove a token; running servers reject it from the next request on
+    Revoke { label: String },
+}
+
+pub fn run(tokens: Option<PathBuf>, command: TokenCommand) -> Result<()> {
+    let path =
+        tokens.ok_or_else(|| eyre!("set --tokens or GLUES_SERVER_TOKENS to the token file"))?;
+    let mut registry = TokenRegistry::load(&path)?;
+
+    match command {
+        TokenCommand::Create {
+            label,
+            read_only,
+            directories,
+        } => {
+            let access = if read_only {
+                Access::ReadOnly
+            } else {
+                Access::ReadWrite
+            };
+            let token = registry.create(
+                label,
+                AccessScope {
+                    access,
+                    directories,
+                },
+            )?;
+            registry.save(&path)?;
+
+            println!("{token}");
+            eprintln!("store this token now; it cannot be shown again");
+        }
+        TokenCommand::List => {
+            for entry in &registry.tokens {
+                let access = match entry.scope.access {
+                    Access::ReadOnly => "read-only",
+                    Access::ReadWrite => "read-write",
+                };
+                let directories = match entry.scope.directories.as_slice() {
+                    [] => "all directories".to_owned(),
+                    directories => directories.join(", "),
+                };
+                let created_at = entry
+                    .created_at
+                    .map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_else(|| "-".to_owned());
+                let last_used_at = entry
+                    .last_used_at
+                    .map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_else(|| "never".to_owned());
+
+                println!(
+                    "{}\t{access}\t{directories}\tcreated {created_at}\tlast used {last_used_at}",
+                    entry.label
+                );
+            }
+        }
+        TokenCommand::Revoke { label } => {
+            registry.revoke(&label)?;
+            registry.save(&path)?;
+
+            println!("revoked '{label}'");
+        }
+    }
+
+    Ok(())
+}
@@ -3,14 +3,17 @@ use {
         App,
         config::{
             self, LAST_FILE_PATH, LAST_GIT_BRANCH, LAST_GIT_PATH, LAST_GIT_REMOTE,
-            LAST_MONGO_CONN_STR, LAST_MONGO_DB_NAME, LAST_PROXY_URL, LAST_REDB_PATH,
+            LAST_MARKDOWN_PATH, LAST_MONGO_CONN_STR, LAST_MONGO_DB_NAME, LAST_PROXY_URL,
+            LAST_REDB_PATH,
         },
         context::{ContextPrompt, InfoDialog, QuitMenu},
         logger::*,
         theme::THEME,
     },
     crate::input::{Input, KeyCode},
-    glues_core::{EntryEvent, Event, KeyEvent, NotebookEvent, NumKey, state::EntryState},
+    glues_core::{
+        EntryEvent, Event, KeyEvent, NotebookEvent, NumKey, data::Note, state::EntryState,
+    },
     ratatui::{style::Stylize, text::Line},
 };
 
@@ -50,6 +53,7 @@ pub enum TuiAction {
     Quit,
 
     OpenFile,
+    OpenMarkdown,
     OpenRedb,
     OpenGit(OpenGitStep),
     OpenMongo(OpenMongoStep),
@@ -61,6 +65,10 @@ pub enum TuiAction {
     AddDirectory,
     RenameDirectory,
     RemoveDirectory,
+    ImportFolder,
+    ShowNoteHistory,
+    ShowBacklinks,
+    AddNoteFromTemplate(Note),
     ShowInfo {
         title: String,
         lines: Vec<Line<'static>>,
@@ -315,6 +323,25 @@ impl App {
                     .log_unwrap();
                 self.handle_transition(transition).await;
             }
+            Action::Tui(TuiAction::OpenMarkdown) => {
+                let path = self
+                    .context
+                    .take_prompt_input()
+                    .log_expect("prompt must not be none");
+                if path.is_empty() {
+                    self.context.alert = Some("Path cannot be empty".to_string());
+                    return false;
+                }
+
+                config::update(LAST_MARKDOWN_PATH, &path).await;
+
+                let transition = self
+                    .glues
+                    .dispatch(EntryEvent::OpenMarkdown(path).into())
+                    .await
+                    .log_unwrap();
+                self.handle_transition(transition).await;
+            }
             Action::Tui(TuiAction::RenameNote) => {
                 let new_name = self
                     .context
@@ -340,6 +367,26 @@ impl App {
                     .log_unwrap();
                 self.handle_transition(transition).await;
             }
+            Action::Tui(TuiAction::ShowNoteHistory) => {
+                self.save().await;
+
+                let transition = self
+                    .glues
+                    .dispatch(NotebookEvent::ShowNoteRevisions.into())
+                    .await
+                    .log_unwrap();
+                self.handle_transition(transition).await;
+            }
+            Action::Tui(TuiAction::ShowBacklinks) => {
+                self.save().await;
+
+                let transition = self
+                    .glues
+                    .dispatch(NotebookEvent::ShowBacklinks.into())
+                    .await
+                    .log_unwrap();
+                self.handle_transition(transition).await;
+            }
             Action::Tui(TuiAction::AddNote) => {
                 let note_name = self
                     .context
@@ -357,6 +404,23 @@ impl App {
                     .log_unwrap();
                 self.handle_transition(transition).await;
             }
+            Action::Tui(TuiAction::AddNoteFromTemplate(template)) => {
+                let name = self
+                    .context
+                    .take_prompt_input()
+                    .log_expect("prompt must not be none");
+                if name.is_empty() {
+                    self.context.alert = Some("Note name cannot be empty".to_string());
+                    return false;
+                }
+
+                let transition = self
+                    .glues
+                    .dispatch(NotebookEvent::AddNoteFromTemplate { template, name }.into())
+                    .await
+                    .log_unwrap();
+                self.handle_transition(transition).await;
+            }
             Action::Tui(TuiAction::AddDirectory) => {
                 let directory_name = self
                     .context
@@ -391,6 +455,23 @@ impl App {
                     .log_unwrap();
                 self.handle_transition(transition).await;
             }
+            Action::Tui(TuiAction::ImportFolder) => {
+                let path = self
+                    .context
+                    .take_prompt_input()
+                    .log_expect("prompt must not be none");
+                if path.is_empty() {
+                    self.context.alert = Some("Path cannot be empty".to_string());
+                    return false;
+                }
+
+                let transition = self
+                    .glues
+                    .dispatch(NotebookEvent::ImportFolder(path).into())
+                    .await
+                    .log_unwrap();
+                self.handle_transition(transition).await;
+            }
             Action::Tui(TuiAction::RemoveDirectory) => {
                 let transition = self
                     .glues
@@ -451,6 +532,7 @@ fn to_event(input: Input) -> Option<KeyEvent> {
         KeyCode::Char('c') => KeyEvent::C,
         KeyCode::Char('d') => KeyEvent::D,
         KeyCode::Char('e') => KeyEvent::E,
+        KeyCode::Char('f') => KeyEvent::F,
         KeyCode::Char('g') => KeyEvent::G,
         KeyCode::Char('h') => KeyEvent::H,
         KeyCode::Char('i') => KeyEvent::I,
@@ -494,6 +576,7 @@ fn to_event(input: Input) -> Option<KeyEvent> {
         KeyCode::Char('^') => KeyEvent::Caret,
         KeyCode::Char('~') => KeyEvent::Tilde,
         KeyCode::Char('?') => KeyEvent::QuestionMark,
+        KeyCode::Char('/') => KeyEvent::Slash,
         KeyCode::Char('<') => KeyEvent::AngleBracketOpen,
         KeyCode::Char('>') => KeyEvent::AngleBracketClose,
         KeyCode::Char('.') => KeyEvent::Dot,
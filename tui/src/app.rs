@@ -1,6 +1,9 @@
 use {
-    crate::{context::Context, views},
-    glues_core::Glues,
+    crate::{
+        context::{Context, ContextState},
+        views,
+    },
+    glues_core::{Glues, NotebookEvent, backend::proxy::Change},
     ratatui::Frame,
 };
 
@@ -17,17 +20,29 @@ use {
             Arc, Mutex,
             atomic::{AtomicBool, Ordering},
         },
-        time::Duration,
+        time::{Duration, Instant},
+    },
+    tokio::{
+        self,
+        task::{self, JoinHandle},
     },
-    tokio::{self, task},
 };
 
+/// How long to wait before resubscribing after the change feed drops.
+const CHANGE_FEED_RETRY: Duration = Duration::from_secs(5);
+/// How often the notebook is compared with the storage when the backend
+/// has no change feed.
+const CHANGE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
 pub struct App {
     pub(crate) glues: Glues,
     pub(crate) context: Context,
     bg_transitions: Arc<Mutex<VecDeque<Transition>>>,
     sync_in_progress: Arc<AtomicBool>,
     sync_pending: Arc<AtomicBool>,
+    bg_changes: Arc<Mutex<Vec<Change>>>,
+    change_feed: Option<JoinHandle<()>>,
+    last_change_check: Instant,
 }
 
 impl Default for App {
@@ -43,6 +58,7 @@ impl App {
         let bg_transitions = Arc::new(Mutex::new(VecDeque::new()));
         let sync_in_progress = Arc::new(AtomicBool::new(false));
         let sync_pending = Arc::new(AtomicBool::new(false));
+        let bg_changes = Arc::new(Mutex::new(Vec::new()));
 
         Self {
             glues,
@@ -50,6 +66,9 @@ impl App {
             bg_transitions,
             sync_in_progress,
             sync_pending,
+            bg_changes,
+            change_feed: None,
+            last_change_check: Instant::now(),
         }
     }
 
@@ -85,6 +104,10 @@ impl App {
             }
 
             let raw_input = ct::event::read()?;
+            if matches!(raw_input, ct::event::Event::FocusGained) {
+                self.check_changes().await;
+                continue;
+            }
             let input: Input = raw_input.into();
 
             if !matches!(
@@ -153,9 +176,74 @@ impl App {
             self.handle_transition(transition).await;
         }
 
+        self.apply_remote_changes().await;
+        if self.last_change_check.elapsed() >= CHANGE_CHECK_INTERVAL {
+            self.check_changes().await;
+        }
         self.flush_pending_sync();
     }
 
+    async fn apply_remote_changes(&mut self) {
+        if !matches!(self.context.state, ContextState::Notebook) {
+            return;
+        }
+
+        let changes = std::mem::take(&mut *self.bg_changes.lock().log_unwrap());
+        if changes.is_empty() {
+            return;
+        }
+
+        let event = NotebookEvent::ApplyChanges(changes).into();
+        let transition = self.glues.dispatch(event).await.log_unwrap();
+        self.handle_transition(transition).await;
+    }
+
+    /// Picks up changes made outside of Glues, such as a Git pull or another
+    /// process writing the same storage. Backends with a change feed report
+    /// their changes already and are skipped.
+    pub async fn check_changes(&mut self) {
+        self.last_change_check = Instant::now();
+        if !matches!(self.context.state, ContextState::Notebook) || self.change_feed.is_some() {
+            return;
+        }
+
+        let event = NotebookEvent::CheckChanges.into();
+        match self.glues.dispatch(event).await {
+            Ok(transition) => self.handle_transition(transition).await,
+            Err(err) => {
+                crate::log!("[CheckChanges] {err}");
+            }
+        }
+    }
+
+    /// Subscribes to the backend's change feed, if it has one, so edits made
+    /// by other clients show up without a restart.
+    pub(crate) fn start_change_feed(&mut self) {
+        if let Some(handle) = self.change_feed.take() {
+            handle.abort();
+        }
+
+        let Some(feed) = self.glues.db.as_ref().and_then(|db| db.change_feed()) else {
+            return;
+        };
+        let queue = Arc::clone(&self.bg_changes);
+
+        self.change_feed = Some(tokio::spawn(async move {
+            loop {
+                let queue = Arc::clone(&queue);
+                let result = feed
+                    .clone()
+                    .run(move |change| queue.lock().log_unwrap().push(change))
+                    .await;
+                if let Err(err) = result {
+                    crate::log!("[ChangeFeed] {err}");
+                }
+
+                tokio::time::sleep(CHANGE_FEED_RETRY).await;
+            }
+        }));
+    }
+
     pub(crate) fn maybe_schedule_sync(&mut self) {
         if self.sync_in_progress.swap(true, Ordering::AcqRel) {
             self.sync_pending.store(true, Ordering::Release);
@@ -1,6 +1,10 @@
 use {
     crate::{App, config, logger, theme},
     color_eyre::Result,
+    ratatui::crossterm::{
+        event::{DisableFocusChange, EnableFocusChange},
+        execute,
+    },
 };
 
 pub async fn run() -> Result<()> {
@@ -18,7 +22,10 @@ pub async fn run() -> Result<()> {
     color_eyre::install()?;
 
     let terminal = ratatui::init();
+    // focus changes trigger a check for notes changed in other programs
+    execute!(std::io::stdout(), EnableFocusChange)?;
     let app_result = App::new().run(terminal).await;
+    execute!(std::io::stdout(), DisableFocusChange)?;
     ratatui::restore();
     app_result
 }
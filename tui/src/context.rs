@@ -1,22 +1,35 @@
+pub mod backlinks;
 pub mod entry;
 pub mod notebook;
+pub mod revisions;
+pub mod search;
+pub mod template;
 pub mod theme_selector;
+pub mod trash;
 
-use theme_selector::ThemeSelector;
 use {
     crate::{
         Action,
+        action::TuiAction,
         config::{self, LAST_THEME},
         input::{Input, KeyCode, KeyEvent},
         log,
         logger::*,
         theme,
     },
-    glues_core::transition::VimKeymapKind,
+    glues_core::{NotebookEvent, data::Note, transition::VimKeymapKind},
     ratatui::text::Line,
     std::time::SystemTime,
     tui_input::InputRequest,
 };
+use {
+    backlinks::BacklinksDialog,
+    revisions::RevisionDialog,
+    search::SearchDialog,
+    template::TemplateDialog,
+    theme_selector::ThemeSelector,
+    trash::{TrashDialog, TrashEntry},
+};
 pub use {entry::EntryContext, notebook::NotebookContext};
 
 pub enum ContextState {
@@ -91,6 +104,19 @@ impl InfoDialog {
     }
 }
 
+/// Shown when a save was refused because the note changed elsewhere;
+/// `content` holds the local edits, as the tab may be closed already.
+pub struct ConflictDialog {
+    pub note: Note,
+    pub content: String,
+}
+
+impl ConflictDialog {
+    pub fn new(note: Note, content: String) -> Self {
+        Self { note, content }
+    }
+}
+
 pub struct Context {
     pub entry: EntryContext,
     pub notebook: NotebookContext,
@@ -99,10 +125,16 @@ pub struct Context {
 
     pub quit_menu: Option<QuitMenu>,
     pub confirm: Option<(String, Action)>,
+    pub conflict: Option<ConflictDialog>,
     pub alert: Option<String>,
     pub info: Option<InfoDialog>,
     pub prompt: Option<ContextPrompt>,
     pub theme_selector: Option<ThemeSelector>,
+    pub search: Option<SearchDialog>,
+    pub trash: Option<TrashDialog>,
+    pub backlinks: Option<BacklinksDialog>,
+    pub template: Option<TemplateDialog>,
+    pub revisions: Option<RevisionDialog>,
     pub last_log: Option<(String, SystemTime)>,
 
     pub help: bool,
@@ -121,10 +153,16 @@ impl Default for Context {
             state: ContextState::Entry,
             quit_menu: None,
             confirm: None,
+            conflict: None,
             alert: None,
             info: None,
             prompt: None,
             theme_selector: None,
+            search: None,
+            trash: None,
+            backlinks: None,
+            template: None,
+            revisions: None,
             last_log: None,
 
             help: false,
@@ -207,6 +245,23 @@ impl Context {
                 }
                 _ => return Action::None,
             }
+        } else if let Some(conflict) = self.conflict.as_ref() {
+            let code = match input {
+                Input::Key(key) => key.code,
+                _ => return Action::None,
+            };
+
+            let note_id = conflict.note.id.clone();
+            let content = conflict.content.clone();
+            let event = match code {
+                KeyCode::Char('r') => NotebookEvent::ReloadNote(note_id),
+                KeyCode::Char('o') => NotebookEvent::OverwriteNoteContent { note_id, content },
+                KeyCode::Char('c') => NotebookEvent::SaveNoteCopy { note_id, content },
+                _ => return Action::None,
+            };
+
+            self.conflict = None;
+            return Action::Dispatch(event.into());
         } else if let Some(selector) = self.theme_selector.as_mut() {
             let key = match input {
                 Input::Key(key) => key,
@@ -243,6 +298,195 @@ impl Context {
                 }
                 _ => return Action::None,
             }
+        } else if let Some(search) = self.search.as_mut() {
+            let key = match input {
+                Input::Key(key) => key,
+                _ => return Action::None,
+            };
+
+            match key.code {
+                KeyCode::Esc => {
+                    self.search = None;
+                    return Action::None;
+                }
+                KeyCode::Enter => {
+                    let Some(result) = search.selected() else {
+                        return Action::None;
+                    };
+                    let note = result.note.clone();
+
+                    self.search = None;
+                    return Action::Dispatch(NotebookEvent::JumpToNote(note).into());
+                }
+                KeyCode::Down => {
+                    search.select_next();
+                    return Action::None;
+                }
+                KeyCode::Up => {
+                    search.select_previous();
+                    return Action::None;
+                }
+                KeyCode::Char('n') if key.modifiers.ctrl => {
+                    search.select_next();
+                    return Action::None;
+                }
+                KeyCode::Char('p') if key.modifiers.ctrl => {
+                    search.select_previous();
+                    return Action::None;
+                }
+                _ => {
+                    let changed = to_input_request(input)
+                        .and_then(|req| search.input.handle(req))
+                        .is_some_and(|changed| changed.value);
+                    if !changed {
+                        return Action::None;
+                    }
+
+                    let query = search.query().to_owned();
+                    return Action::Dispatch(NotebookEvent::SearchNotes(query).into());
+                }
+            }
+        } else if let Some(dialog) = self.revisions.as_mut() {
+            let key = match input {
+                Input::Key(key) => key,
+                _ => return Action::None,
+            };
+
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.revisions = None;
+                    return Action::None;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    dialog.select_next();
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    dialog.select_previous();
+                }
+                KeyCode::Char('r') | KeyCode::Enter => {
+                    let Some(revision) = dialog.selected() else {
+                        return Action::None;
+                    };
+                    let event = NotebookEvent::RestoreNoteRevision {
+                        note_id: dialog.note.id.clone(),
+                        revision_id: revision.id.clone(),
+                    };
+
+                    return TuiAction::SaveAndConfirm {
+                        message: format!("Restore revision from {}?", revision.created_at),
+                        action: Box::new(Action::Dispatch(event.into())),
+                    }
+                    .into();
+                }
+                _ => return Action::None,
+            }
+
+            return match dialog.pending() {
+                Some(event) => Action::Dispatch(event.into()),
+                None => Action::None,
+            };
+        } else if let Some(trash) = self.trash.as_mut() {
+            let key = match input {
+                Input::Key(key) => key,
+                _ => return Action::None,
+            };
+
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.trash = None;
+                    return Action::None;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    trash.select_next();
+                    return Action::None;
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    trash.select_previous();
+                    return Action::None;
+                }
+                KeyCode::Char('r') | KeyCode::Enter => {
+                    let event = match trash.selected() {
+                        Some(TrashEntry::Directory(directory)) => {
+                            NotebookEvent::RestoreDirectory(directory.id.clone())
+                        }
+                        Some(TrashEntry::Note(note)) => NotebookEvent::RestoreNote(note.id.clone()),
+                        None => return Action::None,
+                    };
+
+                    return Action::Dispatch(event.into());
+                }
+                KeyCode::Char('D') if !trash.is_empty() => {
+                    return TuiAction::Confirm {
+                        message: "Confirm to permanently delete everything in trash?".to_owned(),
+                        action: Box::new(Action::Dispatch(NotebookEvent::EmptyTrash.into())),
+                    }
+                    .into();
+                }
+                _ => return Action::None,
+            }
+        } else if let Some(dialog) = self.template.as_mut() {
+            let key = match input {
+                Input::Key(key) => key,
+                _ => return Action::None,
+            };
+
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.template = None;
+                    return Action::None;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    dialog.select_next();
+                    return Action::None;
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    dialog.select_previous();
+                    return Action::None;
+                }
+                KeyCode::Enter => {
+                    let Some(template) = dialog.selected().cloned() else {
+                        return Action::None;
+                    };
+
+                    self.template = None;
+                    return TuiAction::Prompt {
+                        message: vec![Line::raw("Enter note name:")],
+                        action: Box::new(TuiAction::AddNoteFromTemplate(template).into()),
+                        default: None,
+                    }
+                    .into();
+                }
+                _ => return Action::None,
+            }
+        } else if let Some(backlinks) = self.backlinks.as_mut() {
+            let key = match input {
+                Input::Key(key) => key,
+                _ => return Action::None,
+            };
+
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.backlinks = None;
+                    return Action::None;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    backlinks.select_next();
+                    return Action::None;
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    backlinks.select_previous();
+                    return Action::None;
+                }
+                KeyCode::Enter => {
+                    let Some(note) = backlinks.selected().cloned() else {
+                        return Action::None;
+                    };
+
+                    self.backlinks = None;
+                    return Action::Dispatch(NotebookEvent::JumpToNote(note).into());
+                }
+                _ => return Action::None,
+            }
         } else if let Some(prompt) = self.prompt.as_ref() {
             match input {
                 Input::Key(KeyEvent {
@@ -0,0 +1,37 @@
+use {glues_core::data::Note, ratatui::widgets::ListState};
+
+pub struct BacklinksDialog {
+    pub note: Note,
+    pub notes: Vec<Note>,
+    pub list_state: ListState,
+}
+
+impl BacklinksDialog {
+    pub fn new(note: Note, notes: Vec<Note>) -> Self {
+        let selected = (!notes.is_empty()).then_some(0);
+
+        Self {
+            note,
+            notes,
+            list_state: ListState::default().with_selected(selected),
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.notes.is_empty() {
+            self.list_state.select_next();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.notes.is_empty() {
+            self.list_state.select_previous();
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Note> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.notes.get(i.min(self.notes.len().saturating_sub(1))))
+    }
+}
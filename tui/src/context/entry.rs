@@ -2,8 +2,8 @@ use {
     crate::{
         action::{Action, OpenGitStep, OpenMongoStep, OpenProxyStep, TuiAction},
         config::{
-            self, LAST_FILE_PATH, LAST_GIT_PATH, LAST_MONGO_CONN_STR, LAST_PROXY_URL,
-            LAST_REDB_PATH,
+            self, LAST_FILE_PATH, LAST_GIT_PATH, LAST_MARKDOWN_PATH, LAST_MONGO_CONN_STR,
+            LAST_PROXY_URL, LAST_REDB_PATH,
         },
         input::KeyCode,
         logger::*,
@@ -15,6 +15,7 @@ use {
 
 pub const INSTANT: &str = "[i] Instant";
 pub const FILE: &str = "[l] Local";
+pub const MARKDOWN: &str = "[d] Markdown";
 pub const REDB: &str = "[r] redb";
 pub const GIT: &str = "[g] Git";
 pub const MONGO: &str = "[m] MongoDB";
@@ -23,8 +24,8 @@ pub const HELP: &str = "[h] Help";
 pub const THEME_MENU: &str = "[t] Theme";
 pub const QUIT: &str = "[q] Quit";
 
-pub const MENU_ITEMS: [&str; 9] = [
-    INSTANT, FILE, REDB, GIT, MONGO, PROXY, HELP, THEME_MENU, QUIT,
+pub const MENU_ITEMS: [&str; 10] = [
+    INSTANT, FILE, MARKDOWN, REDB, GIT, MONGO, PROXY, HELP, THEME_MENU, QUIT,
 ];
 
 pub struct EntryContext {
@@ -116,6 +117,7 @@ impl EntryContext {
             }
             KeyCode::Char('i') => EntryEvent::OpenMemory.into(),
             KeyCode::Char('l') => open(LAST_FILE_PATH, TuiAction::OpenFile).await,
+            KeyCode::Char('d') => open(LAST_MARKDOWN_PATH, TuiAction::OpenMarkdown).await,
             KeyCode::Char('r') => open_redb().await,
             KeyCode::Char('g') => open_git().await,
             KeyCode::Char('m') => open_mongo().await,
@@ -131,6 +133,7 @@ impl EntryContext {
                 match MENU_ITEMS[i] {
                     INSTANT => EntryEvent::OpenMemory.into(),
                     FILE => open(LAST_FILE_PATH, TuiAction::OpenFile).await,
+                    MARKDOWN => open(LAST_MARKDOWN_PATH, TuiAction::OpenMarkdown).await,
                     REDB => open_redb().await,
                     GIT => open_git().await,
                     MONGO => open_mongo().await,
@@ -30,22 +30,35 @@ pub use tree_item::{TreeItem, TreeItemKind};
 pub const REMOVE_NOTE: &str = "Remove note";
 pub const RENAME_NOTE: &str = "Rename note";
 pub const SHOW_NOTE_INFO: &str = "Show note info";
+pub const SHOW_NOTE_HISTORY: &str = "Show history";
+pub const SHOW_BACKLINKS: &str = "Show backlinks";
 
 pub const ADD_NOTE: &str = "Add note";
+pub const ADD_NOTE_FROM_TEMPLATE: &str = "Add note from template";
 pub const ADD_DIRECTORY: &str = "Add directory";
 pub const RENAME_DIRECTORY: &str = "Rename directory";
 pub const REMOVE_DIRECTORY: &str = "Remove directory";
 pub const SHOW_DIRECTORY_INFO: &str = "Show directory info";
+pub const IMPORT_FOLDER: &str = "Import folder";
 
 pub const CLOSE: &str = "Close";
 
-pub const NOTE_ACTIONS: [&str; 4] = [RENAME_NOTE, REMOVE_NOTE, SHOW_NOTE_INFO, CLOSE];
-pub const DIRECTORY_ACTIONS: [&str; 6] = [
+pub const NOTE_ACTIONS: [&str; 6] = [
+    RENAME_NOTE,
+    REMOVE_NOTE,
+    SHOW_NOTE_INFO,
+    SHOW_NOTE_HISTORY,
+    SHOW_BACKLINKS,
+    CLOSE,
+];
+pub const DIRECTORY_ACTIONS: [&str; 8] = [
     ADD_NOTE,
+    ADD_NOTE_FROM_TEMPLATE,
     ADD_DIRECTORY,
     RENAME_DIRECTORY,
     REMOVE_DIRECTORY,
     SHOW_DIRECTORY_INFO,
+    IMPORT_FOLDER,
     CLOSE,
 ];
 
@@ -216,6 +229,20 @@ impl NotebookContext {
         }
     }
 
+    /// Whether the editor of the note holds changes not saved yet; typing in
+    /// insert mode only marks it dirty when leaving the mode.
+    pub fn has_local_edits(&self, note_id: &NoteId) -> bool {
+        let Some(item) = self.editors.get(note_id) else {
+            return false;
+        };
+        let current = self
+            .tab_index
+            .and_then(|i| self.tabs.get(i))
+            .is_some_and(|tab| &tab.note.id == note_id);
+
+        item.dirty || (current && self.state == ContextState::EditorInsertMode)
+    }
+
     pub fn mark_clean(&mut self, note_id: &NoteId) {
         if let Some(editor_item) = self.editors.get_mut(note_id) {
             editor_item.dirty = false;
@@ -631,6 +658,8 @@ impl NotebookContext {
                         action: Box::new(TuiAction::RemoveNote.into()),
                     }
                     .into(),
+                    SHOW_NOTE_HISTORY => TuiAction::ShowNoteHistory.into(),
+                    SHOW_BACKLINKS => TuiAction::ShowBacklinks.into(),
                     SHOW_NOTE_INFO => {
                         let note = match &self.selected().kind {
                             TreeItemKind::Note { note } => note,
@@ -700,6 +729,7 @@ impl NotebookContext {
                         default: None,
                     }
                     .into(),
+                    ADD_NOTE_FROM_TEMPLATE => Action::Dispatch(NotebookEvent::ShowTemplates.into()),
                     ADD_DIRECTORY => TuiAction::Prompt {
                         message: vec![Line::raw("Enter directory name:")],
                         action: Box::new(TuiAction::AddDirectory.into()),
@@ -717,6 +747,15 @@ impl NotebookContext {
                         action: Box::new(TuiAction::RemoveDirectory.into()),
                     }
                     .into(),
+                    IMPORT_FOLDER => TuiAction::Prompt {
+                        message: vec![
+                            Line::raw("Enter the path of a folder to import."),
+                            Line::raw("Markdown and text files become notes."),
+                        ],
+                        action: Box::new(TuiAction::ImportFolder.into()),
+                        default: None,
+                    }
+                    .into(),
                     SHOW_DIRECTORY_INFO => {
                         let directory = match &self.selected().kind {
                             TreeItemKind::Directory { directory, .. } => directory,
@@ -0,0 +1,71 @@
+use {
+    glues_core::{
+        NotebookEvent,
+        data::{Note, NoteRevision},
+        types::RevisionId,
+    },
+    ratatui::widgets::ListState,
+    std::collections::HashMap,
+};
+
+pub struct RevisionDialog {
+    pub note: Note,
+    pub current: String,
+    pub revisions: Vec<NoteRevision>,
+    pub contents: HashMap<RevisionId, String>,
+    pub list_state: ListState,
+}
+
+impl RevisionDialog {
+    pub fn new(note: Note, current: String, revisions: Vec<NoteRevision>) -> Self {
+        let selected = (!revisions.is_empty()).then_some(0);
+
+        Self {
+            note,
+            current,
+            revisions,
+            contents: HashMap::new(),
+            list_state: ListState::default().with_selected(selected),
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.revisions.is_empty() {
+            self.list_state.select_next();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.revisions.is_empty() {
+            self.list_state.select_previous();
+        }
+    }
+
+    pub fn selected(&self) -> Option<&NoteRevision> {
+        self.list_state.selected().and_then(|i| {
+            self.revisions
+                .get(i.min(self.revisions.len().saturating_sub(1)))
+        })
+    }
+
+    pub fn selected_content(&self) -> Option<&str> {
+        self.selected()
+            .and_then(|revision| self.contents.get(&revision.id))
+            .map(String::as_str)
+    }
+
+    /// Returns the selected revision when its content still has to be loaded.
+    /// Event loading the selected revision when its content is not fetched yet.
+    pub fn pending(&self) -> Option<NotebookEvent> {
+        self.selected()
+            .filter(|revision| !self.contents.contains_key(&revision.id))
+            .map(|revision| NotebookEvent::LoadNoteRevision {
+                note_id: revision.note_id.clone(),
+                revision_id: revision.id.clone(),
+            })
+    }
+
+    pub fn set_content(&mut self, revision_id: RevisionId, content: String) {
+        self.contents.insert(revision_id, content);
+    }
+}
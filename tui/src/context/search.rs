@@ -0,0 +1,53 @@
+use {glues_core::data::NoteSearchResult, ratatui::widgets::ListState};
+
+pub struct SearchDialog {
+    pub input: tui_input::Input,
+    pub results: Vec<NoteSearchResult>,
+    pub list_state: ListState,
+}
+
+impl SearchDialog {
+    pub fn new() -> Self {
+        Self {
+            input: tui_input::Input::default(),
+            results: Vec::new(),
+            list_state: ListState::default(),
+        }
+    }
+
+    pub fn query(&self) -> &str {
+        self.input.value()
+    }
+
+    pub fn set_results(&mut self, results: Vec<NoteSearchResult>) {
+        let selected = (!results.is_empty()).then_some(0);
+
+        self.results = results;
+        self.list_state.select(selected);
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.results.is_empty() {
+            self.list_state.select_next();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.results.is_empty() {
+            self.list_state.select_previous();
+        }
+    }
+
+    pub fn selected(&self) -> Option<&NoteSearchResult> {
+        self.list_state.selected().and_then(|i| {
+            self.results
+                .get(i.min(self.results.len().saturating_sub(1)))
+        })
+    }
+}
+
+impl Default for SearchDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
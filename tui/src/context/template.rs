@@ -0,0 +1,36 @@
+use {glues_core::data::Note, ratatui::widgets::ListState};
+
+pub struct TemplateDialog {
+    pub templates: Vec<Note>,
+    pub list_state: ListState,
+}
+
+impl TemplateDialog {
+    pub fn new(templates: Vec<Note>) -> Self {
+        let selected = (!templates.is_empty()).then_some(0);
+
+        Self {
+            templates,
+            list_state: ListState::default().with_selected(selected),
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.templates.is_empty() {
+            self.list_state.select_next();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.templates.is_empty() {
+            self.list_state.select_previous();
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Note> {
+        self.list_state.selected().and_then(|i| {
+            self.templates
+                .get(i.min(self.templates.len().saturating_sub(1)))
+        })
+    }
+}
@@ -0,0 +1,71 @@
+use {
+    glues_core::data::{Directory, Note, Trash},
+    ratatui::widgets::ListState,
+};
+
+pub enum TrashEntry<'a> {
+    Directory(&'a Directory),
+    Note(&'a Note),
+}
+
+pub struct TrashDialog {
+    pub trash: Trash,
+    pub list_state: ListState,
+}
+
+impl TrashDialog {
+    pub fn new(trash: Trash) -> Self {
+        let mut dialog = Self {
+            trash: Trash::default(),
+            list_state: ListState::default(),
+        };
+        dialog.set_trash(trash);
+        dialog
+    }
+
+    pub fn set_trash(&mut self, trash: Trash) {
+        self.trash = trash;
+
+        let len = self.len();
+        let selected = match self.list_state.selected() {
+            _ if len == 0 => None,
+            Some(i) => Some(i.min(len - 1)),
+            None => Some(0),
+        };
+        self.list_state.select(selected);
+    }
+
+    pub fn len(&self) -> usize {
+        self.trash.directories.len() + self.trash.notes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = TrashEntry<'_>> {
+        self.trash
+            .directories
+            .iter()
+            .map(TrashEntry::Directory)
+            .chain(self.trash.notes.iter().map(TrashEntry::Note))
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.is_empty() {
+            self.list_state.select_next();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.is_empty() {
+            self.list_state.select_previous();
+        }
+    }
+
+    pub fn selected(&self) -> Option<TrashEntry<'_>> {
+        let i = self.list_state.selected()?;
+
+        self.entries().nth(i.min(self.len().saturating_sub(1)))
+    }
+}
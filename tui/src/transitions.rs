@@ -50,7 +50,10 @@ fn transition_requires_sync(transition: &Transition) -> bool {
                 | NoteTreeTransition::RemoveDirectory { .. }
                 | NoteTreeTransition::AddNote(_)
                 | NoteTreeTransition::AddDirectory(_)
+                | NoteTreeTransition::ImportFolder { .. }
                 | NoteTreeTransition::MoveMode(MoveModeTransition::Commit)
-        )) | Transition::Notebook(NotebookTransition::UpdateNoteContent(_))
+        )) | Transition::Notebook(
+            NotebookTransition::UpdateNoteContent(_) | NotebookTransition::NoteCopySaved { .. }
+        )
     )
 }
@@ -15,6 +15,7 @@ impl App {
                 let NotebookState { root, .. } = self.glues.state.get_inner().log_unwrap();
                 self.context.state = ContextState::Notebook;
                 self.context.notebook.update_items(root);
+                self.start_change_feed();
             }
             EntryTransition::Inedible(event) => {
                 log!("Inedible event: {event}");
@@ -6,7 +6,10 @@ mod textarea;
 use {
     crate::{
         App,
-        context::{self},
+        context::{
+            self, ConflictDialog, backlinks::BacklinksDialog, revisions::RevisionDialog,
+            search::SearchDialog, template::TemplateDialog, trash::TrashDialog,
+        },
         logger::*,
     },
     glues_core::{
@@ -16,19 +19,23 @@ use {
             notebook::{EditorState, InnerState, NoteTreeState, VimNormalState},
         },
         transition::NotebookTransition,
+        types::NoteId,
     },
+    std::time::SystemTime,
 };
 
 impl App {
     pub(super) async fn handle_notebook_transition(&mut self, transition: NotebookTransition) {
         use context::notebook::ContextState;
 
+        let notebook_state: &NotebookState = self.glues.state.get_inner().log_unwrap();
         let NotebookState {
             root,
             inner_state,
             tab_index,
+            tabs,
             ..
-        } = self.glues.state.get_inner().log_unwrap();
+        } = notebook_state;
         let new_state = match inner_state {
             InnerState::NoteTree(
                 NoteTreeState::NoteSelected | NoteTreeState::DirectorySelected,
@@ -69,6 +76,126 @@ impl App {
             NotebookTransition::UpdateNoteContent(note_id) => {
                 self.context.notebook.mark_clean(&note_id);
             }
+            NotebookTransition::ShowSearchDialog => {
+                self.context.search = Some(SearchDialog::new());
+            }
+            NotebookTransition::SearchResults(results) => {
+                if let Some(search) = self.context.search.as_mut() {
+                    search.set_results(results);
+                }
+            }
+            NotebookTransition::ShowNoteRevisions {
+                note,
+                content,
+                revisions,
+            } => {
+                let dialog = RevisionDialog::new(note, content, revisions);
+                let pending = dialog.pending();
+                self.context.revisions = Some(dialog);
+
+                if let Some(event) = pending {
+                    let transition = self.glues.dispatch(event.into()).await.log_unwrap();
+                    self.handle_transition(transition).await;
+                }
+            }
+            NotebookTransition::ShowTemplates(templates) => {
+                self.context.template = Some(TemplateDialog::new(templates));
+            }
+            NotebookTransition::ShowBacklinks { note, notes } => {
+                self.context.backlinks = Some(BacklinksDialog::new(note, notes));
+            }
+            NotebookTransition::NoteRevisionLoaded { id, content } => {
+                if let Some(dialog) = self.context.revisions.as_mut() {
+                    dialog.set_content(id, content);
+                }
+            }
+            NotebookTransition::NoteRevisionRestored { note_id, content } => {
+                if self.context.notebook.editors.contains_key(&note_id) {
+                    self.context.notebook.open_note(note_id, content);
+                }
+
+                self.context.revisions = None;
+            }
+            NotebookTransition::ShowTrash(trash) => match self.context.trash.as_mut() {
+                Some(dialog) => dialog.set_trash(trash),
+                None => self.context.trash = Some(TrashDialog::new(trash)),
+            },
+            NotebookTransition::RestoreFromTrash { id, trash } => {
+                self.context.notebook.update_items(root);
+                self.context.notebook.select_item(&id);
+
+                if let Some(dialog) = self.context.trash.as_mut() {
+                    dialog.set_trash(trash);
+                }
+            }
+            NotebookTransition::RemoteChanges { notes } => {
+                self.context.notebook.tabs = tabs.clone();
+                self.context.notebook.update_items(root);
+                if let Ok(id) = notebook_state.get_selected_id() {
+                    self.context.notebook.select_item(id);
+                }
+
+                // unsaved local edits stay until the user resolves the conflict
+                for note_id in notes {
+                    let Some(item) = self.context.notebook.editors.get(&note_id) else {
+                        continue;
+                    };
+                    if self.context.notebook.has_local_edits(&note_id) {
+                        let note = self
+                            .context
+                            .notebook
+                            .tabs
+                            .iter()
+                            .find(|tab| tab.note.id == note_id);
+                        if let Some(tab) = note
+                            && self.context.conflict.is_none()
+                        {
+                            let content = item.editor.lines.to_string();
+                            self.context.conflict =
+                                Some(ConflictDialog::new(tab.note.clone(), content));
+                        }
+                        continue;
+                    }
+
+                    let event = NotebookEvent::ReloadNote(note_id).into();
+                    let transition = self.glues.dispatch(event).await.log_unwrap();
+                    self.handle_transition(transition).await;
+                }
+            }
+            NotebookTransition::NoteConflict { note, message } => {
+                log!("[Conflict] {message}");
+                let content = self
+                    .context
+                    .notebook
+                    .editors
+                    .get(&note.id)
+                    .map(|item| item.editor.lines.to_string());
+                if let Some(content) = content
+                    && self.context.conflict.is_none()
+                {
+                    self.context.conflict = Some(ConflictDialog::new(note, content));
+                }
+            }
+            NotebookTransition::NoteReloaded { note_id, content } => {
+                self.context.notebook.tabs = tabs.clone();
+                self.reload_editor(note_id, content);
+            }
+            NotebookTransition::NoteCopySaved {
+                copy,
+                note_id,
+                content,
+            } => {
+                self.context.notebook.tabs = tabs.clone();
+                self.context.notebook.update_items(root);
+                if let Ok(id) = notebook_state.get_selected_id() {
+                    self.context.notebook.select_item(id);
+                }
+                self.reload_editor(note_id, content);
+
+                let message = format!("Your changes were saved as '{}'", copy.name);
+                log!("{message}");
+                self.context.last_log = Some((message, SystemTime::now()));
+            }
             NotebookTransition::BrowseNoteTree => {}
             NotebookTransition::FocusEditor => {
                 let note_id = self
@@ -92,6 +219,13 @@ impl App {
                 self.handle_visual_mode_transition(transition).await;
             }
             NotebookTransition::Alert(message) => {
+                // e.g. a rename refused by a conflict, after the tree was reloaded
+                self.context.notebook.tabs = tabs.clone();
+                self.context.notebook.update_items(root);
+                if let Ok(id) = notebook_state.get_selected_id() {
+                    self.context.notebook.select_item(id);
+                }
+
                 log!("[Alert] {message}");
                 self.context.alert = Some(message);
             }
@@ -99,6 +233,20 @@ impl App {
         }
     }
 
+    /// Shows `content` in the open editor of the note, keeping the cursor
+    /// when nothing changed.
+    fn reload_editor(&mut self, note_id: NoteId, content: String) {
+        let Some(item) = self.context.notebook.editors.get_mut(&note_id) else {
+            return;
+        };
+
+        if item.editor.lines.to_string().trim_end() == content.trim_end() {
+            item.dirty = false;
+        } else {
+            self.context.notebook.open_note(note_id, content);
+        }
+    }
+
     pub(crate) async fn save(&mut self) {
         let mut transitions = vec![];
 
@@ -106,6 +254,12 @@ impl App {
             if !item.dirty {
                 continue;
             }
+            // waits for the user to resolve the conflict
+            if let Some(conflict) = self.context.conflict.as_ref()
+                && &conflict.note.id == note_id
+            {
+                continue;
+            }
 
             let event = NotebookEvent::UpdateNoteContent {
                 note_id: note_id.clone(),
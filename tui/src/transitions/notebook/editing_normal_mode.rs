@@ -14,6 +14,7 @@ use {
         },
     },
     glues_core::{
+        NotebookEvent, link,
         state::{GetInner, NotebookState},
         transition::NormalModeTransition,
     },
@@ -59,6 +60,44 @@ impl App {
             ToggleBrowser => {
                 self.context.notebook.show_browser = !self.context.notebook.show_browser;
             }
+            FollowLink => {
+                let editor = self.context.notebook.get_editor();
+                let line = editor
+                    .lines
+                    .get(RowIndex::new(editor.cursor.row))
+                    .map(|line| line.iter().collect::<String>())
+                    .unwrap_or_default();
+                let Some(link) = link::link_at(&line, editor.cursor.col) else {
+                    self.context.alert = Some("No link under the cursor".to_owned());
+                    return;
+                };
+
+                self.save().await;
+                let transition = self
+                    .glues
+                    .dispatch(NotebookEvent::FollowLink(link.target).into())
+                    .await
+                    .log_unwrap();
+                self.handle_transition(transition).await;
+            }
+            OpenJournal(day) => {
+                self.save().await;
+                let transition = self
+                    .glues
+                    .dispatch(NotebookEvent::OpenJournal(day).into())
+                    .await
+                    .log_unwrap();
+                self.handle_transition(transition).await;
+            }
+            ShowBacklinks => {
+                self.save().await;
+                let transition = self
+                    .glues
+                    .dispatch(NotebookEvent::ShowBacklinks.into())
+                    .await
+                    .log_unwrap();
+                self.handle_transition(transition).await;
+            }
             ToggleSyntaxHighlight => {
                 self.context.notebook.show_syntax_highlight =
                     !self.context.notebook.show_syntax_highlight;
@@ -1,7 +1,10 @@
 use {
     crate::{
         App,
-        context::notebook::{TreeItem, TreeItemKind},
+        context::{
+            InfoDialog,
+            notebook::{TreeItem, TreeItemKind},
+        },
         logger::*,
     },
     glues_core::{
@@ -10,6 +13,7 @@ use {
         state::{GetInner, NotebookState},
         transition::{MoveModeTransition, NoteTreeTransition},
     },
+    ratatui::text::Line,
 };
 
 impl App {
@@ -27,6 +31,8 @@ impl App {
                 self.context.notebook.select_item(&id);
             }
             NoteTreeTransition::OpenNote { note, content, .. } => {
+                self.context.notebook.update_items(root);
+                self.context.notebook.select_item(&note.id);
                 self.context.notebook.open_note(note.id, content);
                 self.context.notebook.tabs = tabs.clone();
                 self.context.notebook.apply_yank();
@@ -85,6 +91,22 @@ impl App {
                 self.context.notebook.update_items(root);
                 self.context.notebook.select_item(&id);
             }
+            NoteTreeTransition::ImportFolder { directory, summary } => {
+                self.context.notebook.update_items(root);
+                self.context.notebook.select_item(&directory.id);
+
+                let mut lines = vec![Line::raw(format!(
+                    "Imported {} directories and {} notes into '{}'.",
+                    summary.directories, summary.notes, directory.name
+                ))];
+                if summary.skipped > 0 {
+                    lines.push(Line::raw(format!(
+                        "Skipped {} unsupported files.",
+                        summary.skipped
+                    )));
+                }
+                self.context.info = Some(InfoDialog::new("Import folder", lines));
+            }
             NoteTreeTransition::MoveMode(transition) => {
                 self.handle_move_mode_transition(transition).await;
             }
@@ -102,6 +124,15 @@ impl App {
                 let event = get_select_event(selected);
                 self.glues.dispatch(event).await.log_unwrap();
             }
+            NoteTreeTransition::OpenJournal(day) => {
+                self.save().await;
+                let transition = self
+                    .glues
+                    .dispatch(NotebookEvent::OpenJournal(day).into())
+                    .await
+                    .log_unwrap();
+                self.handle_transition(transition).await;
+            }
             NoteTreeTransition::SelectFirst => {
                 self.context.notebook.select_first();
 
@@ -20,7 +20,7 @@ pub fn draw(frame: &mut Frame, area: Rect, context: &mut EntryContext) {
     let [area] = Layout::horizontal([Length(38)])
         .flex(Flex::Center)
         .areas(area);
-    let [title_area, area] = Layout::vertical([Length(9), Length(13)])
+    let [title_area, area] = Layout::vertical([Length(9), Length(14)])
         .flex(Flex::Center)
         .areas(area);
 
@@ -1,5 +1,7 @@
 mod alert;
+mod backlinks;
 mod confirm;
+mod conflict;
 mod directory_actions;
 mod editor_keymap;
 mod help;
@@ -8,7 +10,11 @@ mod keymap;
 mod note_actions;
 mod prompt;
 mod quit_menu;
+mod revisions;
+mod search;
+mod template;
 mod theme;
+mod trash;
 mod vim_keymap;
 
 use {
@@ -46,9 +52,27 @@ pub fn draw(frame: &mut Frame, state: &State, context: &mut Context) {
     } else if context.confirm.is_some() {
         confirm::draw(frame, context);
         return;
+    } else if context.conflict.is_some() {
+        conflict::draw(frame, context);
+        return;
     } else if context.theme_selector.is_some() {
         theme::draw(frame, context);
         return;
+    } else if context.search.is_some() {
+        search::draw(frame, context);
+        return;
+    } else if context.revisions.is_some() {
+        revisions::draw(frame, context);
+        return;
+    } else if context.trash.is_some() {
+        trash::draw(frame, context);
+        return;
+    } else if context.template.is_some() {
+        template::draw(frame, context);
+        return;
+    } else if context.backlinks.is_some() {
+        backlinks::draw(frame, context);
+        return;
     } else if context.prompt.is_some() {
         prompt::draw(frame, context);
         return;
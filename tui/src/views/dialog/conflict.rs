@@ -0,0 +1,68 @@
+use {
+    crate::{context::Context, logger::*, theme::THEME},
+    ratatui::{
+        Frame,
+        layout::{Alignment, Constraint::Length, Flex, Layout},
+        style::{Style, Stylize},
+        text::{Line, Span},
+        widgets::{Block, Clear, Padding, Paragraph, Wrap},
+    },
+};
+
+pub fn draw(frame: &mut Frame, context: &mut Context) {
+    let [area] = Layout::horizontal([Length(56)])
+        .flex(Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::vertical([Length(11)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    let block = Block::bordered()
+        .bg(THEME.surface)
+        .fg(THEME.text)
+        .padding(Padding::new(2, 2, 1, 1))
+        .title(Line::from("Conflict").centered());
+    let inner_area = block.inner(area);
+    let [message_area, options_area] = Layout::vertical([Length(4), Length(3)])
+        .flex(Flex::SpaceBetween)
+        .areas(inner_area);
+
+    let conflict = context
+        .conflict
+        .as_ref()
+        .log_expect("conflict must be some");
+
+    let message = format!(
+        "'{}' was changed elsewhere while you were editing it. Your changes are not saved yet.",
+        conflict.note.name
+    );
+    let message = Paragraph::new(message)
+        .wrap(Wrap { trim: true })
+        .style(Style::default())
+        .alignment(Alignment::Left);
+
+    let key_style = Style::default().fg(THEME.text);
+    let hint_style = Style::default().fg(THEME.text_secondary);
+    let option = |key: &'static str, hint: &'static str| {
+        Line::from(vec![
+            Span::styled(key, key_style),
+            Span::raw(" "),
+            Span::styled(hint, hint_style),
+        ])
+    };
+
+    let lines = vec![
+        option("[r]", "Reload, dropping your changes"),
+        option("[o]", "Overwrite with your changes"),
+        option("[c]", "Save your changes as a copy"),
+    ];
+    let options = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .style(Style::default())
+        .alignment(Alignment::Left);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(message, message_area);
+    frame.render_widget(options, options_area);
+}
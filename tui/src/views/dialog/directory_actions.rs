@@ -13,10 +13,10 @@ use {
 };
 
 pub fn draw(frame: &mut Frame, context: &mut NotebookContext) {
-    let [area] = Layout::horizontal([Length(28)])
+    let [area] = Layout::horizontal([Length(32)])
         .flex(Flex::Center)
         .areas(frame.area());
-    let [area] = Layout::vertical([Length(10)])
+    let [area] = Layout::vertical([Length(12)])
         .flex(Flex::Center)
         .areas(area);
 
@@ -13,7 +13,7 @@ pub fn draw(frame: &mut Frame) {
     let [area] = Layout::horizontal([Length(120)])
         .flex(Flex::Center)
         .areas(frame.area());
-    let [area] = Layout::vertical([Length(37)])
+    let [area] = Layout::vertical([Length(40)])
         .flex(Flex::Center)
         .areas(area);
 
@@ -24,7 +24,7 @@ pub fn draw(frame: &mut Frame) {
         .title(Line::from("Help").centered());
 
     let inner_area = block.inner(area);
-    let [message_area, control_area] = Layout::vertical([Length(30), Length(1)])
+    let [message_area, control_area] = Layout::vertical([Length(33), Length(1)])
         .flex(Flex::SpaceBetween)
         .areas(inner_area);
 
@@ -43,6 +43,11 @@ pub fn draw(frame: &mut Frame) {
             "This is the default option for users who prefer a simple, file-based approach without any remote synchronization.",
         ),
         Line::raw(""),
+        Line::from("Markdown".fg(THEME.accent_text).bg(THEME.accent)),
+        Line::raw(
+            "Every directory is a folder and every note a `.md` file, so other editors and tools can work on them too.",
+        ),
+        Line::raw(""),
         Line::from("redb".fg(THEME.accent_text).bg(THEME.accent)),
         Line::raw("Persist everything inside a single redb database file."),
         Line::raw("Provide a path and Glues will create or reuse the file for portable storage."),
@@ -0,0 +1,97 @@
+use {
+    crate::{context::Context, logger::*, theme::THEME},
+    ratatui::{
+        Frame,
+        layout::{
+            Constraint::{Length, Percentage},
+            Flex, Layout,
+        },
+        style::{Style, Stylize},
+        text::{Line, Span},
+        widgets::{Block, Borders, Clear, HighlightSpacing, List, ListItem, Padding, Paragraph},
+    },
+    similar::{ChangeTag, TextDiff},
+};
+
+pub fn draw(frame: &mut Frame, context: &mut Context) {
+    let dialog = context
+        .revisions
+        .as_mut()
+        .log_expect("revision dialog not found");
+
+    let [area] = Layout::horizontal([Length(100)])
+        .flex(Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::vertical([Length(28)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    let block = Block::bordered()
+        .bg(THEME.surface)
+        .fg(THEME.text)
+        .padding(Padding::new(2, 2, 1, 1))
+        .title(Line::from(format!("History of '{}'", dialog.note.name)).centered());
+    let inner_area = block.inner(area);
+    let [body_area, control_area] =
+        Layout::vertical([Percentage(100), Length(1)]).areas(inner_area);
+    let [list_area, diff_area] = Layout::horizontal([Length(24), Percentage(100)]).areas(body_area);
+
+    let items = dialog
+        .revisions
+        .iter()
+        .map(|revision| {
+            let created_at = revision
+                .created_at
+                .get(..19)
+                .unwrap_or(&revision.created_at);
+            ListItem::new(created_at.to_owned().fg(THEME.menu))
+        })
+        .collect::<Vec<_>>();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::RIGHT)
+                .border_style(Style::default().fg(THEME.inactive_text)),
+        )
+        .highlight_style(Style::new().fg(THEME.accent_text).bg(THEME.accent))
+        .highlight_symbol(" ")
+        .highlight_spacing(HighlightSpacing::Always);
+
+    let lines = match (dialog.revisions.is_empty(), dialog.selected_content()) {
+        (true, _) => vec![Line::from("No revisions yet".fg(THEME.hint))],
+        (false, None) => vec![Line::from("Loading...".fg(THEME.hint))],
+        (false, Some(content)) => diff_lines(&dialog.current, content),
+    };
+    let diff = Paragraph::new(lines).block(Block::default().padding(Padding::horizontal(1)));
+
+    let control = Line::from("[Enter] Restore  [j/k] Select  [Esc] Close".fg(THEME.text_secondary))
+        .centered();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_stateful_widget(list, list_area, &mut dialog.list_state);
+    frame.render_widget(diff, diff_area);
+    frame.render_widget(control, control_area);
+}
+
+/// Line diff from the current content to the selected revision, i.e. what
+/// restoring it would change.
+fn diff_lines(current: &str, revision: &str) -> Vec<Line<'static>> {
+    if current == revision {
+        return vec![Line::from("Same as the current content".fg(THEME.hint))];
+    }
+
+    TextDiff::from_lines(current, revision)
+        .iter_all_changes()
+        .map(|change| {
+            let text = change.value().trim_end_matches('\n').to_owned();
+            let (sign, style) = match change.tag() {
+                ChangeTag::Delete => ("- ", Style::default().fg(THEME.error)),
+                ChangeTag::Insert => ("+ ", Style::default().fg(THEME.success)),
+                ChangeTag::Equal => ("  ", Style::default().fg(THEME.text_secondary)),
+            };
+
+            Line::from(vec![Span::styled(sign, style), Span::styled(text, style)])
+        })
+        .collect()
+}
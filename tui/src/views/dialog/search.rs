@@ -0,0 +1,122 @@
+use {
+    crate::{
+        context::{Context, search::SearchDialog},
+        logger::*,
+        theme::THEME,
+    },
+    ratatui::{
+        Frame,
+        layout::{
+            Alignment,
+            Constraint::{Length, Percentage},
+            Flex, Layout, Position,
+        },
+        style::{Style, Stylize},
+        text::{Line, Span},
+        widgets::{
+            Block, Borders, Clear, HighlightSpacing, List, ListItem, Padding, Paragraph, Wrap,
+        },
+    },
+};
+
+pub fn draw(frame: &mut Frame, context: &mut Context) {
+    let search = context
+        .search
+        .as_mut()
+        .log_expect("search dialog not found");
+
+    let [area] = Layout::horizontal([Length(90)])
+        .flex(Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::vertical([Length(24)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    let block = Block::bordered()
+        .bg(THEME.surface)
+        .fg(THEME.text)
+        .padding(Padding::new(2, 2, 1, 1))
+        .title(Line::from("Search Notes").centered());
+    let inner_area = block.inner(area);
+    let [input_area, body_area, control_area] =
+        Layout::vertical([Length(3), Percentage(100), Length(1)]).areas(inner_area);
+    let [list_area, preview_area] =
+        Layout::horizontal([Percentage(40), Percentage(60)]).areas(body_area);
+
+    let input_block = Block::default()
+        .border_style(Style::default())
+        .borders(Borders::ALL);
+    let input_inner = input_block.inner(input_area);
+    let width = input_inner.width.max(1) as usize;
+    let scroll = search.input.visual_scroll(width);
+    let input_widget = Paragraph::new(search.query())
+        .scroll((0, scroll as u16))
+        .block(input_block);
+
+    let items = search
+        .results
+        .iter()
+        .map(|result| ListItem::new(result.note.name.clone().fg(THEME.menu)))
+        .collect::<Vec<_>>();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::RIGHT)
+                .border_style(Style::default().fg(THEME.inactive_text)),
+        )
+        .highlight_style(Style::new().fg(THEME.accent_text).bg(THEME.accent))
+        .highlight_symbol(" ")
+        .highlight_spacing(HighlightSpacing::Always);
+
+    let preview = Paragraph::new(preview_lines(search))
+        .wrap(Wrap { trim: false })
+        .block(Block::default().padding(Padding::horizontal(1)))
+        .alignment(Alignment::Left);
+
+    let control =
+        Line::from("[Enter] Open  [Up/Down] Select  [Esc] Close".fg(THEME.text_secondary))
+            .centered();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(input_widget, input_area);
+    frame.render_stateful_widget(list, list_area, &mut search.list_state);
+    frame.render_widget(preview, preview_area);
+    frame.render_widget(control, control_area);
+
+    let cursor_x = input_inner.x + (search.input.visual_cursor().max(scroll) - scroll) as u16;
+    let cursor_y = input_inner.y;
+    frame.set_cursor_position(Position::new(cursor_x, cursor_y));
+}
+
+fn preview_lines(search: &SearchDialog) -> Vec<Line<'static>> {
+    if search.query().trim().is_empty() {
+        return vec![Line::from(
+            "Type to search notes by name or content".fg(THEME.hint),
+        )];
+    }
+
+    let Some(result) = search.selected() else {
+        return vec![Line::from("No matching notes".fg(THEME.hint))];
+    };
+
+    let mut lines = vec![
+        Line::from(result.note.name.clone().fg(THEME.accent)),
+        Line::default(),
+    ];
+    if result.snippets.is_empty() {
+        lines.push(Line::from("Matched by note name".fg(THEME.hint)));
+    }
+
+    lines.extend(result.snippets.iter().map(|snippet| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:>4} ", snippet.line_number),
+                Style::default().fg(THEME.text_secondary),
+            ),
+            Span::styled(snippet.text.clone(), Style::default().fg(THEME.text)),
+        ])
+    }));
+
+    lines
+}
@@ -0,0 +1,79 @@
+use {
+    crate::{context::Context, logger::*, theme::THEME},
+    glues_core::template::TEMPLATES_DIRECTORY,
+    ratatui::{
+        Frame,
+        layout::{
+            Alignment,
+            Constraint::{Length, Percentage},
+            Flex, Layout,
+        },
+        style::{Style, Stylize},
+        text::{Line, Span},
+        widgets::{Block, Clear, HighlightSpacing, List, ListItem, Padding, Paragraph},
+    },
+};
+
+const NOTE_SYMBOL: &str = "󱇗 ";
+
+pub fn draw(frame: &mut Frame, context: &mut Context) {
+    let dialog = context
+        .template
+        .as_mut()
+        .log_expect("template dialog not found");
+
+    let [area] = Layout::horizontal([Length(60)])
+        .flex(Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::vertical([Length(20)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    let block = Block::bordered()
+        .bg(THEME.surface)
+        .fg(THEME.text)
+        .padding(Padding::new(2, 2, 1, 1))
+        .title(Line::from("Templates").centered());
+    let inner_area = block.inner(area);
+    let [list_area, control_area] =
+        Layout::vertical([Percentage(100), Length(1)]).areas(inner_area);
+
+    let control =
+        Line::from("[Enter] Use  [j/k] Select  [Esc] Close".fg(THEME.text_secondary)).centered();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(control, control_area);
+
+    if dialog.templates.is_empty() {
+        let message = Paragraph::new(vec![
+            Line::from("No templates yet".fg(THEME.hint)),
+            Line::default(),
+            Line::from(
+                format!("Add notes to a top-level \"{TEMPLATES_DIRECTORY}\" directory")
+                    .fg(THEME.hint),
+            ),
+            Line::from("to use them here.".fg(THEME.hint)),
+        ])
+        .alignment(Alignment::Center);
+        frame.render_widget(message, list_area);
+        return;
+    }
+
+    let items = dialog
+        .templates
+        .iter()
+        .map(|note| {
+            ListItem::new(Line::from(vec![
+                Span::raw(NOTE_SYMBOL).fg(THEME.text_secondary),
+                Span::raw(note.name.clone()).fg(THEME.menu),
+            ]))
+        })
+        .collect::<Vec<_>>();
+    let list = List::new(items)
+        .highlight_style(Style::new().fg(THEME.accent_text).bg(THEME.accent))
+        .highlight_symbol(" ")
+        .highlight_spacing(HighlightSpacing::Always);
+
+    frame.render_stateful_widget(list, list_area, &mut dialog.list_state);
+}
@@ -0,0 +1,75 @@
+use {
+    crate::{
+        context::{Context, trash::TrashEntry},
+        logger::*,
+        theme::THEME,
+    },
+    ratatui::{
+        Frame,
+        layout::{
+            Alignment,
+            Constraint::{Length, Percentage},
+            Flex, Layout,
+        },
+        style::{Style, Stylize},
+        text::{Line, Span},
+        widgets::{Block, Clear, HighlightSpacing, List, ListItem, Padding, Paragraph},
+    },
+};
+
+const DIRECTORY_SYMBOL: &str = "󰉋 ";
+const NOTE_SYMBOL: &str = "󱇗 ";
+
+pub fn draw(frame: &mut Frame, context: &mut Context) {
+    let trash = context.trash.as_mut().log_expect("trash dialog not found");
+
+    let [area] = Layout::horizontal([Length(60)])
+        .flex(Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::vertical([Length(20)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    let block = Block::bordered()
+        .bg(THEME.surface)
+        .fg(THEME.text)
+        .padding(Padding::new(2, 2, 1, 1))
+        .title(Line::from("Trash").centered());
+    let inner_area = block.inner(area);
+    let [list_area, control_area] =
+        Layout::vertical([Percentage(100), Length(1)]).areas(inner_area);
+
+    let control =
+        Line::from("[r] Restore  [D] Empty trash  [Esc] Close".fg(THEME.text_secondary)).centered();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(control, control_area);
+
+    if trash.is_empty() {
+        let message = Paragraph::new("Trash is empty".fg(THEME.hint)).alignment(Alignment::Center);
+        frame.render_widget(message, list_area);
+        return;
+    }
+
+    let items = trash
+        .entries()
+        .map(|entry| {
+            let (symbol, name) = match entry {
+                TrashEntry::Directory(directory) => (DIRECTORY_SYMBOL, directory.name.clone()),
+                TrashEntry::Note(note) => (NOTE_SYMBOL, note.name.clone()),
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::raw(symbol).fg(THEME.text_secondary),
+                Span::raw(name).fg(THEME.menu),
+            ]))
+        })
+        .collect::<Vec<_>>();
+    let list = List::new(items)
+        .highlight_style(Style::new().fg(THEME.accent_text).bg(THEME.accent))
+        .highlight_symbol(" ")
+        .highlight_spacing(HighlightSpacing::Always);
+
+    frame.render_stateful_widget(list, list_area, &mut trash.list_state);
+}
@@ -25,6 +25,7 @@ async fn closing_tab_then_reopening_still_panics() -> Result<()> {
     t.press('m').await;
     t.draw()?;
     t.press('j').await;
+    t.press('j').await;
     t.key(KeyCode::Enter).await;
     t.draw()?;
     for ch in "Tmp".chars() {
@@ -79,6 +80,7 @@ async fn moving_note_between_directories_still_panics() -> Result<()> {
     t.press('m').await;
     t.draw()?;
     t.press('j').await;
+    t.press('j').await;
     t.key(KeyCode::Enter).await;
     t.draw()?;
     snap!(t, "moving_note_open");
@@ -105,6 +107,7 @@ async fn moving_note_between_directories_still_panics() -> Result<()> {
     t.press('m').await;
     t.draw()?;
     t.press('j').await;
+    t.press('j').await;
     t.key(KeyCode::Enter).await;
     t.draw()?;
     for ch in "Dst".chars() {
@@ -0,0 +1,141 @@
+#[macro_use]
+mod tester;
+use tester::Tester;
+
+use color_eyre::Result;
+use glues_core::types::NoteId;
+use glues_tui::input::KeyCode;
+
+/// Opens the sample note, lets another client save it, then edits and saves
+/// it here, which raises the conflict dialog.
+async fn edit_after_external_save(t: &mut Tester) -> Result<NoteId> {
+    t.open_instant().await?;
+    t.open_first_note().await?;
+
+    let note_id = {
+        let glues = t.app.glues_mut();
+        let db = glues.db.as_mut().expect("backend must be initialized");
+        let root_id = db.root_id();
+        let note = db
+            .fetch_notes(root_id)
+            .await
+            .expect("failed to fetch notes")
+            .remove(0);
+        db.update_note_content(note.id.clone(), "Theirs".to_owned(), None)
+            .await
+            .expect("failed to update note");
+        note.id
+    };
+
+    t.press('i').await;
+    t.type_str("Mine ").await;
+    t.key(KeyCode::Esc).await;
+    t.key(KeyCode::Tab).await;
+    t.draw()?;
+
+    Ok(note_id)
+}
+
+async fn stored_content(t: &mut Tester, note_id: NoteId) -> String {
+    let glues = t.app.glues_mut();
+    let db = glues.db.as_mut().expect("backend must be initialized");
+    db.fetch_note_content(note_id)
+        .await
+        .expect("failed to fetch note content")
+}
+
+#[tokio::test]
+async fn conflict_dialog_saves_copy() -> Result<()> {
+    let mut t = Tester::new().await?;
+    let note_id = edit_after_external_save(&mut t).await?;
+    snap!(t, "conflict_dialog");
+
+    t.press('c').await;
+    t.draw()?;
+    snap!(t, "conflict_saved_copy");
+    assert_eq!(t.editor_text(), "Theirs");
+    assert_eq!(stored_content(&mut t, note_id).await, "Theirs");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn conflict_dialog_overwrites_or_reloads() -> Result<()> {
+    let mut t = Tester::new().await?;
+    let note_id = edit_after_external_save(&mut t).await?;
+
+    t.press('o').await;
+    assert_eq!(stored_content(&mut t, note_id.clone()).await, "Mine Hi :D");
+
+    // the overwrite is the new base; the next save goes through
+    t.press('l').await;
+    t.press('i').await;
+    t.type_str("Again ").await;
+    t.key(KeyCode::Esc).await;
+    t.key(KeyCode::Tab).await;
+    assert_eq!(
+        stored_content(&mut t, note_id.clone()).await,
+        "Again Mine Hi :D"
+    );
+
+    let mut t = Tester::new().await?;
+    let note_id = edit_after_external_save(&mut t).await?;
+
+    t.press('r').await;
+    assert_eq!(t.editor_text(), "Theirs");
+    assert_eq!(stored_content(&mut t, note_id).await, "Theirs");
+
+    Ok(())
+}
+
+/// Saves the sample note from outside the TUI, as another process would.
+async fn save_externally(t: &mut Tester, content: &str) -> NoteId {
+    let glues = t.app.glues_mut();
+    let db = glues.db.as_mut().expect("backend must be initialized");
+    let root_id = db.root_id();
+    let note = db
+        .fetch_notes(root_id)
+        .await
+        .expect("failed to fetch notes")
+        .remove(0);
+    db.update_note_content(note.id.clone(), content.to_owned(), None)
+        .await
+        .expect("failed to update note");
+
+    note.id
+}
+
+#[tokio::test]
+async fn external_change_reloads_clean_editor() -> Result<()> {
+    let mut t = Tester::new().await?;
+    t.open_instant().await?;
+    t.open_first_note().await?;
+
+    save_externally(&mut t, "Theirs").await;
+    t.app.check_changes().await;
+    assert_eq!(t.editor_text(), "Theirs");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn external_change_prompts_dirty_editor() -> Result<()> {
+    let mut t = Tester::new().await?;
+    t.open_instant().await?;
+    t.open_first_note().await?;
+
+    t.press('i').await;
+    t.type_str("Mine ").await;
+    let note_id = save_externally(&mut t, "Theirs").await;
+    t.app.check_changes().await;
+    t.draw()?;
+    assert!(t.snapshot_text().contains("was changed elsewhere"));
+    assert_eq!(t.editor_text(), "Mine Hi :D");
+
+    t.key(KeyCode::Esc).await;
+    t.press('r').await;
+    assert_eq!(t.editor_text(), "Theirs");
+    assert_eq!(stored_content(&mut t, note_id).await, "Theirs");
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+#[macro_use]
+mod tester;
+use tester::Tester;
+
+use color_eyre::Result;
+use glues_tui::input::KeyCode;
+
+#[tokio::test]
+async fn history_dialog_restores_revision() -> Result<()> {
+    let mut t = Tester::new().await?;
+    t.open_instant().await?;
+    t.open_first_note().await?;
+
+    t.press('i').await;
+    t.type_str("Draft ").await;
+    t.key(KeyCode::Esc).await;
+    t.key(KeyCode::Tab).await;
+
+    t.press('m').await;
+    t.press('j').await;
+    t.press('j').await;
+    t.press('j').await;
+    t.key(KeyCode::Enter).await;
+    t.draw()?;
+    snap_sanitized!(t, "history_open");
+
+    t.press('j').await;
+    t.draw()?;
+    snap_sanitized!(t, "history_older_revision");
+
+    t.key(KeyCode::Enter).await;
+    t.press('y').await;
+    t.draw()?;
+    snap!(t, "history_restored");
+    assert!(!t.editor_text().contains("Draft"));
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+#[macro_use]
+mod tester;
+use tester::Tester;
+
+use color_eyre::Result;
+use glues_tui::input::KeyCode;
+use std::fs;
+
+#[tokio::test]
+async fn import_folder_from_directory_actions() -> Result<()> {
+    let src = std::env::temp_dir().join(format!("glues-import-{}", std::process::id()));
+    fs::create_dir_all(src.join("Archive"))?;
+    fs::write(src.join("Ideas.md"), "# Ideas")?;
+    fs::write(src.join("Archive/2023.txt"), "old notes")?;
+
+    let mut t = Tester::new().await?;
+    t.open_instant().await?;
+
+    // root directory is selected; open its actions and pick Import folder
+    t.press('m').await;
+    for _ in 0..6 {
+        t.press('j').await;
+    }
+    t.draw()?;
+    snap!(t, "import_folder_selected");
+
+    t.key(KeyCode::Enter).await;
+    t.type_str(src.to_str().expect("temp path should be utf-8"))
+        .await;
+    t.key(KeyCode::Enter).await;
+    t.draw()?;
+    snap!(t, "import_folder_done");
+
+    t.key(KeyCode::Esc).await;
+    t.draw()?;
+    snap!(t, "import_folder_tree");
+
+    fs::remove_dir_all(&src)?;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+#[macro_use]
+mod tester;
+use tester::Tester;
+
+use color_eyre::Result;
+use glues_core::{
+    journal,
+    state::{GetInner, NotebookState},
+};
+
+fn editing_note_name(t: &mut Tester) -> String {
+    let state: &NotebookState = t.app.glues_mut().state.get_inner().expect("notebook state");
+
+    state
+        .get_editing()
+        .expect("a note should be open")
+        .name
+        .clone()
+}
+
+#[tokio::test]
+async fn journal_opens_today_and_moves_between_days() -> Result<()> {
+    let mut t = Tester::new().await?;
+    t.open_instant().await?;
+
+    // from the note tree
+    t.press('g').await;
+    t.press('d').await;
+    t.draw()?;
+    let today = journal::today();
+    assert_eq!(editing_note_name(&mut t), journal::note_name(today));
+
+    // from the editor
+    t.press('g').await;
+    t.press('n').await;
+    let tomorrow = today.succ_opt().expect("tomorrow should exist");
+    assert_eq!(editing_note_name(&mut t), journal::note_name(tomorrow));
+
+    t.press('g').await;
+    t.press('p').await;
+    t.press('g').await;
+    t.press('p').await;
+    let yesterday = today.pred_opt().expect("yesterday should exist");
+    assert_eq!(editing_note_name(&mut t), journal::note_name(yesterday));
+    t.draw()?;
+    let screen = t.snapshot_text();
+    assert!(screen.contains("󰝰 Journal"));
+    assert!(screen.contains(&format!("󰝰 {}", today.format("%Y"))));
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+#[macro_use]
+mod tester;
+use tester::Tester;
+
+use color_eyre::Result;
+use glues_tui::input::KeyCode;
+
+async fn add_note(t: &mut Tester, name: &str, content: &str) {
+    let glues = t.app.glues_mut();
+    let db = glues.db.as_mut().expect("backend must be initialized");
+    let root_id = db.root_id();
+    let note = db
+        .add_note(root_id, name.to_owned())
+        .await
+        .expect("failed to add note");
+    db.update_note_content(note.id, content.to_owned(), None)
+        .await
+        .expect("failed to update note");
+}
+
+#[tokio::test]
+async fn follow_link_and_show_backlinks() -> Result<()> {
+    let mut t = Tester::new().await?;
+    t.open_instant().await?;
+    add_note(&mut t, "Target", "Linked from elsewhere").await;
+    t.open_first_note().await?;
+
+    t.press('i').await;
+    t.type_str("See [[target]]").await;
+    t.key(KeyCode::Esc).await;
+    t.press('h').await;
+    t.press('g').await;
+    t.press('f').await;
+    t.draw()?;
+    snap!(t, "link_followed");
+    assert_eq!(t.editor_text(), "Linked from elsewhere");
+
+    t.press('g').await;
+    t.press('b').await;
+    t.draw()?;
+    snap!(t, "backlinks_open");
+
+    t.key(KeyCode::Enter).await;
+    assert!(t.editor_text().starts_with("See [[target]]"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn follow_link_to_missing_note_alerts() -> Result<()> {
+    let mut t = Tester::new().await?;
+    t.open_instant().await?;
+    t.open_first_note().await?;
+
+    t.press('i').await;
+    t.type_str("[[Nowhere]]").await;
+    t.key(KeyCode::Esc).await;
+    t.press('h').await;
+    t.press('g').await;
+    t.press('f').await;
+    t.draw()?;
+    snap!(t, "link_missing");
+
+    Ok(())
+}
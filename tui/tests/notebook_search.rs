@@ -0,0 +1,43 @@
+#[macro_use]
+mod tester;
+use tester::Tester;
+
+use color_eyre::Result;
+use glues_tui::input::KeyCode;
+
+#[tokio::test]
+async fn search_dialog_jumps_to_note() -> Result<()> {
+    let mut t = Tester::new().await?;
+    t.open_instant().await?;
+
+    t.press('/').await;
+    t.draw()?;
+    snap!(t, "search_open");
+
+    t.type_str("hi").await;
+    t.draw()?;
+    snap!(t, "search_results");
+
+    t.key(KeyCode::Enter).await;
+    t.draw()?;
+    snap!(t, "search_jumped");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn search_dialog_closes_on_esc() -> Result<()> {
+    let mut t = Tester::new().await?;
+    t.open_instant().await?;
+
+    t.press('/').await;
+    t.type_str("missing").await;
+    t.draw()?;
+    snap!(t, "search_no_results");
+
+    t.key(KeyCode::Esc).await;
+    t.draw()?;
+    snap!(t, "search_closed");
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+#[macro_use]
+mod tester;
+use tester::Tester;
+
+use color_eyre::Result;
+use glues_tui::input::KeyCode;
+
+#[tokio::test]
+async fn add_note_from_template() -> Result<()> {
+    let mut t = Tester::new().await?;
+    t.open_instant().await?;
+    {
+        let glues = t.app.glues_mut();
+        let db = glues.db.as_mut().expect("backend must be initialized");
+        let templates = db
+            .add_directory(db.root_id(), "Templates".to_owned())
+            .await
+            .expect("failed to add directory");
+        let template = db
+            .add_note(templates.id, "Meeting".to_owned())
+            .await
+            .expect("failed to add note");
+        db.update_note_content(
+            template.id,
+            "# {{title}}\n\nFiled under {{directory}}".to_owned(),
+            None,
+        )
+        .await
+        .expect("failed to update note");
+    }
+
+    // root directory is selected; open its actions and pick the template entry
+    t.press('m').await;
+    t.press('j').await;
+    t.key(KeyCode::Enter).await;
+    t.draw()?;
+    snap!(t, "template_dialog_open");
+
+    t.key(KeyCode::Enter).await;
+    t.type_str("Kickoff").await;
+    t.key(KeyCode::Enter).await;
+    t.draw()?;
+    snap!(t, "template_note_added");
+
+    let glues = t.app.glues_mut();
+    let db = glues.db.as_mut().expect("backend must be initialized");
+    let note = db
+        .fetch_notes(db.root_id())
+        .await
+        .expect("failed to fetch notes")
+        .into_iter()
+        .find(|note| note.name == "Kickoff")
+        .expect("note should be added");
+    assert_eq!(
+        db.fetch_note_content(note.id)
+            .await
+            .expect("failed to fetch note content"),
+        "# Kickoff\n\nFiled under Notes"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn template_dialog_without_templates() -> Result<()> {
+    let mut t = Tester::new().await?;
+    t.open_instant().await?;
+
+    t.press('m').await;
+    t.press('j').await;
+    t.key(KeyCode::Enter).await;
+    t.draw()?;
+    snap!(t, "template_dialog_empty");
+
+    t.key(KeyCode::Esc).await;
+    t.draw()?;
+    snap!(t, "template_dialog_closed");
+
+    Ok(())
+}
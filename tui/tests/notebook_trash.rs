@@ -0,0 +1,57 @@
+#[macro_use]
+mod tester;
+use tester::Tester;
+
+use color_eyre::Result;
+use glues_tui::input::KeyCode;
+
+#[tokio::test]
+async fn trash_restores_removed_note() -> Result<()> {
+    let mut t = Tester::new().await?;
+    t.open_instant().await?;
+
+    t.press('j').await;
+    t.press('m').await;
+    t.press('j').await;
+    t.key(KeyCode::Enter).await;
+    t.press('y').await;
+    t.draw()?;
+    snap!(t, "trash_note_removed");
+
+    t.press('t').await;
+    t.draw()?;
+    snap!(t, "trash_open");
+
+    t.press('r').await;
+    t.draw()?;
+    snap!(t, "trash_restored");
+
+    t.key(KeyCode::Esc).await;
+    t.draw()?;
+    snap!(t, "trash_closed");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn trash_empties_after_confirm() -> Result<()> {
+    let mut t = Tester::new().await?;
+    t.open_instant().await?;
+
+    t.press('j').await;
+    t.press('m').await;
+    t.press('j').await;
+    t.key(KeyCode::Enter).await;
+    t.press('y').await;
+
+    t.press('t').await;
+    t.press('D').await;
+    t.draw()?;
+    snap!(t, "trash_empty_confirm");
+
+    t.press('y').await;
+    t.draw()?;
+    snap!(t, "trash_emptied");
+
+    Ok(())
+}
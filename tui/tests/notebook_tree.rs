@@ -132,6 +132,7 @@ async fn add_directory_via_directory_actions() -> Result<()> {
     t.press('m').await;
     t.draw()?;
     t.press('j').await;
+    t.press('j').await;
     t.key(KeyCode::Enter).await;
     t.draw()?;
     snap!(t, "add_dir_prompt_open");
@@ -212,6 +213,7 @@ async fn remove_directory_confirm_cancel_then_accept() -> Result<()> {
     // first add a directory so we can remove it
     t.press('m').await;
     t.draw()?;
+    t.press('j').await;
     t.press('j').await; // Add directory
     t.key(KeyCode::Enter).await;
     for ch in "Tmp".chars() {
@@ -220,13 +222,12 @@ async fn remove_directory_confirm_cancel_then_accept() -> Result<()> {
     t.key(KeyCode::Enter).await;
     t.draw()?;
 
-    // select the new directory (first child under root)
-    t.press('j').await;
+    // the new directory stays selected after it is added
     t.draw()?;
 
     // open directory actions and choose Remove directory
     t.press('m').await;
-    for _ in 0..3 {
+    for _ in 0..4 {
         t.press('j').await;
     }
     t.key(KeyCode::Enter).await;
@@ -238,11 +239,7 @@ async fn remove_directory_confirm_cancel_then_accept() -> Result<()> {
     t.draw()?;
     snap!(t, "remove_dir_cancelled");
 
-    // confirm remove
-    t.press('m').await;
-    for _ in 0..3 {
-        t.press('j').await;
-    }
+    // confirm remove; the actions dialog stays open on Remove directory
     t.key(KeyCode::Enter).await;
     t.press('y').await;
     t.draw()?;
@@ -296,7 +293,7 @@ async fn directory_info_dialog_shows_metadata() -> Result<()> {
 
     t.press('m').await;
     t.draw()?;
-    for _ in 0..4 {
+    for _ in 0..5 {
         t.press('j').await;
     }
     t.key(KeyCode::Enter).await;
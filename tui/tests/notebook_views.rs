@@ -98,6 +98,7 @@ async fn notebook_editor_states() -> Result<()> {
     t.press('k').await;
     t.press('m').await;
     t.press('j').await;
+    t.press('j').await;
     t.key(KeyCode::Enter).await;
     t.type_str("Dir").await;
     t.key(KeyCode::Enter).await;
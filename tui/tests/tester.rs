@@ -216,6 +216,12 @@ fn sanitize_snapshot(text: &str) -> String {
         .replace_all(&sanitized, "${1}1970-01-01T00:00:00.000000Z")
         .into_owned();
 
+    static TIMESTAMP: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"\d{4}-\d{2}-\d{2}[ T]\d{2}:\d{2}:\d{2}").unwrap());
+    sanitized = TIMESTAMP
+        .replace_all(&sanitized, "1970-01-01T00:00:00")
+        .into_owned();
+
     // remove trailing spaces so snapshots stay stable across environments
     sanitized = sanitized
         .lines()